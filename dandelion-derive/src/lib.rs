@@ -0,0 +1,126 @@
+//! Derive macro for `dandelion::FromRng`.
+//!
+//! This crate is an implementation detail of `dandelion-random`. Use it
+//! through the `derive` feature of that crate.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Error;
+use syn::Fields;
+use syn::LitInt;
+use syn::parse_macro_input;
+use syn::parse_quote;
+
+/// Derives `dandelion::FromRng`.
+///
+/// Struct fields are sampled in declaration order. Enum variants are chosen
+/// uniformly at random, or in proportion to their `#[random(weight = N)]`
+/// attributes when present, where the weight of an unannotated variant is 1.
+
+#[proc_macro_derive(Random, attributes(random))]
+pub fn derive_random(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+  match expand(input) {
+    Ok(tokens) => tokens.into(),
+    Err(err) => err.to_compile_error().into(),
+  }
+}
+
+fn expand(mut input: DeriveInput) -> Result<TokenStream, Error> {
+  for param in input.generics.type_params_mut() {
+    param.bounds.push(parse_quote!(::dandelion::FromRng));
+  }
+
+  let name = &input.ident;
+  let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+  let body =
+    match &input.data {
+      Data::Struct(data) => {
+        construct(quote!(#name), &data.fields)
+      }
+      Data::Enum(data) => {
+        if data.variants.is_empty() {
+          return Err(Error::new_spanned(name, "cannot derive `Random` for an empty enum"));
+        }
+
+        let mut total = 0u64;
+        let mut arms = Vec::new();
+
+        for variant in &data.variants {
+          let weight = weight(&variant.attrs)?;
+          if weight == 0 { continue; }
+          let ident = &variant.ident;
+          let value = construct(quote!(#name::#ident), &variant.fields);
+          total =
+            match total.checked_add(weight) {
+              Some(total) => total,
+              None => return Err(Error::new_spanned(variant, "total weight is too large")),
+            };
+          let bound = total - 1;
+          arms.push(quote!(if k <= #bound { return #value; }));
+        }
+
+        if total == 0 {
+          return Err(Error::new_spanned(name, "at least one variant must have a nonzero weight"));
+        }
+
+        let max = total - 1;
+
+        quote! {
+          let k = rng.bounded_u64(#max);
+          #(#arms)*
+          ::core::unreachable!()
+        }
+      }
+      Data::Union(data) => {
+        return Err(Error::new_spanned(data.union_token, "cannot derive `Random` for a union"));
+      }
+    };
+
+  Ok(quote! {
+    impl #impl_generics ::dandelion::FromRng for #name #ty_generics #where_clause {
+      #[allow(unused_variables)]
+      fn from_rng<__C: ::dandelion::Core>(rng: &mut ::dandelion::Rng<__C>) -> Self {
+        #body
+      }
+    }
+  })
+}
+
+fn construct(path: TokenStream, fields: &Fields) -> TokenStream {
+  match fields {
+    Fields::Named(fields) => {
+      let names = fields.named.iter().map(|f| &f.ident);
+      quote!(#path { #(#names: ::dandelion::FromRng::from_rng(rng)),* })
+    }
+    Fields::Unnamed(fields) => {
+      let values = fields.unnamed.iter().map(|_| quote!(::dandelion::FromRng::from_rng(rng)));
+      quote!(#path(#(#values),*))
+    }
+    Fields::Unit => {
+      path
+    }
+  }
+}
+
+fn weight(attrs: &[syn::Attribute]) -> Result<u64, Error> {
+  let mut weight = 1;
+
+  for attr in attrs {
+    if ! attr.path().is_ident("random") { continue; }
+
+    attr.parse_nested_meta(|meta| {
+      if meta.path.is_ident("weight") {
+        weight = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+        Ok(())
+      } else {
+        Err(meta.error("unsupported `random` attribute"))
+      }
+    })?;
+  }
+
+  Ok(weight)
+}
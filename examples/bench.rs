@@ -3,6 +3,8 @@
 use std::time::Instant;
 use dandelion::Rng as Dandelion;
 use pcg_rand::OneseqDXsM12864 as PcgDxsm128;
+use rand::rngs::SmallRng;
+use rand_chacha::ChaCha8Rng;
 use rand_xoshiro::Xoroshiro128PlusPlus;
 use rand::Rng as _;
 use rand::RngCore as _;
@@ -60,6 +62,30 @@ impl Rng for Xoroshiro128PlusPlus {
   fn bytes(&mut self, buf: &mut [u8]) { self.fill_bytes(buf) }
 }
 
+impl Rng for SmallRng {
+  fn from_u64(n: u64) -> Self { Self::seed_from_u64(n) }
+  fn u64(&mut self) -> u64 { self.gen() }
+  fn between_u64(&mut self, lo: u64, hi: u64) -> u64 { self.gen_range(lo ..= hi) }
+  fn f64(&mut self) -> f64 { self.gen() }
+  fn bytes(&mut self, buf: &mut [u8]) { self.fill_bytes(buf) }
+}
+
+impl Rng for ChaCha8Rng {
+  fn from_u64(n: u64) -> Self { Self::seed_from_u64(n) }
+  fn u64(&mut self) -> u64 { self.gen() }
+  fn between_u64(&mut self, lo: u64, hi: u64) -> u64 { self.gen_range(lo ..= hi) }
+  fn f64(&mut self) -> f64 { self.gen() }
+  fn bytes(&mut self, buf: &mut [u8]) { self.fill_bytes(buf) }
+}
+
+impl Rng for fastrand::Rng {
+  fn from_u64(n: u64) -> Self { Self::with_seed(n) }
+  fn u64(&mut self) -> u64 { self.u64(..) }
+  fn between_u64(&mut self, lo: u64, hi: u64) -> u64 { self.u64(lo ..= hi) }
+  fn f64(&mut self) -> f64 { self.f64() }
+  fn bytes(&mut self, buf: &mut [u8]) { self.fill(buf) }
+}
+
 const OUTER: usize = 1024 * 16;
 const INNER: usize = 1024;
 const COUNT: usize = OUTER * INNER;
@@ -141,6 +167,27 @@ fn fill_8<T: Rng>(rng: &mut T, buf: &mut [Box<[u8]>; INNER]) {
   }
 }
 
+// Fills buffers of each size with random bytes, in total the same number of
+// bytes as the other benchmarks.
+
+#[inline(never)]
+fn fill_curve<T: Rng>(rng: &mut T) {
+  let mut buf = vec![0_u8; 1 << 20];
+
+  for log_size in (4 ..= 20).step_by(2) {
+    let size = 1 << log_size;
+    let reps = COUNT * 8 / size;
+    let start = Instant::now();
+    for _ in 0 .. reps {
+      rng.bytes(&mut buf[.. size]);
+      let _ = std::hint::black_box(&buf[.. size]);
+    }
+    let stop = Instant::now();
+    let e = stop.saturating_duration_since(start).as_nanos() as f64;
+    println!("{:6.3} ns / word - bytes fill {} B", e / COUNT as f64, size);
+  }
+}
+
 #[inline(never)]
 fn go<T: Rng>(name: &str) {
   let lo = 0;
@@ -181,6 +228,7 @@ fn go<T: Rng>(name: &str) {
   println!("{:6.3} ns / word - bytes bulk fill", e6 / COUNT as f64);
   println!("{:6.3} ns / word - bytes short", e7 / COUNT as f64);
   println!("{:6.3} ns / word - bytes short noinline", e8 / COUNT as f64);
+  fill_curve(&mut rng);
   println!("");
 }
 
@@ -189,4 +237,7 @@ fn main() {
   go::<Dandelion>("dandelion");
   go::<PcgDxsm128>("pcgdxsm128");
   go::<Xoroshiro128PlusPlus>("xoroshiro128++");
+  go::<fastrand::Rng>("fastrand (wyrand)");
+  go::<SmallRng>("smallrng");
+  go::<ChaCha8Rng>("chacha8");
 }
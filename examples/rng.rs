@@ -1,9 +1,18 @@
 //! Writes random bytes to stdout.
+//!
+//! When run with the arguments `dieharder COUNT`, instead writes `COUNT`
+//! random 32-bit integers in dieharder's ASCII input format, so that the
+//! output can be tested with
+//!
+//! ```text
+//! cargo run --release --example rng -- dieharder 100000000 > out.txt
+//! dieharder -g 202 -f out.txt -a
+//! ```
 
 use std::io::Write;
 use dandelion::Rng;
 
-fn main() {
+fn raw() {
   let mut rng = Rng::new([0; 15]);
   let mut out = std::io::stdout().lock();
   let buf = &mut [0u8; 65_536];
@@ -13,3 +22,37 @@ fn main() {
     if let Err(_) = out.write_all(buf) { break; }
   }
 }
+
+fn dieharder(count: u64) -> std::io::Result<()> {
+  let mut rng = Rng::new([0; 15]);
+  let mut out = std::io::BufWriter::new(std::io::stdout().lock());
+
+  writeln!(out, "#==================================================================")?;
+  writeln!(out, "# generator dandelion  seed = 0")?;
+  writeln!(out, "#==================================================================")?;
+  writeln!(out, "type: d")?;
+  writeln!(out, "count: {}", count)?;
+  writeln!(out, "numbit: 32")?;
+
+  for _ in 0 .. count {
+    writeln!(out, "{}", rng.u32())?;
+  }
+
+  out.flush()
+}
+
+fn main() {
+  let args: Vec<String> = std::env::args().skip(1).collect();
+
+  match args.as_slice() {
+    [] => raw(),
+    [mode, count] if mode == "dieharder" => {
+      let count = count.parse().expect("COUNT must be a non-negative integer");
+      let _ = dieharder(count);
+    }
+    _ => {
+      eprintln!("usage: rng [dieharder COUNT]");
+      std::process::exit(2);
+    }
+  }
+}
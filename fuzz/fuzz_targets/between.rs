@@ -0,0 +1,26 @@
+//! Checks that `between_*` stays within its inclusive range, including ranges
+//! that wrap around.
+
+#![no_main]
+
+use dandelion::Rng;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: ([u8; 15], u64, u64)| {
+  let (seed, lo, hi) = input;
+  let mut rng = Rng::new(seed);
+
+  for _ in 0 .. 16 {
+    let x = rng.between_u64(lo, hi);
+    assert!(x.wrapping_sub(lo) <= hi.wrapping_sub(lo));
+
+    let x = rng.between_i64(lo as i64, hi as i64);
+    assert!((x as u64).wrapping_sub(lo) <= hi.wrapping_sub(lo));
+
+    let x = rng.between_u32(lo as u32, hi as u32);
+    assert!(x.wrapping_sub(lo as u32) <= (hi as u32).wrapping_sub(lo as u32));
+
+    let x = rng.between_i32(lo as i32, hi as i32);
+    assert!((x as u32).wrapping_sub(lo as u32) <= (hi as u32).wrapping_sub(lo as u32));
+  }
+});
@@ -0,0 +1,16 @@
+//! Checks that `bounded_*` never exceeds its inclusive upper bound.
+
+#![no_main]
+
+use dandelion::Rng;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: ([u8; 15], u64)| {
+  let (seed, n) = input;
+  let mut rng = Rng::new(seed);
+
+  for _ in 0 .. 16 {
+    assert!(rng.bounded_u64(n) <= n);
+    assert!(rng.bounded_u32(n as u32) <= n as u32);
+  }
+});
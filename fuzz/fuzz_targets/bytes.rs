@@ -0,0 +1,27 @@
+//! Checks `bytes` against a reference implementation for arbitrary lengths
+//! and alignments.
+
+#![no_main]
+
+use dandelion::Rng;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: ([u8; 15], u16, u8)| {
+  let (seed, len, offset) = input;
+  let len = len as usize % 1024;
+  let offset = offset as usize % 16;
+
+  let mut rng = Rng::new(seed);
+  let mut buf = vec![0xa5_u8; offset + len + 16];
+  rng.bytes(&mut buf[offset .. offset + len]);
+
+  let mut ref_rng = Rng::new(seed);
+  let mut ref_buf = vec![0xa5_u8; offset + len + 16];
+  for chunk in ref_buf[offset .. offset + len].chunks_mut(8) {
+    let n = chunk.len();
+    chunk.copy_from_slice(&ref_rng.u64().to_le_bytes()[.. n]);
+  }
+
+  assert!(buf == ref_buf);
+  assert!(rng.state() == ref_rng.state());
+});
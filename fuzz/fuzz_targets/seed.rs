@@ -0,0 +1,13 @@
+//! Checks that distinct seeds always produce distinct initial states.
+
+#![no_main]
+
+use dandelion::Rng;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: ([u8; 15], [u8; 15], u64, u64)| {
+  let (a, b, x, y) = input;
+
+  assert!((a == b) == (Rng::new(a).state() == Rng::new(b).state()));
+  assert!((x == y) == (Rng::from_u64(x).state() == Rng::from_u64(y).state()));
+});
@@ -0,0 +1,65 @@
+//! Walker's alias method for sampling from discrete distributions.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::Core;
+use crate::Rng;
+
+// A table for sampling indices in proportion to a list of weights, in
+// constant time per sample.
+
+#[derive(Clone, Debug)]
+pub(crate) struct AliasTable {
+  threshold: Vec<u64>,
+  alias: Vec<usize>,
+}
+
+impl AliasTable {
+  // Builds a table with Vose's algorithm. Returns `None` if there are no
+  // weights, any weight is negative or not finite, or the weights sum to zero
+  // or overflow.
+
+  pub(crate) fn new(weights: &[f64]) -> Option<Self> {
+    let n = weights.len();
+
+    if n == 0 || ! weights.iter().all(|&w| w >= 0.0 && w.is_finite()) { return None; }
+
+    let total: f64 = weights.iter().sum();
+
+    if ! (total > 0.0 && total.is_finite()) { return None; }
+
+    // Each column is filled to a height of 1 with its own weight and an alias.
+
+    let mut p: Vec<f64> = weights.iter().map(|&w| w * n as f64 / total).collect();
+    let mut threshold = vec![u64::MAX; n];
+    let mut alias: Vec<usize> = (0 .. n).collect();
+    let mut small: Vec<usize> = (0 .. n).filter(|&i| p[i] < 1.0).collect();
+    let mut large: Vec<usize> = (0 .. n).filter(|&i| p[i] >= 1.0).collect();
+
+    while let (Some(&s), Some(&l)) = (small.last(), large.last()) {
+      let _ = small.pop();
+      threshold[s] = (p[s] * 18446744073709551616.0) as u64;
+      alias[s] = l;
+      p[l] = p[l] + p[s] - 1.0;
+      if p[l] < 1.0 {
+        let _ = large.pop();
+        small.push(l);
+      }
+    }
+
+    // Any columns left over are full, up to rounding error.
+
+    Some(Self { threshold, alias })
+  }
+
+  pub(crate) fn len(&self) -> usize {
+    self.threshold.len()
+  }
+
+  #[inline]
+  pub(crate) fn sample<C: Core>(&self, rng: &mut Rng<C>) -> usize {
+    let i = rng.bounded_u64(self.threshold.len() as u64 - 1) as usize;
+    if rng.u64() < self.threshold[i] { i } else { self.alias[i] }
+  }
+}
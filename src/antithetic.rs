@@ -0,0 +1,127 @@
+//! Antithetic variates and common random numbers.
+//!
+//! An [AntitheticRng] has two hands, [primary](AntitheticRng::primary) and
+//! [mirror](AntitheticRng::mirror), which are generators that consume one
+//! shared stream of draws in lockstep. The `k`th draw of the mirror hand is
+//! derived from the `k`th draw of the primary hand, no matter how the calls
+//! on the two hands are interleaved.
+//!
+//! ```
+//! use dandelion::Core;
+//! use dandelion::Rng;
+//! use dandelion::antithetic::AntitheticRng;
+//!
+//! fn simulate<C: Core>(rng: &mut Rng<C>) -> f64 {
+//!   rng.f64() * rng.f64()
+//! }
+//!
+//! let mut rng = AntitheticRng::new(Rng::from_u64(0));
+//! let mut total = 0.0;
+//! for _ in 0 .. 1000 {
+//!   total += simulate(&mut rng.primary()) + simulate(&mut rng.mirror());
+//! }
+//! let estimate = total / 2000.0;
+//! ```
+
+use alloc::collections::VecDeque;
+
+use crate::Core;
+use crate::Dandelion;
+use crate::Rng;
+
+/// A pair of synchronized generators for variance reduction.
+///
+/// In antithetic mode, created with [AntitheticRng::new], the mirror hand
+/// sees each draw with its top bit flipped. The float samplers [Rng::f64] and
+/// [Rng::f32] fold the sign of the draw, so this maps each sample `u` on the
+/// primary hand to `1 - u` on the mirror hand, up to rounding. Monotone
+/// transformations of those samples, as in inverse transform sampling, are
+/// then negatively correlated between the hands. The integer samplers on the
+/// mirror hand are correctly distributed but not antithetic.
+///
+/// In common random numbers mode, created with
+/// [AntitheticRng::common_random_numbers], both hands see the same draws,
+/// so that two variants of a simulation can be compared under the same
+/// randomness.
+///
+/// <div class="warning">
+///
+/// The draws that the leading hand has made and the other hand has not yet
+/// consumed are buffered, so memory use grows with the [lag](Self::lag)
+/// between the hands. It grows without bound if only one hand is used.
+/// Consume the same number of draws from both hands, for example by running
+/// each simulation on both hands in turn.
+///
+/// </div>
+
+pub struct AntitheticRng<C = Dandelion> {
+  core: C,
+  mask: u64,
+  buffer: VecDeque<u64>,
+  mirror_ahead: bool,
+}
+
+/// The core of one hand of an [AntitheticRng].
+
+pub struct Hand<'a, C = Dandelion> {
+  inner: &'a mut AntitheticRng<C>,
+  mirror: bool,
+}
+
+impl<C: Core> AntitheticRng<C> {
+  /// Creates an antithetic pair of generators that share the draws of `rng`.
+
+  pub fn new(rng: Rng<C>) -> Self {
+    Self { core: rng.into_core(), mask: 1 << 63, buffer: VecDeque::new(), mirror_ahead: false }
+  }
+
+  /// Creates a pair of generators that both see the draws of `rng`.
+
+  pub fn common_random_numbers(rng: Rng<C>) -> Self {
+    Self { core: rng.into_core(), mask: 0, buffer: VecDeque::new(), mirror_ahead: false }
+  }
+
+  /// Returns the primary hand.
+
+  pub fn primary(&mut self) -> Rng<Hand<'_, C>> {
+    Rng::from_core(Hand { inner: self, mirror: false })
+  }
+
+  /// Returns the mirror hand.
+
+  pub fn mirror(&mut self) -> Rng<Hand<'_, C>> {
+    Rng::from_core(Hand { inner: self, mirror: true })
+  }
+
+  /// Returns the number of draws that the leading hand is ahead of the
+  /// other.
+  ///
+  /// This is also the number of draws that are buffered, one `u64` each.
+
+  pub fn lag(&self) -> usize {
+    self.buffer.len()
+  }
+}
+
+impl<C: Core> Core for Hand<'_, C> {
+  #[inline]
+  fn next_u64(&mut self) -> u64 {
+    // The buffer holds the draws that the leading hand has made and the
+    // other hand has not yet consumed.
+
+    let inner = &mut *self.inner;
+
+    let x =
+      if inner.buffer.is_empty() || inner.mirror_ahead == self.mirror {
+        let x = inner.core.next_u64();
+        inner.buffer.push_back(x);
+        inner.mirror_ahead = self.mirror;
+        x
+      } else {
+        let Some(x) = inner.buffer.pop_front() else { unreachable!() };
+        x
+      };
+
+    if self.mirror { x ^ inner.mask } else { x }
+  }
+}
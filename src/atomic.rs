@@ -0,0 +1,470 @@
+//! Access a global random number generator that is shared between threads.
+//!
+//! Unlike [dandelion::thread_local](crate::thread_local), this module does not
+//! require `std`. The global generator is SplitMix64, whose 64-bit state is
+//! stored in a [portable_atomic::AtomicU64] and advanced with a single
+//! `fetch_add` for each draw. This is lock-free on targets with native 64-bit
+//! atomics. On targets without them, such as 32-bit microcontrollers, it uses
+//! the fallback implementation from `portable-atomic`, which takes a lock or
+//! disables interrupts.
+//!
+//! Each draw is handed out to exactly one caller, but the draws of concurrent
+//! calls may be interleaved, so a call that takes several draws does not take
+//! consecutive values from the sequence.
+//!
+//! The generator starts from a fixed state. Call [seed] or [seed_from] to seed
+//! it, for example from a hardware entropy source during initialization.
+//!
+//! If you want to generate many random numbers, you should create a local
+//! generator with [dandelion::atomic::split](split).
+
+use core::num::NonZeroU128;
+use core::ops::RangeBounds;
+use core::sync::atomic::Ordering::Relaxed;
+use core::time::Duration;
+#[cfg(not(loom))]
+use portable_atomic::AtomicU64;
+use crate::Core;
+use crate::Dandelion;
+use crate::RangeInt;
+use crate::Rng;
+use crate::bits;
+use crate::entropy::EntropySource;
+use crate::mix::mix64;
+
+#[cfg(not(loom))]
+static STATE: AtomicU64 = AtomicU64::new(0);
+
+#[cfg(not(loom))]
+static BANK: AtomicU64 = AtomicU64::new(bits::EMPTY_BANK);
+
+// Under loom, the globals are recreated for each execution of a model.
+
+#[cfg(loom)]
+loom::lazy_static! {
+  static ref STATE: loom::sync::atomic::AtomicU64 = loom::sync::atomic::AtomicU64::new(0);
+  static ref BANK: loom::sync::atomic::AtomicU64 = loom::sync::atomic::AtomicU64::new(bits::EMPTY_BANK);
+}
+
+const GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+
+// The core of the global generator. Its state is the shared counter, so every
+// draw advances the counter and no two draws see the same value.
+
+struct Global;
+
+impl Core for Global {
+  #[inline(always)]
+  fn next_u64(&mut self) -> u64 {
+    mix64(STATE.fetch_add(GAMMA, Relaxed).wrapping_add(GAMMA))
+  }
+}
+
+#[inline(always)]
+fn with<F, T>(f: F) -> T
+where
+  F: FnOnce(&mut Rng<Global>) -> T
+{
+  f(&mut Rng::from_core(Global))
+}
+
+/// Seeds the global generator with the given state.
+///
+/// Within a single thread, the same seed always produces the same sequence of
+/// values.
+
+pub fn seed(seed: u64) {
+  reset(seed);
+}
+
+/// Seeds the global generator from the given entropy source.
+///
+/// If the source fails, the generator is unchanged and the error is returned.
+
+pub fn seed_from<E: EntropySource + ?Sized>(source: &mut E) -> Result<(), E::Error> {
+  let mut buf = [0u8; 8];
+  source.fill(&mut buf)?;
+  reset(u64::from_le_bytes(buf));
+  Ok(())
+}
+
+// The globals are replaced with `swap` instead of `store`, so that every
+// update to them is a read-modify-write. Loom does not order a plain store
+// correctly against concurrent read-modify-writes.
+
+#[inline(always)]
+fn reset(state: u64) {
+  let _ = STATE.swap(state, Relaxed);
+  let _ = BANK.swap(bits::EMPTY_BANK, Relaxed);
+}
+
+/// Splits off a new random number generator with the default core, seeded
+/// from two draws of the global generator.
+
+pub fn split() -> Rng {
+  with(|rng| {
+    let x = rng.u64();
+    let y = rng.u64();
+    let s = x as u128 ^ (y as u128) << 64;
+    let s = s | 1;
+    let s = unsafe { NonZeroU128::new_unchecked(s) };
+    Rng::from_core(Dandelion::from_state(s))
+  })
+}
+
+/// See [Rng::bernoulli].
+
+pub fn bernoulli(p: f64) -> bool {
+  with(|rng| rng.bernoulli(p))
+}
+
+/// See [Rng::bernoulli_ratio].
+
+pub fn bernoulli_ratio(numerator: u64, denominator: u64) -> bool {
+  with(|rng| rng.bernoulli_ratio(numerator, denominator))
+}
+
+/// See [Rng::one_in].
+
+pub fn one_in(n: u64) -> bool {
+  with(|rng| rng.one_in(n))
+}
+
+/// See [Rng::bernoulli_mask64].
+
+pub fn bernoulli_mask64(p: f64) -> u64 {
+  with(|rng| rng.bernoulli_mask64(p))
+}
+
+/// See [Rng::mask_u32].
+
+pub fn mask_u32(k: u32) -> u32 {
+  with(|rng| rng.mask_u32(k))
+}
+
+/// See [Rng::mask_u64].
+
+pub fn mask_u64(k: u32) -> u64 {
+  with(|rng| rng.mask_u64(k))
+}
+
+/// Samples a `u64` whose low `n` bits are uniformly distributed and whose
+/// other bits are zero.
+///
+/// See [Rng::bits]. The global generator has its own bank of unused bits,
+/// and each banked bit is handed out to exactly one caller.
+///
+/// # Panics
+///
+/// Panics if `n > 64`.
+
+pub fn bits(n: u32) -> u64 {
+  assert!(n <= 64, "bits: n > 64");
+
+  // A new draw is made at most once, and is reused if the bank changes
+  // before it can be refilled.
+
+  let mut w = None;
+  let mut bank = BANK.load(Relaxed);
+  loop {
+    let (x, new) =
+      match bits::take_banked(bank, n) {
+        Some(t) => t,
+        None => bits::take_refilled(bank, *w.get_or_insert_with(|| with(|rng| rng.u64())), n),
+      };
+    match BANK.compare_exchange_weak(bank, new, Relaxed, Relaxed) {
+      Ok(_) => return x,
+      Err(t) => bank = t,
+    }
+  }
+}
+
+/// See [Rng::bool].
+
+pub fn bool() -> bool {
+  with(|rng| rng.bool())
+}
+
+/// See [Rng::i8].
+
+pub fn i8() -> i8 {
+  with(|rng| rng.i8())
+}
+
+/// See [Rng::i16].
+
+pub fn i16() -> i16 {
+  with(|rng| rng.i16())
+}
+
+/// See [Rng::i32].
+
+pub fn i32() -> i32 {
+  with(|rng| rng.i32())
+}
+
+/// See [Rng::i64].
+
+pub fn i64() -> i64 {
+  with(|rng| rng.i64())
+}
+
+/// See [Rng::i128].
+
+pub fn i128() -> i128 {
+  with(|rng| rng.i128())
+}
+
+/// See [Rng::isize].
+
+pub fn isize() -> isize {
+  with(|rng| rng.isize())
+}
+
+/// See [Rng::u8].
+
+pub fn u8() -> u8 {
+  with(|rng| rng.u8())
+}
+
+/// See [Rng::u16].
+
+pub fn u16() -> u16 {
+  with(|rng| rng.u16())
+}
+
+/// See [Rng::u32].
+
+pub fn u32() -> u32 {
+  with(|rng| rng.u32())
+}
+
+/// See [Rng::u64].
+
+pub fn u64() -> u64 {
+  with(|rng| rng.u64())
+}
+
+/// See [Rng::u128].
+
+pub fn u128() -> u128 {
+  with(|rng| rng.u128())
+}
+
+/// See [Rng::usize].
+
+pub fn usize() -> usize {
+  with(|rng| rng.usize())
+}
+
+/// See [Rng::bounded_u8].
+
+pub fn bounded_u8(n: u8) -> u8 {
+  with(|rng| rng.bounded_u8(n))
+}
+
+/// See [Rng::bounded_u16].
+
+pub fn bounded_u16(n: u16) -> u16 {
+  with(|rng| rng.bounded_u16(n))
+}
+
+/// See [Rng::bounded_u32].
+
+pub fn bounded_u32(n: u32) -> u32 {
+  with(|rng| rng.bounded_u32(n))
+}
+
+/// See [Rng::bounded_u64].
+
+pub fn bounded_u64(n: u64) -> u64 {
+  with(|rng| rng.bounded_u64(n))
+}
+
+/// See [Rng::bounded_u128].
+
+pub fn bounded_u128(n: u128) -> u128 {
+  with(|rng| rng.bounded_u128(n))
+}
+
+/// See [Rng::bounded_usize].
+
+pub fn bounded_usize(n: usize) -> usize {
+  with(|rng| rng.bounded_usize(n))
+}
+
+/// See [Rng::index].
+
+pub fn index(len: usize) -> usize {
+  with(|rng| rng.index(len))
+}
+
+/// See [Rng::shuffle].
+
+pub fn shuffle<T>(slice: &mut [T]) {
+  with(|rng| rng.shuffle(slice))
+}
+
+/// See [Rng::below_u32].
+
+pub fn below_u32(n: u32) -> u32 {
+  with(|rng| rng.below_u32(n))
+}
+
+/// See [Rng::below_u64].
+
+pub fn below_u64(n: u64) -> u64 {
+  with(|rng| rng.below_u64(n))
+}
+
+/// See [Rng::range].
+
+pub fn range<T, R>(range: R) -> T
+where
+  T: RangeInt,
+  R: RangeBounds<T>
+{
+  // The closure may be called more than once, so we pass it a copy of the
+  // bounds rather than the range itself.
+
+  let range = (range.start_bound().cloned(), range.end_bound().cloned());
+  with(|rng| rng.range(range))
+}
+
+/// See [Rng::between_i8].
+
+pub fn between_i8(lo: i8, hi: i8) -> i8 {
+  with(|rng| rng.between_i8(lo, hi))
+}
+
+/// See [Rng::between_i16].
+
+pub fn between_i16(lo: i16, hi: i16) -> i16 {
+  with(|rng| rng.between_i16(lo, hi))
+}
+
+/// See [Rng::between_i32].
+
+pub fn between_i32(lo: i32, hi: i32) -> i32 {
+  with(|rng| rng.between_i32(lo, hi))
+}
+
+/// See [Rng::between_i64].
+
+pub fn between_i64(lo: i64, hi: i64) -> i64 {
+  with(|rng| rng.between_i64(lo, hi))
+}
+
+/// See [Rng::between_i128].
+
+pub fn between_i128(lo: i128, hi: i128) -> i128 {
+  with(|rng| rng.between_i128(lo, hi))
+}
+
+/// See [Rng::between_isize].
+
+pub fn between_isize(lo: isize, hi: isize) -> isize {
+  with(|rng| rng.between_isize(lo, hi))
+}
+
+/// See [Rng::between_u8].
+
+pub fn between_u8(lo: u8, hi: u8) -> u8 {
+  with(|rng| rng.between_u8(lo, hi))
+}
+
+/// See [Rng::between_u16].
+
+pub fn between_u16(lo: u16, hi: u16) -> u16 {
+  with(|rng| rng.between_u16(lo, hi))
+}
+
+/// See [Rng::between_u32].
+
+pub fn between_u32(lo: u32, hi: u32) -> u32 {
+  with(|rng| rng.between_u32(lo, hi))
+}
+
+/// See [Rng::between_u64].
+
+pub fn between_u64(lo: u64, hi: u64) -> u64 {
+  with(|rng| rng.between_u64(lo, hi))
+}
+
+/// See [Rng::between_u128].
+
+pub fn between_u128(lo: u128, hi: u128) -> u128 {
+  with(|rng| rng.between_u128(lo, hi))
+}
+
+/// See [Rng::between_usize].
+
+pub fn between_usize(lo: usize, hi: usize) -> usize {
+  with(|rng| rng.between_usize(lo, hi))
+}
+
+/// See [Rng::f32].
+
+pub fn f32() -> f32 {
+  with(|rng| rng.f32())
+}
+
+/// See [Rng::f64].
+
+pub fn f64() -> f64 {
+  with(|rng| rng.f64())
+}
+
+/// See [Rng::between_f32].
+
+pub fn between_f32(lo: f32, hi: f32) -> f32 {
+  with(|rng| rng.between_f32(lo, hi))
+}
+
+/// See [Rng::between_f64].
+
+pub fn between_f64(lo: f64, hi: f64) -> f64 {
+  with(|rng| rng.between_f64(lo, hi))
+}
+
+/// See [Rng::f32_signed].
+
+pub fn f32_signed() -> f32 {
+  with(|rng| rng.f32_signed())
+}
+
+/// See [Rng::f64_signed].
+
+pub fn f64_signed() -> f64 {
+  with(|rng| rng.f64_signed())
+}
+
+/// See [Rng::bytes].
+
+pub fn bytes(dst: &mut [u8]) {
+  with(|rng| rng.bytes(dst))
+}
+
+/// See [Rng::fill_f32].
+
+pub fn fill_f32(dst: &mut [f32]) {
+  with(|rng| rng.fill_f32(dst))
+}
+
+/// See [Rng::fill_f64].
+
+pub fn fill_f64(dst: &mut [f64]) {
+  with(|rng| rng.fill_f64(dst))
+}
+
+/// See [Rng::duration_between].
+
+pub fn duration_between(lo: Duration, hi: Duration) -> Duration {
+  with(|rng| rng.duration_between(lo, hi))
+}
+
+/// See [Rng::byte_array].
+
+pub fn byte_array<const N: usize>() -> [u8; N] {
+  with(|rng| rng.byte_array())
+}
@@ -0,0 +1,130 @@
+//! Random permutations of the bits of a word.
+//!
+//! ```
+//! use dandelion::Rng;
+//!
+//! let mut rng = Rng::from_u64(0);
+//! let p = rng.random_bit_permutation();
+//! let x = 0x0123_4567_89ab_cdef;
+//! assert!(p.inverse().apply(p.apply(x)) == x);
+//! ```
+
+use crate::Core;
+use crate::Rng;
+
+/// A permutation of the 64 bit positions of a `u64`.
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct BitPermutation { map: [u8; 64] }
+
+impl BitPermutation {
+  /// The identity permutation.
+
+  pub const IDENTITY: Self = {
+    let mut map = [0; 64];
+    let mut i = 0;
+    while i < 64 {
+      map[i] = i as u8;
+      i += 1;
+    }
+    Self { map }
+  };
+
+  /// Creates a permutation that moves bit `i` to bit `map[i]`, or returns
+  /// `None` if `map` is not a permutation of `0 ... 63`.
+
+  pub fn from_array(map: [u8; 64]) -> Option<Self> {
+    let mut seen = 0u64;
+    for &j in &map {
+      if j >= 64 { return None; }
+      seen |= 1 << j;
+    }
+    if seen != u64::MAX { return None; }
+    Some(Self { map })
+  }
+
+  /// Returns the array that maps each bit position to its destination.
+
+  pub const fn as_array(&self) -> &[u8; 64] {
+    &self.map
+  }
+
+  /// Moves bit `i` of `x` to bit `map[i]`.
+
+  pub fn apply(&self, x: u64) -> u64 {
+    let mut y = 0;
+    for (i, &j) in self.map.iter().enumerate() {
+      y |= (x >> i & 1) << j;
+    }
+    y
+  }
+
+  /// Returns the inverse permutation.
+
+  pub fn inverse(&self) -> Self {
+    let mut map = [0; 64];
+    for (i, &j) in self.map.iter().enumerate() {
+      map[j as usize] = i as u8;
+    }
+    Self { map }
+  }
+}
+
+impl Default for BitPermutation {
+  fn default() -> Self {
+    Self::IDENTITY
+  }
+}
+
+impl<C: Core> Rng<C> {
+  /// Permutes the bits of `x` in a uniformly random order.
+  ///
+  /// The result is uniformly distributed over the words with the same number
+  /// of ones as `x`. Use [Rng::random_bit_permutation] to apply the same
+  /// permutation to many words.
+
+  pub fn shuffle_bits_u64(&mut self, x: u64) -> u64 {
+    // We choose the positions of the ones, or of the zeros if there are
+    // fewer, with Floyd's algorithm for sampling without replacement.
+
+    let k = x.count_ones();
+    let k_min = k.min(64 - k);
+    let mut y = 0u64;
+
+    for j in 64 - k_min .. 64 {
+      let t = 1 << self.bounded_u32(j);
+      y |= if y & t == 0 { t } else { 1 << j };
+    }
+
+    if k_min == k { y } else { ! y }
+  }
+
+  /// Samples a `u64` from the uniform distribution over the words with
+  /// exactly `k` ones.
+  ///
+  /// ```
+  /// use dandelion::Rng;
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// let x = rng.u64_with_popcount(3);
+  /// assert!(x.count_ones() == 3);
+  /// ```
+  ///
+  /// # Panics
+  ///
+  /// Panics if `k > 64`.
+
+  pub fn u64_with_popcount(&mut self, k: u32) -> u64 {
+    assert!(k <= 64, "u64_with_popcount: k > 64");
+    self.shuffle_bits_u64(u64::MAX.checked_shr(64 - k).unwrap_or(0))
+  }
+
+  /// Samples a permutation of the 64 bit positions of a `u64` from the
+  /// uniform distribution.
+
+  pub fn random_bit_permutation(&mut self) -> BitPermutation {
+    let mut map = BitPermutation::IDENTITY.map;
+    self.shuffle(&mut map);
+    BitPermutation { map }
+  }
+}
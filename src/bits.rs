@@ -0,0 +1,73 @@
+//! Sampling bits without wasting draws.
+
+use crate::Core;
+use crate::Rng;
+
+impl<C: Core> Rng<C> {
+  /// Samples a `u64` whose low `n` bits are uniformly distributed and whose
+  /// other bits are zero.
+  ///
+  /// The unused bits of each draw are banked and handed out on later calls,
+  /// so that, for example, 64 calls to `bits(1)` consume a single draw. The
+  /// other sampling methods draw from the core directly and leave the bank
+  /// alone.
+  ///
+  /// ```
+  /// use dandelion::Rng;
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// let flip = rng.bits(1) != 0;
+  /// let symbol = rng.bits(3);
+  /// ```
+  ///
+  /// # Panics
+  ///
+  /// Panics if `n > 64`.
+
+  #[inline]
+  pub fn bits(&mut self, n: u32) -> u64 {
+    assert!(n <= 64, "bits: n > 64");
+
+    let (x, bank) =
+      match take_banked(self.bank, n) {
+        Some(t) => t,
+        None => take_refilled(self.bank, self.u64(), n),
+      };
+
+    self.bank = bank;
+    x
+  }
+}
+
+#[inline(always)]
+fn low_mask(n: u32) -> u64 {
+  u64::MAX.checked_shr(64 - n).unwrap_or(0)
+}
+
+// The unused bits are banked in a single `u64`, with a sentinel bit just
+// above the banked bits, so that an empty bank is one and a bank of `k` bits
+// has a base-2 logarithm of `k`. At most 63 bits are banked.
+
+pub(crate) const EMPTY_BANK: u64 = 1;
+
+// Takes `n` bits from the bank, returning them and the new bank, or returns
+// `None` if the bank has fewer than `n` bits.
+
+#[inline(always)]
+pub(crate) fn take_banked(bank: u64, n: u32) -> Option<(u64, u64)> {
+  let k = bank.ilog2();
+  if n > k { return None; }
+  Some((bank & low_mask(n), bank >> n))
+}
+
+// Takes all of the banked bits and the rest of the `n` bits from a new draw,
+// returning them and the new bank.
+
+#[inline(always)]
+pub(crate) fn take_refilled(bank: u64, w: u64, n: u32) -> (u64, u64) {
+  let k = bank.ilog2();
+  let r = n - k;
+  let x = bank & low_mask(k) | (w & low_mask(r)) << k;
+  let m = (64 - r).min(63);
+  (x, w.checked_shr(r).unwrap_or(0) & low_mask(m) | 1 << m)
+}
@@ -0,0 +1,68 @@
+//! Sampling elements of slices.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::Core;
+use crate::Rng;
+
+impl<C: Core> Rng<C> {
+  /// Moves a uniformly random selection of `k` distinct elements of the
+  /// slice, in uniformly random order, to its front, and returns the two
+  /// parts.
+  ///
+  /// This is the first `k` steps of the Fisher-Yates algorithm, so it takes
+  /// `k` draws, and it does not allocate. The order of the remaining elements
+  /// is unspecified.
+  ///
+  /// ```
+  /// use dandelion::Rng;
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// let mut balls: [u32; 49] = core::array::from_fn(|i| i as u32 + 1);
+  /// let (drawn, _) = rng.partial_shuffle(&mut balls, 6);
+  /// ```
+  ///
+  /// # Panics
+  ///
+  /// Panics if `k > slice.len()`.
+
+  pub fn partial_shuffle<'a, T>(&mut self, slice: &'a mut [T], k: usize) -> (&'a mut [T], &'a mut [T]) {
+    assert!(k <= slice.len(), "partial_shuffle: k > len");
+
+    let n = slice.len();
+
+    for i in 0 .. k {
+      let j = i + self.bounded_u64((n - 1 - i) as u64) as usize;
+      slice.swap(i, j);
+    }
+
+    slice.split_at_mut(k)
+  }
+
+  /// Samples `k` distinct elements of the slice, in uniformly random order.
+  ///
+  /// Every selection of `k` elements is equally likely. Elements at
+  /// different positions are distinct even if they compare equal.
+  ///
+  /// ```
+  /// use dandelion::Rng;
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// let tests = ["parse", "eval", "print", "gc", "jit"];
+  /// let smoke = rng.choose_multiple(&tests, 2);
+  /// ```
+  ///
+  /// # Panics
+  ///
+  /// Panics if `k > slice.len()`.
+
+  #[cfg(feature = "alloc")]
+  pub fn choose_multiple<'a, T>(&mut self, slice: &'a [T], k: usize) -> Vec<&'a T> {
+    assert!(k <= slice.len(), "choose_multiple: k > len");
+
+    let mut indices: Vec<usize> = (0 .. slice.len()).collect();
+    let (chosen, _) = self.partial_shuffle(&mut indices, k);
+    chosen.iter().map(|&i| &slice[i]).collect()
+  }
+}
@@ -0,0 +1,55 @@
+//! Sampling colors.
+
+use crate::Core;
+use crate::Rng;
+
+impl<C: Core> Rng<C> {
+  /// Samples a color from the uniform distribution on 24-bit RGB colors.
+
+  #[inline(always)]
+  pub fn rgb(&mut self) -> [u8; 3] {
+    let [r, g, b, _] = self.u32().to_le_bytes();
+    [r, g, b]
+  }
+
+  /// Samples a color from the uniform distribution on 32-bit RGBA colors.
+
+  #[inline(always)]
+  pub fn rgba(&mut self) -> [u8; 4] {
+    self.u32().to_le_bytes()
+  }
+
+  /// Samples an RGB color with a uniformly random hue and moderate saturation
+  /// and lightness.
+  ///
+  /// In the HSL color model, the saturation is in [0.55, 0.85] and the
+  /// lightness is in [0.45, 0.65], which avoids colors that are washed out,
+  /// nearly black, or nearly white. Such colors are easy to tell apart and
+  /// are suitable for visualizations and placeholders.
+
+  pub fn hsl_pleasing(&mut self) -> [u8; 3] {
+    let h = self.f64() * 6.0;
+    let s = 0.55 + 0.30 * self.f64();
+    let l = 0.45 + 0.20 * self.f64();
+
+    // The standard conversion from HSL to RGB.
+
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let k = h as u32;
+    let x = c * (1.0 - (h - (k & !1) as f64 - 1.0).abs());
+    let m = l - c / 2.0;
+
+    let (r, g, b) =
+      match k {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+      };
+
+    let byte = |v: f64| ((v + m) * 255.0 + 0.5) as u8;
+    [byte(r), byte(g), byte(b)]
+  }
+}
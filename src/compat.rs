@@ -0,0 +1,4 @@
+//! Facades that mimic the APIs of other random number crates, to ease
+//! migration.
+
+pub mod fastrand;
@@ -0,0 +1,188 @@
+//! Free functions with the same names and signatures as those of the
+//! `fastrand` crate, backed by the thread-local generator from
+//! [dandelion::thread_local](crate::thread_local).
+//!
+//! Migrating from `fastrand` is mostly a matter of replacing `fastrand::` with
+//! `dandelion::compat::fastrand::`. The sampled values differ from those of
+//! `fastrand` for the same seed.
+//!
+//! ```
+//! use dandelion::compat::fastrand;
+//!
+//! fastrand::seed(7);
+//! let die = fastrand::u32(1 ..= 6);
+//! let index = fastrand::usize(.. 10);
+//! let mut v = [1, 2, 3, 4];
+//! fastrand::shuffle(&mut v);
+//! ```
+//!
+//! Like `fastrand`, the range functions panic if the range is empty.
+
+use core::ops::Bound;
+use core::ops::RangeBounds;
+use crate::Charset;
+use crate::thread_local;
+
+macro_rules! integer {
+  ($($t:ident => $between:ident),*) => {
+    $(
+      #[doc = concat!("Samples a `", stringify!($t), "` from the uniform distribution over the range.")]
+
+      #[inline]
+      pub fn $t<R: RangeBounds<$t>>(range: R) -> $t {
+        let lo =
+          match range.start_bound() {
+            Bound::Included(&x) => x,
+            Bound::Excluded(&x) => x.checked_add(1).expect("empty range"),
+            Bound::Unbounded => $t::MIN,
+          };
+        let hi =
+          match range.end_bound() {
+            Bound::Included(&x) => x,
+            Bound::Excluded(&x) => x.checked_sub(1).expect("empty range"),
+            Bound::Unbounded => $t::MAX,
+          };
+        assert!(lo <= hi, "empty range");
+        thread_local::$between(lo, hi)
+      }
+    )*
+  }
+}
+
+integer! {
+  u8 => between_u8,
+  u16 => between_u16,
+  u32 => between_u32,
+  u64 => between_u64,
+  u128 => between_u128,
+  usize => between_usize,
+  i8 => between_i8,
+  i16 => between_i16,
+  i32 => between_i32,
+  i64 => between_i64,
+  i128 => between_i128,
+  isize => between_isize
+}
+
+/// Seeds the thread-local generator as with
+/// [Rng::from_u64](crate::Rng::from_u64).
+
+#[inline]
+pub fn seed(seed: u64) {
+  thread_local::seed(seed)
+}
+
+/// Samples a `bool` from the uniform distribution.
+
+#[inline]
+pub fn bool() -> bool {
+  thread_local::bool()
+}
+
+/// Samples a `f32` from the uniform distribution over [0, 1).
+///
+/// Unlike [dandelion::thread_local::f32](crate::thread_local::f32), this
+/// never returns 1, as in `fastrand`.
+
+#[inline]
+pub fn f32() -> f32 {
+  (thread_local::u32() >> 8) as f32 * f32::from_bits(0x3380_0000)
+}
+
+/// Samples a `f64` from the uniform distribution over [0, 1).
+///
+/// Unlike [dandelion::thread_local::f64](crate::thread_local::f64), this
+/// never returns 1, as in `fastrand`.
+
+#[inline]
+pub fn f64() -> f64 {
+  (thread_local::u64() >> 11) as f64 * f64::from_bits(0x3ca0_0000_0000_0000)
+}
+
+/// Samples a `char` from the uniform distribution over the Unicode scalar
+/// values in the range.
+
+#[inline]
+pub fn char<R: RangeBounds<char>>(range: R) -> char {
+  // The `char` ranges step over the surrogate code points.
+
+  let lo =
+    match range.start_bound() {
+      Bound::Included(&c) => c,
+      Bound::Excluded(&c) => (c ..= char::MAX).nth(1).expect("empty range"),
+      Bound::Unbounded => '\0',
+    };
+  let hi =
+    match range.end_bound() {
+      Bound::Included(&c) => c,
+      Bound::Excluded(&c) => ('\0' .. c).next_back().expect("empty range"),
+      Bound::Unbounded => char::MAX,
+    };
+  assert!(lo <= hi, "empty range");
+  thread_local::with(|rng| rng.between_char(lo, hi))
+}
+
+/// Samples an ASCII letter or digit from the uniform distribution.
+
+#[inline]
+pub fn alphanumeric() -> char {
+  thread_local::with(|rng| rng.ascii(Charset::Alphanumeric) as char)
+}
+
+/// Samples an ASCII lowercase letter from the uniform distribution.
+
+#[inline]
+pub fn lowercase() -> char {
+  thread_local::with(|rng| rng.ascii(Charset::Lowercase) as char)
+}
+
+/// Samples an ASCII uppercase letter from the uniform distribution.
+
+#[inline]
+pub fn uppercase() -> char {
+  thread_local::with(|rng| rng.ascii(Charset::Uppercase) as char)
+}
+
+/// Samples a digit in the given base from the uniform distribution. Digits
+/// above 9 are lowercase letters.
+///
+/// # Panics
+///
+/// Panics if `base` is zero or greater than 36.
+
+#[inline]
+pub fn digit(base: u32) -> char {
+  assert!(base != 0, "digit: base == 0");
+  assert!(base <= 36, "digit: base > 36");
+  let Some(c) = char::from_digit(thread_local::bounded_u32(base - 1), base) else { unreachable!() };
+  c
+}
+
+/// Fills the buffer with random bytes.
+
+#[inline]
+pub fn fill(dst: &mut [u8]) {
+  thread_local::bytes(dst)
+}
+
+/// Shuffles the slice uniformly at random.
+
+#[inline]
+pub fn shuffle<T>(slice: &mut [T]) {
+  thread_local::with(|rng| rng.shuffle(slice))
+}
+
+/// Chooses an item from the iterator uniformly at random, or returns `None`
+/// if it is empty.
+
+#[inline]
+pub fn choice<I>(iter: I) -> Option<I::Item>
+where
+  I: IntoIterator,
+  I::IntoIter: ExactSizeIterator
+{
+  let mut iter = iter.into_iter();
+  let n = iter.len();
+  if n == 0 { return None; }
+  iter.nth(thread_local::bounded_u64(n as u64 - 1) as usize)
+}
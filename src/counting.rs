@@ -0,0 +1,70 @@
+//! Counting random draws.
+//!
+//! A [CountingRng] counts the `u64` outputs drawn from an inner core. Tests can
+//! use it to check that a code path consumes no randomness, or a bounded
+//! amount, and profiling can use it to attribute generator usage.
+//!
+//! ```
+//! use dandelion::Rng;
+//!
+//! let mut rng = Rng::from_u64(0).counting();
+//! let _ = rng.bounded_u64(5);
+//! assert!(rng.outputs_drawn() == 2);
+//! ```
+
+use crate::Core;
+use crate::Dandelion;
+use crate::Rng;
+
+/// A core that counts the outputs of an inner core.
+
+#[derive(Clone)]
+pub struct Counter<C = Dandelion> {
+  core: C,
+  count: u64,
+}
+
+/// A random number generator that counts the outputs of an inner core.
+
+pub type CountingRng<C = Dandelion> = Rng<Counter<C>>;
+
+impl<C: Core> Core for Counter<C> {
+  #[inline(always)]
+  fn next_u64(&mut self) -> u64 {
+    self.count += 1;
+    self.core.next_u64()
+  }
+}
+
+impl<C: Core> Rng<C> {
+  /// Wraps the random number generator so that it counts its outputs,
+  /// starting from zero.
+
+  #[inline(always)]
+  pub fn counting(self) -> CountingRng<C> {
+    Rng::from_core(Counter { core: self.into_core(), count: 0 })
+  }
+}
+
+impl<C: Core> Rng<Counter<C>> {
+  /// Returns the number of `u64` outputs drawn so far.
+
+  #[inline(always)]
+  pub const fn outputs_drawn(&self) -> u64 {
+    self.core().count
+  }
+
+  /// Resets the count to zero.
+
+  #[inline(always)]
+  pub fn reset_count(&mut self) {
+    self.core_mut().count = 0;
+  }
+
+  /// Consumes the counting generator and returns the inner generator.
+
+  #[inline(always)]
+  pub fn into_inner(self) -> Rng<C> {
+    Rng::from_core(self.into_core().core)
+  }
+}
@@ -0,0 +1,46 @@
+//! Sampling decimal numbers.
+
+use crate::Core;
+use crate::Rng;
+use rust_decimal::Decimal;
+
+impl<C: Core> Rng<C> {
+  /// Samples a `Decimal` from the uniform distribution over the range
+  /// `lo ... hi`.
+  ///
+  /// The lower and upper bounds are inclusive. The result has the larger of
+  /// the scales of `lo` and `hi`, and every number in the range with that
+  /// scale is equally likely, so for example a range from `0.00` to `100.00`
+  /// samples whole cents. The sample is computed on the underlying integers,
+  /// with no rounding through a float.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `lo > hi`, or if `lo` and `hi` cannot both be represented with
+  /// the larger scale.
+
+  pub fn decimal_between(&mut self, lo: Decimal, hi: Decimal) -> Decimal {
+    assert!(lo <= hi, "decimal_between: lo > hi");
+
+    let scale = lo.scale().max(hi.scale());
+
+    // The mantissas have at most 96 bits, and so does a rescaled bound that
+    // is representable.
+
+    let rescale = |x: Decimal| {
+      10i128.checked_pow(scale - x.scale())
+        .and_then(|m| x.mantissa().checked_mul(m))
+        .filter(|&m| Decimal::try_from_i128_with_scale(m, scale).is_ok())
+        .expect("decimal_between: bounds cannot be represented with a common scale")
+    };
+
+    let a = rescale(lo);
+    let b = rescale(hi);
+    let t = a + self.bounded_u128((b - a) as u128) as i128;
+
+    // The result is between the bounds, so it is representable.
+
+    let Ok(t) = Decimal::try_from_i128_with_scale(t, scale) else { unreachable!() };
+    t
+  }
+}
@@ -0,0 +1,166 @@
+//! Rolling dice written in the usual tabletop notation.
+//!
+//! An expression is a sum of terms such as `3d6 + 2` or `1d20 - 1d4 + d%`.
+//! Each term is either a constant or a group of dice `NdM`, which is the sum
+//! of `N` dice with faces numbered `1` through `M`. The count `N` may be
+//! omitted for a single die, and `d%` is a percentile die with 100 faces.
+//! Letters may be in either case and whitespace is ignored.
+//!
+//! ```
+//! use dandelion::Rng;
+//! use dandelion::dice::DiceExpr;
+//!
+//! let mut rng = Rng::from_u64(0);
+//! let x = rng.roll("3d6+2");
+//! assert!(5 <= x && x <= 20);
+//!
+//! let damage = DiceExpr::parse("2d8 + 1d6 + 4").unwrap();
+//! let y = damage.roll(&mut rng);
+//! assert!(7 <= y && y <= 26);
+//! ```
+
+use crate::Core;
+use crate::Rng;
+
+/// The maximum number of groups of dice in a [DiceExpr].
+
+pub const MAX_GROUPS: usize = 8;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+struct Group {
+  count: u16,
+  sides: u32,
+  negative: bool,
+}
+
+/// A parsed dice expression.
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DiceExpr {
+  groups: [Group; MAX_GROUPS],
+  len: usize,
+  offset: i64,
+}
+
+struct Parser<'a> { bytes: &'a [u8] }
+
+impl Parser<'_> {
+  fn peek(&mut self) -> Option<u8> {
+    while let [c, rest @ ..] = self.bytes {
+      if ! c.is_ascii_whitespace() { return Some(*c); }
+      self.bytes = rest;
+    }
+    None
+  }
+
+  fn bump(&mut self) {
+    if let [_, rest @ ..] = self.bytes { self.bytes = rest; }
+  }
+
+  fn number(&mut self) -> Option<u64> {
+    // Returns `None` if there are no digits. Values too large to be useful
+    // saturate and are rejected by the caller.
+
+    let mut n = None;
+    while let Some(c @ b'0' ..= b'9') = self.peek() {
+      self.bump();
+      n = Some(n.unwrap_or(0u64).saturating_mul(10).saturating_add((c - b'0') as u64));
+    }
+    n
+  }
+}
+
+impl DiceExpr {
+  /// Parses a dice expression, returning `None` if it is invalid.
+  ///
+  /// Groups of dice may have at most 65535 dice, each die has between 1 and
+  /// 2³² - 1 faces, and constants must be less than 2³². There may be at most
+  /// [MAX_GROUPS] groups of dice.
+
+  pub fn parse(notation: &str) -> Option<Self> {
+    let mut p = Parser { bytes: notation.as_bytes() };
+    let mut expr = Self { groups: [Group::default(); MAX_GROUPS], len: 0, offset: 0 };
+    let mut first = true;
+
+    loop {
+      let negative =
+        match p.peek() {
+          None if ! first => return Some(expr),
+          Some(b'+') => { p.bump(); false }
+          Some(b'-') => { p.bump(); true }
+          _ if first => false,
+          _ => return None,
+        };
+
+      first = false;
+
+      let n = p.number();
+
+      if let Some(b'd' | b'D') = p.peek() {
+        p.bump();
+        let count = u16::try_from(n.unwrap_or(1)).ok()?;
+        let sides =
+          if let Some(b'%') = p.peek() {
+            p.bump();
+            100
+          } else {
+            u32::try_from(p.number()?).ok()?
+          };
+        if sides == 0 || expr.len == MAX_GROUPS { return None; }
+        expr.groups[expr.len] = Group { count, sides, negative };
+        expr.len += 1;
+      } else {
+        let k = u32::try_from(n?).ok()? as i64;
+        expr.offset += if negative { - k } else { k };
+      }
+    }
+  }
+
+  /// Returns the smallest possible result.
+
+  pub fn min(&self) -> i64 {
+    self.groups[.. self.len].iter().fold(self.offset, |acc, g| {
+      let k = if g.negative { g.count as i64 * g.sides as i64 } else { g.count as i64 };
+      if g.negative { acc - k } else { acc + k }
+    })
+  }
+
+  /// Returns the largest possible result.
+
+  pub fn max(&self) -> i64 {
+    self.groups[.. self.len].iter().fold(self.offset, |acc, g| {
+      let k = if g.negative { g.count as i64 } else { g.count as i64 * g.sides as i64 };
+      if g.negative { acc - k } else { acc + k }
+    })
+  }
+
+  /// Rolls the dice and returns the total.
+
+  pub fn roll<C: Core>(&self, rng: &mut Rng<C>) -> i64 {
+    let mut total = self.offset;
+    for g in &self.groups[.. self.len] {
+      let mut sum = g.count as i64;
+      for _ in 0 .. g.count {
+        sum += rng.bounded_u32(g.sides - 1) as i64;
+      }
+      total += if g.negative { - sum } else { sum };
+    }
+    total
+  }
+}
+
+impl<C: Core> Rng<C> {
+  /// Rolls the dice in the given expression and returns the total.
+  ///
+  /// See the [dice](crate::dice) module for the notation. To roll the same
+  /// expression repeatedly, parse it once with [DiceExpr::parse].
+  ///
+  /// # Panics
+  ///
+  /// Panics if the expression is invalid.
+
+  pub fn roll(&mut self, notation: &str) -> i64 {
+    let Some(expr) = DiceExpr::parse(notation) else { panic!("invalid dice expression") };
+    expr.roll(self)
+  }
+}
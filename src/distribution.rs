@@ -0,0 +1,356 @@
+//! Composable probability distributions.
+//!
+//! A [Distribution] is a reusable description of how to sample a value.
+//! Derived distributions can be built with combinators instead of new types.
+//!
+//! ```
+//! use dandelion::Rng;
+//! use dandelion::distribution::Distribution;
+//! use dandelion::distribution::from_fn;
+//!
+//! let mut rng = Rng::from_u64(0);
+//! let latency = from_fn(|rng| 10.0 / (1.0 - rng.f64())).map(|x| x.min(1000.0) as u32);
+//! let x = rng.sample(&latency);
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::marker::PhantomData;
+
+use crate::Core;
+use crate::Dandelion;
+use crate::FromRng;
+use crate::Rng;
+
+/// A probability distribution over values of type `Self::Output`.
+///
+/// The distribution can be sampled by any [Rng] whose core is `C`, which
+/// defaults to [Dandelion]. Most distributions in this crate implement the
+/// trait for every core.
+
+pub trait Distribution<C: Core = Dandelion> {
+  /// The type of the sampled values.
+
+  type Output;
+
+  /// Samples a value.
+
+  fn sample(&self, rng: &mut Rng<C>) -> Self::Output;
+
+  /// Applies a function to each sampled value.
+
+  #[inline(always)]
+  fn map<U, F>(self, f: F) -> Map<Self, F, C>
+  where
+    Self: Sized,
+    F: Fn(Self::Output) -> U
+  {
+    Map { inner: self, f, core: PhantomData }
+  }
+
+  /// Samples from both distributions, in order, and returns the pair.
+
+  #[inline(always)]
+  fn zip<D>(self, other: D) -> Zip<Self, D, C>
+  where
+    Self: Sized,
+    D: Distribution<C>
+  {
+    Zip { a: self, b: other, core: PhantomData }
+  }
+
+  /// Samples repeatedly until a value satisfies the predicate.
+  ///
+  /// The resulting distribution returns `None` if none of `max_tries` attempts
+  /// succeed.
+
+  #[inline(always)]
+  fn filter_reject<P>(self, pred: P, max_tries: usize) -> FilterReject<Self, P, C>
+  where
+    Self: Sized,
+    P: Fn(&Self::Output) -> bool
+  {
+    FilterReject { inner: self, pred, max_tries, core: PhantomData }
+  }
+
+  /// Erases the type of the distribution.
+
+  #[cfg(feature = "alloc")]
+  #[inline(always)]
+  fn boxed<'a>(self) -> BoxedDistribution<'a, Self::Output, C>
+  where
+    Self: Sized + 'a
+  {
+    BoxedDistribution(Box::new(self))
+  }
+}
+
+/// The distribution returned by [from_fn].
+
+#[derive(Clone, Copy)]
+pub struct FromFn<F>(F);
+
+/// The distribution returned by [random].
+
+pub struct Random<T>(PhantomData<fn() -> T>);
+
+// The combinators record the core that they sample with, so that it can be
+// inferred from the eventual call to `sample` even when the underlying
+// distributions work with any core.
+
+/// The distribution returned by [Distribution::map].
+
+pub struct Map<D, F, C = Dandelion> {
+  inner: D,
+  f: F,
+  core: PhantomData<fn() -> C>,
+}
+
+/// The distribution returned by [Distribution::zip].
+
+pub struct Zip<A, B, C = Dandelion> {
+  a: A,
+  b: B,
+  core: PhantomData<fn() -> C>,
+}
+
+/// The distribution returned by [Distribution::filter_reject].
+
+pub struct FilterReject<D, P, C = Dandelion> {
+  inner: D,
+  pred: P,
+  max_tries: usize,
+  core: PhantomData<fn() -> C>,
+}
+
+/// The uniform distribution over a fixed range of `u64`s.
+///
+/// The range arithmetic is done once when the distribution is created, so
+/// each sample costs a single multiplication in the common case. The samples
+/// are exactly uniform, as with [Rng::bounded_u64_exact].
+///
+/// ```
+/// use dandelion::Rng;
+/// use dandelion::distribution::Uniform;
+///
+/// let mut rng = Rng::from_u64(0);
+/// let die = Uniform::new(1, 6);
+/// let rolls = [rng.sample(&die), rng.sample(&die), rng.sample(&die)];
+/// ```
+
+#[derive(Clone, Copy, Debug)]
+pub struct Uniform {
+  lo: u64,
+  m: u64,
+  t: u64,
+}
+
+/// A type-erased distribution.
+
+#[cfg(feature = "alloc")]
+pub struct BoxedDistribution<'a, T, C: Core = Dandelion>(Box<dyn Distribution<C, Output = T> + 'a>);
+
+/// Creates a distribution from a sampling function.
+
+#[inline(always)]
+pub fn from_fn<C, T, F>(f: F) -> FromFn<F>
+where
+  C: Core,
+  F: Fn(&mut Rng<C>) -> T
+{
+  FromFn(f)
+}
+
+/// Creates a distribution that samples with [Rng::random].
+
+#[inline(always)]
+pub fn random<T: FromRng>() -> Random<T> {
+  Random(PhantomData)
+}
+
+impl Uniform {
+  /// Creates the uniform distribution over the range `lo ... hi`.
+  ///
+  /// The lower and upper bounds are inclusive, and the range can wrap around
+  /// from `u64::MAX` to `u64::MIN`, as with [Rng::between_u64].
+
+  #[inline]
+  pub const fn new(lo: u64, hi: u64) -> Self {
+    // Samples are taken with Lemire's method, with the rejection threshold
+    // `2⁶⁴ mod m` computed here. We represent `m = 2⁶⁴`, for the full range,
+    // as `m = 0`, which never rejects.
+
+    let m = hi.wrapping_sub(lo).wrapping_add(1);
+    let t = if m == 0 { 0 } else { m.wrapping_neg() % m };
+    Self { lo, m, t }
+  }
+
+  /// Creates the uniform distribution over the range `0 .. n`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `n == 0`.
+
+  #[inline]
+  pub const fn below(n: u64) -> Self {
+    assert!(n != 0, "Uniform::below: n == 0");
+    Self::new(0, n - 1)
+  }
+}
+
+impl<C, T, F> Distribution<C> for FromFn<F>
+where
+  C: Core,
+  F: Fn(&mut Rng<C>) -> T
+{
+  type Output = T;
+
+  #[inline(always)]
+  fn sample(&self, rng: &mut Rng<C>) -> T {
+    (self.0)(rng)
+  }
+}
+
+impl<T> Clone for Random<T> {
+  #[inline(always)]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<T> Copy for Random<T> {
+}
+
+impl<D: Clone, F: Clone, C> Clone for Map<D, F, C> {
+  #[inline(always)]
+  fn clone(&self) -> Self {
+    Map { inner: self.inner.clone(), f: self.f.clone(), core: PhantomData }
+  }
+}
+
+impl<D: Copy, F: Copy, C> Copy for Map<D, F, C> {
+}
+
+impl<A: Clone, B: Clone, C> Clone for Zip<A, B, C> {
+  #[inline(always)]
+  fn clone(&self) -> Self {
+    Zip { a: self.a.clone(), b: self.b.clone(), core: PhantomData }
+  }
+}
+
+impl<A: Copy, B: Copy, C> Copy for Zip<A, B, C> {
+}
+
+impl<D: Clone, P: Clone, C> Clone for FilterReject<D, P, C> {
+  #[inline(always)]
+  fn clone(&self) -> Self {
+    FilterReject {
+      inner: self.inner.clone(),
+      pred: self.pred.clone(),
+      max_tries: self.max_tries,
+      core: PhantomData,
+    }
+  }
+}
+
+impl<D: Copy, P: Copy, C> Copy for FilterReject<D, P, C> {
+}
+
+impl<C: Core, T: FromRng> Distribution<C> for Random<T> {
+  type Output = T;
+
+  #[inline(always)]
+  fn sample(&self, rng: &mut Rng<C>) -> T {
+    T::from_rng(rng)
+  }
+}
+
+impl<C, U, D, F> Distribution<C> for Map<D, F, C>
+where
+  C: Core,
+  D: Distribution<C>,
+  F: Fn(D::Output) -> U
+{
+  type Output = U;
+
+  #[inline(always)]
+  fn sample(&self, rng: &mut Rng<C>) -> U {
+    (self.f)(self.inner.sample(rng))
+  }
+}
+
+impl<C, A, B> Distribution<C> for Zip<A, B, C>
+where
+  C: Core,
+  A: Distribution<C>,
+  B: Distribution<C>
+{
+  type Output = (A::Output, B::Output);
+
+  #[inline(always)]
+  fn sample(&self, rng: &mut Rng<C>) -> Self::Output {
+    let a = self.a.sample(rng);
+    let b = self.b.sample(rng);
+    (a, b)
+  }
+}
+
+impl<C, D, P> Distribution<C> for FilterReject<D, P, C>
+where
+  C: Core,
+  D: Distribution<C>,
+  P: Fn(&D::Output) -> bool
+{
+  type Output = Option<D::Output>;
+
+  #[inline]
+  fn sample(&self, rng: &mut Rng<C>) -> Self::Output {
+    for _ in 0 .. self.max_tries {
+      let x = self.inner.sample(rng);
+      if (self.pred)(&x) { return Some(x); }
+    }
+    None
+  }
+}
+
+impl<C: Core> Distribution<C> for Uniform {
+  type Output = u64;
+
+  #[inline(always)]
+  fn sample(&self, rng: &mut Rng<C>) -> u64 {
+    if self.m == 0 { return self.lo.wrapping_add(rng.u64()); }
+
+    let mut t = Some(self.t);
+    loop {
+      if let Some(x) = rng.lemire_u64(self.m, &mut t) { return self.lo.wrapping_add(x); }
+    }
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<C: Core, T> Distribution<C> for BoxedDistribution<'_, T, C> {
+  type Output = T;
+
+  #[inline(always)]
+  fn sample(&self, rng: &mut Rng<C>) -> T {
+    self.0.sample(rng)
+  }
+}
+
+impl<C: Core, D: Distribution<C> + ?Sized> Distribution<C> for &D {
+  type Output = D::Output;
+
+  #[inline(always)]
+  fn sample(&self, rng: &mut Rng<C>) -> D::Output {
+    (**self).sample(rng)
+  }
+}
+
+impl<C: Core> Rng<C> {
+  /// Samples a value from a [Distribution].
+
+  #[inline(always)]
+  pub fn sample<D: Distribution<C> + ?Sized>(&mut self, distribution: &D) -> D::Output {
+    distribution.sample(self)
+  }
+}
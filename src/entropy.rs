@@ -0,0 +1,159 @@
+//! Sources of entropy for seeding generators.
+//!
+//! An [EntropySource] can be used with [Rng::from_entropy_source] and
+//! [dandelion::thread_local::seed_from](crate::thread_local::seed_from) to
+//! seed generators from a hardware random number generator, a sandbox
+//! service, or a deterministic source in tests, instead of the operating
+//! system.
+//!
+//! ```
+//! use dandelion::Rng;
+//!
+//! let mut trng = |buf: &mut [u8]| -> Result<(), ()> {
+//!   buf.fill(0x5a); // Read from the device here.
+//!   Ok(())
+//! };
+//! let rng = Rng::from_entropy_source(&mut trng).unwrap();
+//! ```
+
+use core::num::NonZeroU128;
+#[cfg(feature = "getrandom")]
+use crate::Core;
+use crate::Dandelion;
+use crate::Rng;
+#[cfg(feature = "cortex-m")]
+use crate::get_chunk;
+#[cfg(feature = "cortex-m")]
+use crate::get_chunk_mut;
+
+/// A source of random bytes.
+///
+/// This trait is implemented for closures with the signature
+/// `FnMut(&mut [u8]) -> Result<(), E>`.
+
+pub trait EntropySource {
+  /// The error returned when the source fails.
+
+  type Error;
+
+  /// Fills the buffer with random bytes.
+
+  fn fill(&mut self, buf: &mut [u8]) -> Result<(), Self::Error>;
+}
+
+/// The entropy source provided by the operating system, via the `getrandom`
+/// crate.
+
+#[cfg(feature = "getrandom")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OsEntropy;
+
+/// A random number generator that retrieves every output from the operating
+/// system.
+///
+/// This is much slower than the default generator, and should be reserved
+/// for the few places that need unpredictability. Because it has the same
+/// methods, code that is generic over the core can switch between the two.
+///
+/// ```
+/// use dandelion::OsRng;
+/// use dandelion::entropy::OsEntropy;
+///
+/// let mut rng = OsRng::from_core(OsEntropy);
+/// let token = rng.byte_array::<16>();
+/// ```
+///
+/// # Panics
+///
+/// Drawing from the generator panics if the operating system fails to
+/// provide entropy.
+
+#[cfg(feature = "getrandom")]
+pub type OsRng = Rng<OsEntropy>;
+
+#[cfg(feature = "getrandom")]
+impl Core for OsEntropy {
+  #[inline]
+  fn next_u64(&mut self) -> u64 {
+    let mut buf = [0u8; 8];
+    getrandom::getrandom(&mut buf).expect("getrandom::getrandom failed!");
+    u64::from_le_bytes(buf)
+  }
+}
+
+#[cfg(feature = "getrandom")]
+impl EntropySource for OsEntropy {
+  type Error = getrandom::Error;
+
+  #[inline(always)]
+  fn fill(&mut self, buf: &mut [u8]) -> Result<(), getrandom::Error> {
+    getrandom::getrandom(buf)
+  }
+}
+
+impl<E, F> EntropySource for F
+where
+  F: FnMut(&mut [u8]) -> Result<(), E>
+{
+  type Error = E;
+
+  #[inline(always)]
+  fn fill(&mut self, buf: &mut [u8]) -> Result<(), E> {
+    self(buf)
+  }
+}
+
+impl Rng {
+  /// Creates a random number generator with an initial state derived by
+  /// hashing the current values of the Cortex-M cycle counters and a salt.
+  ///
+  /// This is a seeding path for microcontrollers without a hardware random
+  /// number generator. It reads the SysTick current value register and, on
+  /// cores that have one, the DWT cycle counter. These must be enabled first,
+  /// or they will read as constants.
+  ///
+  /// <div class="warning">
+  ///
+  /// This is *NOT* suitable for cryptographic purposes. After a reset the
+  /// counters advance deterministically, so the seed only varies if this is
+  /// called after an event with unpredictable timing, such as user input or
+  /// waiting on an external oscillator. A per-device salt, such as a unique
+  /// device ID, keeps different devices from producing the same stream.
+  ///
+  /// </div>
+
+  #[cfg(feature = "cortex-m")]
+  #[inline(never)]
+  #[cold]
+  pub fn from_cortex_m_counters(salt: u64) -> Self {
+    // The DWT cycle counter does not exist on ARMv6-M.
+
+    #[cfg(not(all(target_arch = "arm", target_feature = "mclass", not(target_feature = "v7"))))]
+    let c = ::cortex_m::peripheral::DWT::cycle_count();
+    #[cfg(all(target_arch = "arm", target_feature = "mclass", not(target_feature = "v7")))]
+    let c = 0u32;
+
+    // The SysTick counter is 24 bits wide.
+
+    let t = ::cortex_m::peripheral::SYST::get_current();
+    let mut seed = [0u8; 15];
+    *get_chunk_mut(&mut seed, 0) = c.to_le_bytes();
+    *get_chunk_mut(&mut seed, 4) = *get_chunk::<u8, 3>(&t.to_le_bytes(), 0);
+    *get_chunk_mut(&mut seed, 7) = salt.to_le_bytes();
+    Self::new(seed)
+  }
+
+  /// Creates a random number generator with entropy retrieved from the given
+  /// source, or returns the error if the source fails.
+
+  #[inline(never)]
+  #[cold]
+  pub fn from_entropy_source<E: EntropySource + ?Sized>(source: &mut E) -> Result<Self, E::Error> {
+    let mut buf = [0u8; 16];
+    source.fill(&mut buf)?;
+    let s = u128::from_le_bytes(buf);
+    let s = s | 1;
+    let s = unsafe { NonZeroU128::new_unchecked(s) };
+    Ok(Self::from_core(Dandelion::from_state(s)))
+  }
+}
@@ -0,0 +1,91 @@
+//! Deterministic fault injection.
+//!
+//! A [FaultPlan] decides, for each fault-injection site in a test, whether
+//! that site fails. The decisions depend only on the seed, the rate, and the
+//! site, so a failing chaos test can be reproduced from its logged seed.
+//!
+//! ```
+//! use dandelion::fault::FaultPlan;
+//!
+//! const WRITE_BLOCK: u64 = 1;
+//! const SEND_ACK: u64 = 2;
+//!
+//! let plan = FaultPlan::new(0x1234, 0.1);
+//! println!("fault plan seed = {:#x}", plan.seed());
+//!
+//! if plan.should_fail(WRITE_BLOCK) {
+//!   // Simulate a disk error.
+//! }
+//!
+//! // The decision for a site never changes.
+//! assert!(plan.should_fail(SEND_ACK) == plan.should_fail(SEND_ACK));
+//! ```
+//!
+//! To fail some but not all of the visits to a site, give each visit its own
+//! id, for example by combining a site number with a counter.
+
+use crate::Rng;
+use crate::mix::mix128;
+
+/// A reproducible schedule of failures.
+
+#[derive(Clone, Copy, Debug)]
+pub struct FaultPlan {
+  seed: u64,
+  rate: f64,
+}
+
+impl FaultPlan {
+  /// Creates a plan in which each site fails independently with probability
+  /// `rate`.
+  ///
+  /// Rates <= 0 or NaN are treated as 0, and rates >= 1 are treated as 1, as
+  /// with [Rng::bernoulli].
+
+  pub const fn new(seed: u64, rate: f64) -> Self {
+    Self { seed, rate }
+  }
+
+  /// Returns the seed.
+
+  pub const fn seed(&self) -> u64 {
+    self.seed
+  }
+
+  /// Returns the failure rate.
+
+  pub const fn rate(&self) -> f64 {
+    self.rate
+  }
+
+  /// Returns whether the site with the given id fails.
+
+  #[inline]
+  pub fn should_fail(&self, site_id: u64) -> bool {
+    // Each site gets its own generator, seeded by a hash of the full pair.
+    // Combining the seed and the site id any more simply, say by xor, would
+    // make the decisions of different plans permutations of each other.
+
+    let k = mix128((self.seed as u128) << 64 | site_id as u128);
+    Rng::from_u64(k as u64 ^ (k >> 64) as u64).bernoulli(self.rate)
+  }
+
+  /// Returns the ids of the sites that fail, among the given sites, in order.
+  ///
+  /// ```
+  /// use dandelion::fault::FaultPlan;
+  ///
+  /// let plan = FaultPlan::new(7, 0.01);
+  /// for site in plan.failures(0 .. 1000) {
+  ///   println!("site {} will fail", site);
+  /// }
+  /// ```
+
+  pub fn failures<I>(&self, sites: I) -> impl Iterator<Item = u64>
+  where
+    I: IntoIterator<Item = u64>
+  {
+    let plan = *self;
+    sites.into_iter().filter(move |&site| plan.should_fail(site))
+  }
+}
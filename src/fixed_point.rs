@@ -0,0 +1,52 @@
+//! Sampling fixed-point numbers.
+
+use crate::Core;
+use crate::Rng;
+use fixed::traits::Fixed;
+use fixed::traits::FixedBits;
+
+impl<C: Core> Rng<C> {
+  /// Samples a fixed-point number from the uniform distribution over the
+  /// range `lo ... hi`.
+  ///
+  /// The lower and upper bounds are inclusive. Every representable number in
+  /// the range is equally likely. The sample is computed on the underlying
+  /// integers, with no rounding through a float.
+  ///
+  /// ```
+  /// use dandelion::Rng;
+  /// use fixed::types::I16F16;
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// let x = rng.fixed_between(I16F16::from_num(-1), I16F16::from_num(1));
+  /// ```
+  ///
+  /// # Panics
+  ///
+  /// Panics if `lo > hi`.
+
+  pub fn fixed_between<F: Fixed>(&mut self, lo: F, hi: F) -> F {
+    assert!(lo <= hi, "fixed_between: lo > hi");
+
+    // The bits are widened to 128 bits, where the difference between the
+    // bounds always fits in a `u128`, and the conversions cannot fail.
+
+    let lo = lo.to_bits();
+    let hi = hi.to_bits();
+
+    let bits =
+      if F::Bits::IS_SIGNED {
+        let (Ok(a), Ok(b)) = (TryInto::<i128>::try_into(lo), TryInto::<i128>::try_into(hi)) else { unreachable!() };
+        let t = a.wrapping_add(self.bounded_u128(b.wrapping_sub(a) as u128) as i128);
+        let Ok(t) = F::Bits::try_from(t) else { unreachable!() };
+        t
+      } else {
+        let (Ok(a), Ok(b)) = (TryInto::<u128>::try_into(lo), TryInto::<u128>::try_into(hi)) else { unreachable!() };
+        let t = a + self.bounded_u128(b - a);
+        let Ok(t) = F::Bits::try_from(t) else { unreachable!() };
+        t
+      };
+
+    F::from_bits(bits)
+  }
+}
@@ -0,0 +1,229 @@
+//! Generic sampling with [Rng::random].
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::Core;
+use crate::Rng;
+
+/// Types whose values can be sampled by a random number generator.
+///
+/// Integers, `bool`, and `char` are sampled uniformly over all of their
+/// values, while floats are sampled uniformly from `[0, 1]` as with [Rng::f64]
+/// and [Rng::f32]. Arrays and tuples sample their elements in order.
+///
+/// With the `derive` feature enabled, this trait can be implemented for
+/// structs and enums with `#[derive(dandelion::Random)]`.
+
+pub trait FromRng: Sized {
+  /// Samples a value.
+
+  fn from_rng<C: Core>(rng: &mut Rng<C>) -> Self;
+}
+
+macro_rules! impl_from_rng {
+  ($($t:ty => $f:ident),* $(,)?) => {
+    $(
+      impl FromRng for $t {
+        #[inline(always)]
+        fn from_rng<C: Core>(rng: &mut Rng<C>) -> Self {
+          rng.$f()
+        }
+      }
+    )*
+  }
+}
+
+impl_from_rng! {
+  bool => bool,
+  char => char,
+  i8 => i8,
+  i16 => i16,
+  i32 => i32,
+  i64 => i64,
+  i128 => i128,
+  isize => isize,
+  u8 => u8,
+  u16 => u16,
+  u32 => u32,
+  u64 => u64,
+  u128 => u128,
+  usize => usize,
+  f32 => f32,
+  f64 => f64,
+}
+
+impl<T: FromRng, const N: usize> FromRng for [T; N] {
+  #[inline]
+  fn from_rng<C: Core>(rng: &mut Rng<C>) -> Self {
+    core::array::from_fn(|_| T::from_rng(rng))
+  }
+}
+
+// Tuple elements are sampled from left to right.
+
+macro_rules! impl_from_rng_tuple {
+  ($($t:ident),*) => {
+    impl<$($t: FromRng),*> FromRng for ($($t,)*) {
+      #[inline]
+      fn from_rng<C: Core>(rng: &mut Rng<C>) -> Self {
+        ($($t::from_rng(rng),)*)
+      }
+    }
+  }
+}
+
+impl_from_rng_tuple!(T0);
+impl_from_rng_tuple!(T0, T1);
+impl_from_rng_tuple!(T0, T1, T2);
+impl_from_rng_tuple!(T0, T1, T2, T3);
+impl_from_rng_tuple!(T0, T1, T2, T3, T4);
+impl_from_rng_tuple!(T0, T1, T2, T3, T4, T5);
+impl_from_rng_tuple!(T0, T1, T2, T3, T4, T5, T6);
+impl_from_rng_tuple!(T0, T1, T2, T3, T4, T5, T6, T7);
+impl_from_rng_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8);
+impl_from_rng_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9);
+impl_from_rng_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
+impl_from_rng_tuple!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
+
+impl<C: Core> Rng<C> {
+  /// Samples a value of any type that implements [FromRng].
+  ///
+  /// ```
+  /// use dandelion::Rng;
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// let x: u64 = rng.random();
+  /// let y = rng.random::<f64>();
+  /// ```
+
+  #[inline(always)]
+  pub fn random<T: FromRng>(&mut self) -> T {
+    T::from_rng(self)
+  }
+
+  /// Creates a vector of `n` values sampled with [Rng::random].
+
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn random_vec<T: FromRng>(&mut self, n: usize) -> Vec<T> {
+    self.collect_random(n, T::from_rng)
+  }
+
+  /// Returns `Some` with probability `p`, and `None` otherwise.
+  ///
+  /// The closure, which receives the random number generator, is only called
+  /// to produce the value in the `Some` case.
+  ///
+  /// ```
+  /// use dandelion::Rng;
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// let age = rng.option(0.75, |rng| rng.between_u32(18, 99));
+  /// ```
+
+  #[inline]
+  pub fn option<T, F>(&mut self, p: f64, f: F) -> Option<T>
+  where
+    F: FnOnce(&mut Self) -> T
+  {
+    if self.bernoulli(p) { Some(f(self)) } else { None }
+  }
+
+  /// Returns `Ok` with probability `p`, and `Err` otherwise.
+  ///
+  /// Only the closure for the chosen variant is called.
+
+  #[inline]
+  pub fn result<T, E, F, G>(&mut self, p: f64, ok: F, err: G) -> Result<T, E>
+  where
+    F: FnOnce(&mut Self) -> T,
+    G: FnOnce(&mut Self) -> E
+  {
+    if self.bernoulli(p) { Ok(ok(self)) } else { Err(err(self)) }
+  }
+
+  /// Extends a collection with `n` items produced by `f`.
+  ///
+  /// For collections like sets and maps that merge duplicates, the collection
+  /// may grow by fewer than `n` items.
+  ///
+  /// ```
+  /// use dandelion::Rng;
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// let mut v = vec![0u32];
+  /// rng.extend_random(&mut v, 3, |rng| rng.bounded_u32(9));
+  /// assert!(v.len() == 4);
+  /// ```
+
+  #[inline]
+  pub fn extend_random<T, B, F>(&mut self, collection: &mut B, n: usize, mut f: F)
+  where
+    B: Extend<T>,
+    F: FnMut(&mut Self) -> T
+  {
+    collection.extend((0 .. n).map(|_| f(self)))
+  }
+
+  /// Creates a collection from `n` items produced by `f`.
+  ///
+  /// ```
+  /// use std::collections::BTreeMap;
+  /// use dandelion::Rng;
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// let m: BTreeMap<u32, bool> = rng.collect_random(8, |rng| (rng.u32(), rng.bool()));
+  /// ```
+
+  #[inline]
+  pub fn collect_random<T, B, F>(&mut self, n: usize, f: F) -> B
+  where
+    B: Default + Extend<T>,
+    F: FnMut(&mut Self) -> T
+  {
+    let mut collection = B::default();
+    self.extend_random(&mut collection, n, f);
+    collection
+  }
+
+  /// Samples values with `f` until one satisfies `accept`, and returns it.
+  ///
+  /// This loops forever if `f` never produces an acceptable value. Use
+  /// [Rng::try_sample_until] to bound the number of attempts.
+  ///
+  /// ```
+  /// use dandelion::Rng;
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// let odd = rng.sample_until(|rng| rng.u32(), |x| x % 2 == 1);
+  /// ```
+
+  #[inline]
+  pub fn sample_until<T, F, P>(&mut self, mut f: F, accept: P) -> T
+  where
+    F: FnMut(&mut Self) -> T,
+    P: Fn(&T) -> bool
+  {
+    loop {
+      let x = f(self);
+      if accept(&x) { return x; }
+    }
+  }
+
+  /// Samples values with `f` until one satisfies `accept`, and returns it, or
+  /// returns `None` if none of `max_tries` attempts succeed.
+
+  #[inline]
+  pub fn try_sample_until<T, F, P>(&mut self, mut f: F, accept: P, max_tries: usize) -> Option<T>
+  where
+    F: FnMut(&mut Self) -> T,
+    P: Fn(&T) -> bool
+  {
+    for _ in 0 .. max_tries {
+      let x = f(self);
+      if accept(&x) { return Some(x); }
+    }
+    None
+  }
+}
@@ -0,0 +1,47 @@
+//! Sampling integers of a generic type.
+
+use crate::Core;
+use crate::Rng;
+use num_traits::PrimInt;
+
+impl<C: Core> Rng<C> {
+  /// Samples an integer of any primitive type from the uniform distribution
+  /// over the range `lo ... hi`.
+  ///
+  /// The lower and upper bounds are inclusive.
+  ///
+  /// ```
+  /// use dandelion::Rng;
+  /// use num_traits::PrimInt;
+  ///
+  /// fn random_index<T: PrimInt>(rng: &mut Rng, len: T) -> T {
+  ///   rng.uniform_int(T::zero(), len - T::one())
+  /// }
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// let i = random_index(&mut rng, 10u16);
+  /// ```
+  ///
+  /// # Panics
+  ///
+  /// Panics if `lo > hi`.
+
+  pub fn uniform_int<T: PrimInt>(&mut self, lo: T, hi: T) -> T {
+    assert!(lo <= hi, "uniform_int: lo > hi");
+
+    // Every primitive integer fits in an `i128` or a `u128`, where the
+    // difference between the bounds always fits in a `u128`.
+
+    let t =
+      if T::min_value() < T::zero() {
+        let (Some(a), Some(b)) = (lo.to_i128(), hi.to_i128()) else { unreachable!() };
+        T::from(a.wrapping_add(self.bounded_u128(b.wrapping_sub(a) as u128) as i128))
+      } else {
+        let (Some(a), Some(b)) = (lo.to_u128(), hi.to_u128()) else { unreachable!() };
+        T::from(a + self.bounded_u128(b - a))
+      };
+
+    let Some(t) = t else { unreachable!() };
+    t
+  }
+}
@@ -0,0 +1,410 @@
+//! Sampling points from geometric shapes.
+
+use crate::Core;
+use crate::Rng;
+
+impl<C: Core> Rng<C> {
+  /// Samples a point from the uniform distribution on the unit circle.
+
+  #[inline]
+  pub fn unit_circle(&mut self) -> (f64, f64) {
+    // This is von Neumann's method. A point sampled from the unit disk is
+    // squared as a complex number and normalized, which doubles its angle and
+    // avoids computing any square roots or trigonometric functions.  The
+    // expected number of iterations is 4/π.
+
+    loop {
+      let x = self.f64_signed();
+      let y = self.f64_signed();
+      let s = x * x + y * y;
+      if s <= 1.0 && s > 0.0 {
+        return ((x * x - y * y) / s, 2.0 * x * y / s);
+      }
+    }
+  }
+
+  /// Samples a point from the uniform distribution on the closed unit disk.
+
+  #[inline]
+  pub fn in_unit_disk(&mut self) -> (f64, f64) {
+    // Rejection sampling from the square [-1, 1]². The expected number of
+    // iterations is 4/π.
+
+    loop {
+      let x = self.f64_signed();
+      let y = self.f64_signed();
+      if x * x + y * y <= 1.0 {
+        return (x, y);
+      }
+    }
+  }
+
+  /// Samples a point from the uniform distribution on the unit circle, with
+  /// `f32` coordinates.
+  ///
+  /// This uses the same method as [Rng::unit_circle].
+
+  #[inline]
+  pub fn unit_circle_f32(&mut self) -> (f32, f32) {
+    loop {
+      let x = self.f32_signed();
+      let y = self.f32_signed();
+      let s = x * x + y * y;
+      if s <= 1.0 && s > 0.0 {
+        return ((x * x - y * y) / s, 2.0 * x * y / s);
+      }
+    }
+  }
+
+  /// Samples a point from the uniform distribution on the closed unit disk,
+  /// with `f32` coordinates.
+
+  #[inline]
+  pub fn in_unit_disk_f32(&mut self) -> (f32, f32) {
+    loop {
+      let x = self.f32_signed();
+      let y = self.f32_signed();
+      if x * x + y * y <= 1.0 {
+        return (x, y);
+      }
+    }
+  }
+
+  /// Samples a point from the uniform distribution on the unit sphere.
+
+  #[cfg(feature = "std")]
+  #[inline]
+  pub fn unit_sphere(&mut self) -> (f64, f64, f64) {
+    // This is Marsaglia's method. The expected number of iterations is 4/π.
+
+    loop {
+      let x = self.f64_signed();
+      let y = self.f64_signed();
+      let s = x * x + y * y;
+      if s < 1.0 {
+        let t = 2.0 * (1.0 - s).sqrt();
+        return (x * t, y * t, 1.0 - 2.0 * s);
+      }
+    }
+  }
+
+  /// Samples a point from the uniform distribution on the unit sphere in
+  /// `dst.len()` dimensions and writes its coordinates to `dst`.
+  ///
+  /// An empty `dst` is left unchanged.
+
+  #[cfg(feature = "std")]
+  pub fn unit_vector(&mut self, dst: &mut [f64]) {
+    // A vector of independent standard normals is rotationally symmetric, so
+    // normalizing it gives a uniform direction.
+
+    if dst.len() == 0 {
+      return;
+    }
+
+    loop {
+      for chunk in dst.chunks_mut(2) {
+        let (x, y) = self.normal_pair();
+        chunk[0] = x;
+        if let Some(z) = chunk.get_mut(1) { *z = y; }
+      }
+
+      let r = dst.iter().map(|x| x * x).sum::<f64>().sqrt();
+
+      if r > 0.0 {
+        for x in dst.iter_mut() { *x /= r; }
+        return;
+      }
+    }
+  }
+
+  /// Samples a unit quaternion from the uniform distribution on the
+  /// three-sphere, which corresponds to a uniformly random rotation in three
+  /// dimensions.
+  ///
+  /// The quaternion `w + xi + yj + zk` is returned as `[x, y, z, w]`. As with
+  /// any unit quaternion, `q` and `-q` represent the same rotation.
+
+  #[cfg(feature = "std")]
+  #[inline]
+  pub fn unit_quaternion(&mut self) -> [f64; 4] {
+    // This is Shoemake's method, with the sine and cosine of each uniform
+    // angle taken from a point on the unit circle.
+
+    let u = self.f64();
+    let a = (1.0 - u).sqrt();
+    let b = u.sqrt();
+    let (c1, s1) = self.unit_circle();
+    let (c2, s2) = self.unit_circle();
+    [a * s1, a * c1, b * s2, b * c2]
+  }
+
+  /// Samples a 2 × 2 rotation matrix from the uniform distribution on
+  /// rotations of the plane.
+
+  #[inline]
+  pub fn rotation_2d(&mut self) -> [[f64; 2]; 2] {
+    let (c, s) = self.unit_circle();
+    [[c, - s], [s, c]]
+  }
+
+  /// Samples a 3 × 3 rotation matrix from the uniform distribution on
+  /// rotations of three-dimensional space.
+  ///
+  /// The matrix is row-major and is the one that corresponds to a quaternion
+  /// from [Rng::unit_quaternion].
+
+  #[cfg(feature = "std")]
+  #[inline]
+  pub fn rotation_3d(&mut self) -> [[f64; 3]; 3] {
+    let [x, y, z, w] = self.unit_quaternion();
+    [
+      [1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y - z * w), 2.0 * (x * z + y * w)],
+      [2.0 * (x * y + z * w), 1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z - x * w)],
+      [2.0 * (x * z - y * w), 2.0 * (y * z + x * w), 1.0 - 2.0 * (x * x + y * y)],
+    ]
+  }
+
+  /// Samples a point from the uniform distribution on the annulus with inner
+  /// radius `r_min` and outer radius `r_max`, centered at the origin.
+
+  #[cfg(feature = "std")]
+  #[inline]
+  pub fn in_annulus(&mut self, r_min: f64, r_max: f64) -> (f64, f64) {
+    // The squared radius is uniformly distributed.
+
+    let a = r_min * r_min;
+    let b = r_max * r_max;
+    let r = (a + (b - a) * self.f64()).sqrt();
+    let (x, y) = self.unit_circle();
+    (r * x, r * y)
+  }
+
+  /// Samples a point from the uniform distribution on the spherical cap of
+  /// points on the unit sphere within angle `max_angle` of `axis`.
+  ///
+  /// The axis need not be normalized. Angles are in radians and are clamped
+  /// to [0, π].
+  ///
+  /// # Panics
+  ///
+  /// Panics if the length of `axis` is zero or not finite.
+
+  #[cfg(feature = "std")]
+  pub fn on_spherical_cap(&mut self, axis: [f64; 3], max_angle: f64) -> [f64; 3] {
+    let l = (axis[0] * axis[0] + axis[1] * axis[1] + axis[2] * axis[2]).sqrt();
+    assert!(l > 0.0 && l.is_finite(), "on_spherical_cap: axis length is zero or not finite");
+
+    // By Archimedes' theorem, the height along the axis is uniformly
+    // distributed.
+
+    let c = max_angle.clamp(0.0, core::f64::consts::PI).cos();
+    let z = 1.0 - (1.0 - c) * self.f64();
+    let r = (1.0 - z * z).max(0.0).sqrt();
+    let (x, y) = self.unit_circle();
+    let (x, y) = (r * x, r * y);
+
+    // The orthonormal basis around the axis is from "Building an Orthonormal
+    // Basis, Revisited" by Duff et al.
+
+    let n = [axis[0] / l, axis[1] / l, axis[2] / l];
+    let sign = 1.0f64.copysign(n[2]);
+    let a = - 1.0 / (sign + n[2]);
+    let b = n[0] * n[1] * a;
+    let u = [1.0 + sign * n[0] * n[0] * a, sign * b, - sign * n[0]];
+    let v = [b, sign + n[1] * n[1] * a, - n[1]];
+
+    core::array::from_fn(|i| x * u[i] + y * v[i] + z * n[i])
+  }
+
+  /// Samples a unit vector from the uniform distribution on directions
+  /// within angle `half_angle` of `axis`, that is, on the solid angle of a
+  /// cone.
+  ///
+  /// This is the same distribution as [Rng::on_spherical_cap].
+  ///
+  /// # Panics
+  ///
+  /// Panics if the length of `axis` is zero or not finite.
+
+  #[cfg(feature = "std")]
+  #[inline]
+  pub fn cone_direction(&mut self, axis: [f64; 3], half_angle: f64) -> [f64; 3] {
+    self.on_spherical_cap(axis, half_angle)
+  }
+
+  /// Samples a point from the uniform distribution on the closed unit ball.
+
+  #[inline]
+  pub fn in_unit_ball(&mut self) -> (f64, f64, f64) {
+    // Rejection sampling from the cube [-1, 1]³. The expected number of
+    // iterations is 6/π.
+
+    loop {
+      let x = self.f64_signed();
+      let y = self.f64_signed();
+      let z = self.f64_signed();
+      if x * x + y * y + z * z <= 1.0 {
+        return (x, y, z);
+      }
+    }
+  }
+
+  /// Samples a point from the uniform distribution on the triangle with
+  /// vertices `a`, `b`, and `c`.
+  ///
+  /// The triangle can lie in a space of any dimension, e.g. a face of a mesh
+  /// in three dimensions.
+
+  #[inline]
+  pub fn in_triangle<const N: usize>(&mut self, a: [f64; N], b: [f64; N], c: [f64; N]) -> [f64; N] {
+    // A point sampled uniformly from the unit square is reflected into the
+    // lower-left half if it lies in the upper-right half. This avoids the
+    // square root in the usual inverse transform method.
+
+    let u = self.f64();
+    let v = self.f64();
+    let (u, v) = if u + v > 1.0 { (1.0 - u, 1.0 - v) } else { (u, v) };
+    core::array::from_fn(|i| a[i] + u * (b[i] - a[i]) + v * (c[i] - a[i]))
+  }
+
+  /// Samples a point from the uniform distribution on a convex polygon.
+  ///
+  /// The polygon is split into a fan of triangles around its first vertex,
+  /// and a triangle is chosen in proportion to its area. The result is also
+  /// uniform for polygons that are star-shaped around their first vertex.
+  /// This takes time proportional to the number of vertices.
+  ///
+  /// # Panics
+  ///
+  /// Panics if there are fewer than three vertices.
+
+  pub fn in_polygon(&mut self, vertices: &[[f64; 2]]) -> [f64; 2] {
+    let [a, rest @ ..] = vertices else { panic!("in_polygon: too few vertices") };
+    assert!(rest.len() >= 2, "in_polygon: too few vertices");
+    let fan = rest.windows(2).map(|w| [*a, w[0], w[1]]);
+    let i = self.choose_by_area(fan.clone());
+    let Some([a, b, c]) = fan.clone().nth(i) else { unreachable!() };
+    self.in_triangle(a, b, c)
+  }
+
+  /// Samples a point from the uniform distribution on the union of the given
+  /// non-overlapping triangles.
+  ///
+  /// A triangle is chosen in proportion to its area. This takes time
+  /// proportional to the number of triangles.
+  ///
+  /// # Panics
+  ///
+  /// Panics if there are no triangles.
+
+  pub fn in_triangles(&mut self, triangles: &[[[f64; 2]; 3]]) -> [f64; 2] {
+    assert!(! triangles.is_empty(), "in_triangles: no triangles");
+    let [a, b, c] = triangles[self.choose_by_area(triangles.iter().copied())];
+    self.in_triangle(a, b, c)
+  }
+
+  // Samples the index of a triangle with probability proportional to its
+  // area. The iterator is traversed twice.
+
+  fn choose_by_area<I>(&mut self, triangles: I) -> usize
+  where
+    I: Iterator<Item = [[f64; 2]; 3]> + Clone
+  {
+    let area = |[a, b, c]: [[f64; 2]; 3]| {
+      ((b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])).abs()
+    };
+
+    let total: f64 = triangles.clone().map(area).sum();
+    let t = total * self.f64();
+
+    // Triangles with zero area are never chosen, except if rounding error
+    // leaves nothing else.
+
+    let mut acc = 0.0;
+    let mut last = 0;
+    for (i, x) in triangles.enumerate() {
+      acc += area(x);
+      if acc > t { return i; }
+      last = i;
+    }
+    last
+  }
+
+  /// Samples a point from the uniform distribution on the standard simplex in
+  /// `dst.len()` dimensions and writes its coordinates to `dst`.
+  ///
+  /// The coordinates are non-negative and sum to 1, up to rounding, so they
+  /// can be used as the weights of a random convex combination. An empty
+  /// `dst` is left unchanged.
+
+  pub fn simplex_weights(&mut self, dst: &mut [f64]) {
+    // The spacings between sorted uniform samples on [0, 1] are uniformly
+    // distributed on the simplex.
+
+    let Some((last, init)) = dst.split_last_mut() else { return; };
+
+    for x in init.iter_mut() { *x = self.f64(); }
+    init.sort_unstable_by(f64::total_cmp);
+    *last = 1.0;
+
+    for i in (1 .. dst.len()).rev() {
+      dst[i] -= dst[i - 1];
+    }
+  }
+
+  /// Fills `dst` with one sample from the uniform distribution on each of the
+  /// intervals [i / n, (i + 1) / n] where `n = dst.len()`.
+  ///
+  /// The sample from interval `i` is stored at index `i`. Such stratified
+  /// samples reduce the variance of Monte Carlo estimates compared to
+  /// independent uniform samples. The intervals are closed, so neighboring
+  /// samples can be equal at a shared endpoint.
+
+  pub fn stratified(&mut self, dst: &mut [f64]) {
+    let d = 1.0 / dst.len() as f64;
+
+    for (i, x) in dst.iter_mut().enumerate() {
+      *x = (i as f64 + self.f64()) * d;
+    }
+  }
+
+  /// Fills `dst` with one sample from each of the intervals [i / n,
+  /// (i + 1) / n] where `n = dst.len()`, in a uniformly random order.
+  ///
+  /// This is the same as [Rng::stratified] followed by a shuffle, which is
+  /// useful for pairing strata across dimensions as in Latin hypercube
+  /// sampling.
+
+  pub fn stratified_shuffled(&mut self, dst: &mut [f64]) {
+    self.stratified(dst);
+    self.shuffle(dst);
+  }
+
+  /// Fills `dst` with one point from the uniform distribution on each cell of
+  /// an `nx` by `ny` grid on the unit square [0, 1]².
+  ///
+  /// The point in cell `(i, j)` is stored at index `j * nx + i`. Such jittered
+  /// samples are spread more evenly than independent uniform points. The
+  /// cells are closed, so a point can land on the boundary that its cell
+  /// shares with the next one.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `dst.len() != nx * ny`.
+
+  pub fn stratified_2d(&mut self, nx: usize, ny: usize, dst: &mut [(f64, f64)]) {
+    assert!(nx.checked_mul(ny) == Some(dst.len()), "stratified_2d: wrong length");
+
+    let dx = 1.0 / nx as f64;
+    let dy = 1.0 / ny as f64;
+
+    for (k, p) in dst.iter_mut().enumerate() {
+      let i = k % nx;
+      let j = k / nx;
+      let x = (i as f64 + self.f64()) * dx;
+      let y = (j as f64 + self.f64()) * dy;
+      *p = (x, y);
+    }
+  }
+}
@@ -0,0 +1,125 @@
+//! Random graphs for testing and benchmarking graph algorithms.
+//!
+//! Graphs are undirected, with vertices `0 ... n - 1`, and are returned as
+//! lists of edges `(u, v)` with `u < v`. Each generator also has a variant
+//! that passes the edges to a callback instead of allocating a list.
+//!
+//! ```
+//! use dandelion::Rng;
+//! use dandelion::graph;
+//!
+//! let mut rng = Rng::from_u64(0);
+//! let edges = graph::erdos_renyi(&mut rng, 1000, 0.01);
+//! ```
+
+use crate::Core;
+use crate::Rng;
+
+/// Samples an Erdős–Rényi random graph G(n, p) with `n` vertices, where each
+/// of the possible edges is present independently with probability `p`.
+///
+/// The edges are sorted by their larger vertex and then by their smaller
+/// vertex. The running time is proportional to the number of vertices plus
+/// the number of edges.
+
+pub fn erdos_renyi<C: Core>(rng: &mut Rng<C>, n: u32, p: f64) -> Vec<(u32, u32)> {
+  let mut edges = Vec::new();
+  erdos_renyi_with(rng, n, p, |u, v| edges.push((u, v)));
+  edges
+}
+
+/// Samples an Erdős–Rényi random graph G(n, p), passing each edge to the
+/// callback.
+///
+/// See [erdos_renyi].
+
+pub fn erdos_renyi_with<C: Core, F>(rng: &mut Rng<C>, n: u32, p: f64, mut edge: F)
+where
+  F: FnMut(u32, u32)
+{
+  // Probabilities `p` <= 0 or NaN are treated as 0, and `p` >= 1 are treated
+  // as 1, as in `Rng::bernoulli`.
+
+  if p.is_nan() || p <= 0.0 { return; }
+
+  if p >= 1.0 {
+    for v in 1 .. n {
+      for u in 0 .. v {
+        edge(u, v);
+      }
+    }
+    return;
+  }
+
+  // This is the algorithm of Batagelj and Brandes, which skips over a
+  // geometrically distributed number of absent edges at a time.
+
+  let lq = (- p).ln_1p();
+  let mut v = 1u64;
+  let mut w = -1i64;
+  let n = n as u64;
+
+  while v < n {
+    let skip = ((- rng.f64()).ln_1p() / lq) as i64;
+    w = w.saturating_add(1).saturating_add(skip);
+    while w >= v as i64 && v < n {
+      w -= v as i64;
+      v += 1;
+    }
+    if v < n {
+      edge(w as u32, v as u32);
+    }
+  }
+}
+
+/// Samples a Barabási–Albert preferential attachment graph with `n`
+/// vertices.
+///
+/// The graph starts with vertices `0 ... m - 1` and no edges. Each later
+/// vertex is attached to `m` distinct earlier vertices, chosen with
+/// probability proportional to their degree. Vertex `m` is attached to all of
+/// the initial vertices.
+///
+/// # Panics
+///
+/// Panics unless `1 <= m < n`.
+
+pub fn barabasi_albert<C: Core>(rng: &mut Rng<C>, n: u32, m: u32) -> Vec<(u32, u32)> {
+  let mut edges = Vec::new();
+  barabasi_albert_with(rng, n, m, |u, v| edges.push((u, v)));
+  edges
+}
+
+/// Samples a Barabási–Albert preferential attachment graph, passing each
+/// edge to the callback.
+///
+/// See [barabasi_albert].
+
+pub fn barabasi_albert_with<C: Core, F>(rng: &mut Rng<C>, n: u32, m: u32, mut edge: F)
+where
+  F: FnMut(u32, u32)
+{
+  assert!(1 <= m && m < n, "barabasi_albert: requires 1 <= m < n");
+
+  // Every vertex appears in `endpoints` once for each edge that it is in,
+  // so a uniform element of `endpoints` is chosen in proportion to degree.
+
+  let mut endpoints = Vec::with_capacity(2 * m as usize * (n - m) as usize);
+  let mut targets: Vec<u32> = (0 .. m).collect();
+
+  for v in m .. n {
+    for &u in &targets {
+      edge(u, v);
+      endpoints.push(u);
+      endpoints.push(v);
+    }
+
+    targets.clear();
+    while targets.len() < m as usize {
+      let i = rng.bounded_u64(endpoints.len() as u64 - 1) as usize;
+      let u = endpoints[i];
+      if ! targets.contains(&u) { targets.push(u); }
+    }
+    targets.sort_unstable();
+  }
+}
@@ -0,0 +1,43 @@
+//! Sampling half-precision floats.
+
+use half::f16;
+
+use crate::Core;
+use crate::FromRng;
+use crate::Rng;
+
+impl<C: Core> Rng<C> {
+  /// Samples a `f16` from a distribution that approximates the uniform
+  /// distribution over the real interval [0, 1].
+  ///
+  /// The distribution is the same as the one produced by the following
+  /// procedure:
+  ///
+  /// - Sample a real number from the uniform distribution on [0, 1].
+  /// - Round to the nearest multiple of 2⁻⁶³.
+  /// - Round to a `f16` using the default rounding mode.
+  ///
+  /// An output zero will always be +0, never -0.
+
+  #[inline(always)]
+  pub fn f16(&mut self) -> f16 {
+    // Converting through `f32` with round-to-nearest would round twice, which
+    // is occasionally different from rounding once. Instead, we first round
+    // the magnitude to 24 significant bits with round-to-odd, which makes the
+    // conversion into a `f32` exact and the final rounding correct.
+
+    let x = self.i64().unsigned_abs();
+    let s = (64 - x.leading_zeros()).saturating_sub(24);
+    let m = (1 << s) - 1;
+    let x = x & ! m | ((x & m != 0) as u64) << s;
+    let x = f32::from_bits(0x2000_0000) * x as f32;
+    f16::from_f32(x)
+  }
+}
+
+impl FromRng for f16 {
+  #[inline(always)]
+  fn from_rng<C: Core>(rng: &mut Rng<C>) -> Self {
+    rng.f16()
+  }
+}
@@ -0,0 +1,110 @@
+//! Hashing with the crate's mixing function.
+//!
+//! ```
+//! use dandelion::hash::hash_one;
+//!
+//! let bucket = hash_one("user-1234") % 16;
+//! assert!(bucket == hash_one("user-1234") % 16);
+//! ```
+//!
+//! The hash values are stable across platforms and versions of this crate,
+//! but the hasher is not resistant to collision attacks.
+
+use core::hash::BuildHasher;
+use core::hash::Hash;
+use core::hash::Hasher;
+
+use crate::mix::mix128;
+
+/// A [Hasher] that absorbs its input in 128-bit blocks, applying
+/// [mix128] after each block.
+
+#[derive(Clone, Debug)]
+pub struct Mix128Hasher {
+  state: u128,
+  len: u64,
+}
+
+/// A [BuildHasher] that creates [Mix128Hasher]s, for use with hash maps and
+/// sets.
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BuildMix128Hasher;
+
+impl Mix128Hasher {
+  /// Creates a hasher with the default initial state.
+
+  #[inline(always)]
+  pub const fn new() -> Self {
+    Self::with_seed(0)
+  }
+
+  /// Creates a hasher whose output depends on the given seed.
+
+  #[inline(always)]
+  pub const fn with_seed(seed: u64) -> Self {
+    // The initial state is never zero, which is a fixed point of the mixing
+    // function.
+
+    Self { state: mix128(seed as u128 | 1 << 64), len: 0 }
+  }
+
+  #[inline(always)]
+  fn absorb(&mut self, block: u128) {
+    self.state = mix128(self.state ^ block);
+  }
+}
+
+impl Default for Mix128Hasher {
+  #[inline(always)]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Hasher for Mix128Hasher {
+  #[inline]
+  fn write(&mut self, bytes: &[u8]) {
+    let mut chunks = bytes.chunks_exact(16);
+
+    for chunk in &mut chunks {
+      let Some(chunk) = chunk.first_chunk() else { unreachable!() };
+      self.absorb(u128::from_le_bytes(*chunk));
+    }
+
+    let tail = chunks.remainder();
+
+    if tail.len() != 0 {
+      let mut buf = [0u8; 16];
+      buf[.. tail.len()].copy_from_slice(tail);
+      self.absorb(u128::from_le_bytes(buf));
+    }
+
+    self.len = self.len.wrapping_add(bytes.len() as u64);
+  }
+
+  #[inline]
+  fn finish(&self) -> u64 {
+    // The total length distinguishes inputs that differ only by trailing
+    // zeros. The high half of the mixed state is the best mixed.
+
+    (mix128(self.state ^ self.len as u128) >> 64) as u64
+  }
+}
+
+impl BuildHasher for BuildMix128Hasher {
+  type Hasher = Mix128Hasher;
+
+  #[inline(always)]
+  fn build_hasher(&self) -> Mix128Hasher {
+    Mix128Hasher::new()
+  }
+}
+
+/// Hashes a value with a fresh [Mix128Hasher].
+
+pub fn hash_one<T: Hash + ?Sized>(value: &T) -> u64 {
+  let mut h = Mix128Hasher::new();
+  value.hash(&mut h);
+  h.finish()
+}
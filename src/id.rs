@@ -0,0 +1,73 @@
+//! Sampling sortable unique identifiers.
+
+use crate::Core;
+use crate::Rng;
+
+impl<C: Core> Rng<C> {
+  /// Samples a ULID with the given timestamp in milliseconds since the Unix
+  /// epoch, and returns its canonical encoding.
+  ///
+  /// The encoding is 26 ASCII characters from Crockford's base 32 alphabet.
+  /// The first 10 characters encode the timestamp, and the remaining 16
+  /// encode 80 random bits.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `timestamp_ms >= 2⁴⁸`.
+
+  pub fn ulid(&mut self, timestamp_ms: u64) -> [u8; 26] {
+    const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+    assert!(timestamp_ms >> 48 == 0, "ulid: timestamp out of range");
+
+    let x = self.u64() as u128;
+    let y = self.u64() as u128;
+    let n = (timestamp_ms as u128) << 80 | y >> 48 << 64 | x;
+
+    let mut buf = [0; 26];
+    for (i, c) in buf.iter_mut().enumerate() {
+      *c = ALPHABET[(n >> 5 * (25 - i) & 31) as usize];
+    }
+    buf
+  }
+
+  /// Samples a KSUID with the given timestamp in seconds since the Unix
+  /// epoch, and returns its canonical encoding.
+  ///
+  /// The encoding is 27 ASCII characters from the base 62 alphabet `0-9A-Za-z`,
+  /// representing a 32-bit timestamp relative to the KSUID epoch 1400000000
+  /// followed by 128 random bits.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `timestamp` is not in the range `1400000000 ... 1400000000 +
+  /// 2³² - 1`.
+
+  pub fn ksuid(&mut self, timestamp: u64) -> [u8; 27] {
+    const ALPHABET: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+    const EPOCH: u64 = 1_400_000_000;
+
+    let Some(t) = timestamp.checked_sub(EPOCH) else { panic!("ksuid: timestamp out of range") };
+    let Ok(t) = u32::try_from(t) else { panic!("ksuid: timestamp out of range") };
+
+    let x = self.u64();
+    let y = self.u64();
+
+    // The 160-bit number as big-endian 32-bit digits, which we repeatedly
+    // divide by 62.
+
+    let mut n = [t, (y >> 32) as u32, y as u32, (x >> 32) as u32, x as u32];
+    let mut buf = [0; 27];
+
+    for c in buf.iter_mut().rev() {
+      let mut r = 0u64;
+      for d in n.iter_mut() {
+        let v = r << 32 | *d as u64;
+        *d = (v / 62) as u32;
+        r = v % 62;
+      }
+      *c = ALPHABET[r as usize];
+    }
+    buf
+  }
+}
@@ -0,0 +1,64 @@
+//! Initializing the weights of neural networks.
+
+use crate::Core;
+use crate::Rng;
+
+impl<C: Core> Rng<C> {
+  /// Fills `dst` with independent samples from the normal distribution with
+  /// mean `mu` and standard deviation `sigma`.
+
+  pub fn fill_normal(&mut self, dst: &mut [f32], mu: f32, sigma: f32) {
+    // Each iteration of the polar method produces two samples.
+
+    for chunk in dst.chunks_mut(2) {
+      let (x, y) = self.normal_pair();
+      chunk[0] = mu + sigma * x as f32;
+      if let Some(z) = chunk.get_mut(1) { *z = mu + sigma * y as f32; }
+    }
+  }
+
+  /// Fills `dst` with independent samples from the uniform distribution
+  /// over the interval `[-a, a]`, where `a = sqrt(6 / (fan_in + fan_out))`.
+  ///
+  /// This is the initialization of Glorot and Bengio, also called Xavier
+  /// initialization, for a layer with `fan_in` inputs and `fan_out` outputs.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `fan_in + fan_out == 0`.
+
+  pub fn fill_xavier_uniform(&mut self, dst: &mut [f32], fan_in: usize, fan_out: usize) {
+    assert!(fan_in + fan_out != 0, "fill_xavier_uniform: fan_in + fan_out == 0");
+
+    let a = (6.0 / (fan_in + fan_out) as f64).sqrt();
+
+    // Each output is split into two 32-bit signed integers, which are scaled
+    // to the interval [-1, 1]. This has more than enough resolution for an
+    // `f32` result, and halves the number of draws.
+
+    let m = (a * f64::from_bits(0x3e00_0000_0000_0000)) as f32;
+
+    for chunk in dst.chunks_mut(2) {
+      let x = self.u64();
+      chunk[0] = m * x as i32 as f32;
+      if let Some(z) = chunk.get_mut(1) { *z = m * (x >> 32) as i32 as f32; }
+    }
+  }
+
+  /// Fills `dst` with independent samples from the normal distribution with
+  /// mean 0 and standard deviation `sqrt(2 / fan_in)`.
+  ///
+  /// This is the initialization of He et al. for a layer with `fan_in`
+  /// inputs followed by rectified linear units.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `fan_in == 0`.
+
+  pub fn fill_he_normal(&mut self, dst: &mut [f32], fan_in: usize) {
+    assert!(fan_in != 0, "fill_he_normal: fan_in == 0");
+
+    let sigma = (2.0 / fan_in as f64).sqrt() as f32;
+    self.fill_normal(dst, 0.0, sigma);
+  }
+}
@@ -0,0 +1,64 @@
+//! Randomized delays for retrying with exponential backoff.
+//!
+//! Adding randomness to backoff spreads out the retries of many clients that
+//! failed at the same time. The strategies are those described in "Exponential
+//! Backoff And Jitter" on the AWS Architecture Blog.
+//!
+//! ```
+//! use dandelion::Rng;
+//! use dandelion::jitter;
+//! use std::time::Duration;
+//!
+//! let mut rng = Rng::from_u64(0);
+//! let base = Duration::from_millis(100);
+//! let cap = Duration::from_secs(10);
+//!
+//! for attempt in 0 .. 5 {
+//!   let delay = jitter::full_jitter(&mut rng, base, cap, attempt);
+//!   assert!(delay <= cap);
+//! }
+//! ```
+
+use core::time::Duration;
+
+use crate::Core;
+use crate::Rng;
+
+// Returns min(cap, base * 2^attempt) without overflowing.
+
+fn exponential(base: Duration, cap: Duration, attempt: u32) -> Duration {
+  1u32.checked_shl(attempt)
+    .and_then(|m| base.checked_mul(m))
+    .map_or(cap, |d| d.min(cap))
+}
+
+/// Samples a delay from the uniform distribution on [0, min(cap, base * 2ⁿ)]
+/// where `n` is the number of previous attempts.
+///
+/// This strategy spreads out retries the most.
+
+pub fn full_jitter<C: Core>(rng: &mut Rng<C>, base: Duration, cap: Duration, attempt: u32) -> Duration {
+  rng.duration_between(Duration::ZERO, exponential(base, cap, attempt))
+}
+
+/// Samples a delay from the uniform distribution on [t / 2, t] where
+/// `t = min(cap, base * 2ⁿ)` and `n` is the number of previous attempts.
+///
+/// This strategy always waits for at least half of the exponential delay.
+
+pub fn equal_jitter<C: Core>(rng: &mut Rng<C>, base: Duration, cap: Duration, attempt: u32) -> Duration {
+  let t = exponential(base, cap, attempt);
+  rng.duration_between(t / 2, t)
+}
+
+/// Samples a delay from the uniform distribution on [base, 3 * previous],
+/// clamped to be at most `cap`.
+///
+/// The `previous` delay should be `base` for the first retry. Unlike the other
+/// strategies, the delay grows according to the previous delay rather than
+/// the number of attempts.
+
+pub fn decorrelated_jitter<C: Core>(rng: &mut Rng<C>, base: Duration, cap: Duration, previous: Duration) -> Duration {
+  let hi = previous.saturating_mul(3).max(base);
+  rng.duration_between(base, hi).min(cap)
+}
@@ -1,42 +1,151 @@
 #![doc = include_str!("../README.md")]
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 use core::num::NonZeroU128;
 
+#[cfg(feature = "alloc")]
+mod alias;
+mod bits;
+mod choose;
+mod color;
+#[cfg(feature = "rust_decimal")]
+mod decimal;
+#[cfg(feature = "fixed")]
+mod fixed_point;
+#[cfg(feature = "num-traits")]
+mod generic;
+mod from_rng;
+mod geometry;
+#[cfg(feature = "half")]
+mod half_float;
+mod id;
+#[cfg(feature = "std")]
+mod init;
+mod net;
+mod range;
+mod round;
+mod string;
+mod time;
+#[cfg(feature = "alloc")]
+mod tree;
+
+#[cfg(feature = "alloc")]
+pub mod antithetic;
+#[cfg(feature = "atomic")]
+pub mod atomic;
+#[cfg(feature = "thread_local")]
+pub mod compat;
+pub mod bit_permutation;
+pub mod counting;
+pub mod dice;
+pub mod distribution;
+pub mod entropy;
+pub mod fault;
+#[cfg(feature = "graph")]
+pub mod graph;
+pub mod hash;
+pub mod jitter;
+#[cfg(feature = "alloc")]
+pub mod markov;
+pub mod mix;
+#[cfg(feature = "noise")]
+pub mod noise;
+#[cfg(feature = "pattern")]
+pub mod pattern;
+pub mod quasi;
+#[cfg(feature = "alloc")]
+pub mod tape;
+#[cfg(feature = "value")]
+pub mod value;
+pub mod walk;
+pub mod weighted;
+pub mod xoshiro;
+
+pub use from_rng::FromRng;
+pub use range::RangeInt;
+pub use string::Charset;
+
+#[cfg(feature = "getrandom")]
+pub use entropy::OsRng;
+
+#[cfg(feature = "derive")]
+pub use dandelion_derive::Random;
+
 /// A high performance non-cryptographic random number generator.
+///
+/// The sampling methods are built on a [Core], which produces a stream of
+/// `u64`s. The default core is [Dandelion]. All of the sampling methods,
+/// including [Rng::sample] and [Rng::random], are available for every core,
+/// while the constructors, [Rng::state], and [Rng::split] are only available
+/// for `Rng<Dandelion>`.
+
+#[derive(Clone)]
+pub struct Rng<C = Dandelion> { core: C, bank: u64 }
+
+/// A source of uniformly distributed `u64`s, on which the sampling methods of
+/// [Rng] are built.
+
+pub trait Core {
+  /// Produces the next output.
+
+  fn next_u64(&mut self) -> u64;
+}
+
+/// The default core, with a state of 128 bits and a cycle length of
+/// 2¹²⁸ - 1.
 
 #[derive(Clone)]
-pub struct Rng { state: NonZeroU128 }
+pub struct Dandelion { state: NonZeroU128 }
+
+// The chunk accessors are built from safe slice primitives. Their bounds
+// checks are removed by the optimizer at every call site in this crate.
 
 #[inline(always)]
 const fn get_chunk<T, const N: usize>(slice: &[T], index: usize) -> &[T; N] {
-  assert!(index <= slice.len() && N <= slice.len() - index);
-  unsafe { &*slice.as_ptr().add(index).cast::<[T; N]>() }
+  let Some(chunk) = slice.split_at(index).1.first_chunk() else { panic!() };
+  chunk
 }
 
 #[inline(always)]
 fn get_chunk_mut<T, const N: usize>(slice: &mut [T], index: usize) -> &mut [T; N] {
-  assert!(index <= slice.len() && N <= slice.len() - index);
-  unsafe { &mut *slice.as_mut_ptr().add(index).cast::<[T; N]>() }
+  let Some(chunk) = slice.split_at_mut(index).1.first_chunk_mut() else { panic!() };
+  chunk
 }
 
+// Detects states that are unlikely to have come from a good source of
+// entropy. The halves of a uniformly random state are almost never all zeros
+// or all ones, and its number of set bits is within 48 of the mean of 64
+// except with probability less than 2⁻⁵⁰.
+
+#[cfg(feature = "debug_diagnostics")]
+const fn is_weak_state(state: NonZeroU128) -> bool {
+  let s = state.get();
+  let x = s as u64;
+  let y = (s >> 64) as u64;
+  let n = s.count_ones();
+  x == 0 || y == 0 || x == u64::MAX || y == u64::MAX || n < 16 || n > 112
+}
+
+// Unlike `f64::floor`, this is available without `std`. Numbers with a
+// magnitude of at least 2⁵² are already integers, as are infinities, and NaN
+// is returned unchanged.
+
 #[inline(always)]
-const fn hash(x: NonZeroU128) -> NonZeroU128 {
-  // The hash uses the multiplier
-  //
-  //   M = round_nearest_odd(EULER_MASCHERONI * 2¹²⁸)
-  //
-  // The Euler-Mascheroni constant was selected because it is a well-known
-  // number in the range (0.5, 1.0).
+fn floor(x: f64) -> f64 {
+  if x.is_nan() || x.abs() >= 4503599627370496.0 { return x; }
+  let t = x as i64 as f64;
+  if t > x { t - 1.0 } else { t }
+}
 
-  const M: u128 = 0x93c4_67e3_7db0_c7a4_d1be_3f81_0152_cb57;
+#[inline(always)]
+const fn hash(x: NonZeroU128) -> NonZeroU128 {
+  // The mixing function is a bijection that maps zero to zero, so it maps
+  // nonzero inputs to nonzero outputs.
 
-  let x = x.get();
-  let x = x.wrapping_mul(M);
-  let x = x.swap_bytes();
-  let x = x.wrapping_mul(M);
-  let x = x.swap_bytes();
-  let x = x.wrapping_mul(M);
+  let x = mix::mix128(x.get());
   unsafe { NonZeroU128::new_unchecked(x) }
 }
 
@@ -50,7 +159,7 @@ impl Rng {
     let s = x as u128 | ((y >> 8) as u128) << 64;
     let s = s | 1 << 120;
     let s = unsafe { NonZeroU128::new_unchecked(s) };
-    Self { state: hash(s) }
+    Self::from_core(Dandelion { state: hash(s) })
   }
 
   /// Creates a random number generator with an initial state derived by
@@ -60,14 +169,18 @@ impl Rng {
     let s = seed as u128;
     let s = s | 1 << 64;
     let s = unsafe { NonZeroU128::new_unchecked(s) };
-    Self { state: hash(s) }
+    Self::from_core(Dandelion { state: hash(s) })
   }
 
   /// Retrieves the current state of the random number generator.
+  ///
+  /// The state is that of the core. It does not include any bits banked by
+  /// [Rng::bits], so a generator restored with [Rng::from_state] starts with
+  /// an empty bank.
 
   #[inline(always)]
   pub const fn state(&self) -> NonZeroU128 {
-    self.state
+    self.core.state
   }
 
   /// Creates a random number generator with a particular initial state.
@@ -79,25 +192,83 @@ impl Rng {
   /// instead use [Rng::new] or [Rng::from_u64] which hash their arguments.
   ///
   /// </div>
+  ///
+  /// With the `debug_diagnostics` feature enabled, debug builds panic if the
+  /// state looks like it has low entropy, such as a small integer or a
+  /// pattern of mostly ones. This is a panic rather than a logged warning
+  /// because this function is `const`.
+  ///
+  /// <div class="warning">
+  ///
+  /// The `debug_diagnostics` feature is *NOT* additive. Cargo enables a
+  /// feature for every crate in the build that depends on this one, so a
+  /// library that passes a weak state on purpose will panic if any other
+  /// crate enables the feature. Only enable it from a binary or a test crate.
+  /// To construct a generator from an arbitrary state without the check, use
+  /// `Rng::from_core(Dandelion::from_state(state))`.
+  ///
+  /// </div>
 
   #[inline(always)]
   pub const fn from_state(state: NonZeroU128) -> Self {
-    Self { state }
+    #[cfg(feature = "debug_diagnostics")]
+    debug_assert!(
+      !is_weak_state(state),
+      "Rng::from_state: weak state, use Rng::new or Rng::from_u64 to hash a seed"
+    );
+    Self::from_core(Dandelion { state })
   }
 
   /// Creates a random number generator with entropy retrieved from the
   /// operating system.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the operating system fails to provide entropy. Use
+  /// [Rng::try_from_operating_system] to handle the failure instead.
 
   #[cfg(feature = "getrandom")]
   #[inline(never)]
   #[cold]
+  pub fn from_operating_system() -> Self {
+    Self::try_from_operating_system().expect("getrandom::getrandom failed!")
+  }
+
+  /// Creates a random number generator with entropy retrieved from the
+  /// operating system, or returns the error if the operating system fails
+  /// to provide entropy.
+  ///
+  /// This can happen in sandboxes that block the relevant system call,
+  /// early in the boot process, or on targets without an entropy source.
+  ///
+  /// With the `log` feature enabled, the initial state is logged at the debug
+  /// level with target `dandelion`, so that randomized behavior can be
+  /// reproduced with [Rng::from_state]. This includes the lazy seeding of the
+  /// generators in [dandelion::thread_local](crate::thread_local).
+
+  #[cfg(feature = "getrandom")]
+  #[inline(never)]
+  #[cold]
+  pub fn try_from_operating_system() -> Result<Self, getrandom::Error> {
+    let rng = Self::from_entropy_source(&mut entropy::OsEntropy)?;
+
+    // With the `log` feature, the initial state is logged so that a run can
+    // be reproduced with `Rng::from_state`.
+
+    #[cfg(feature = "log")]
+    log::debug!(target: "dandelion", "seeded from operating system: state = {:#034x}", rng.state());
+
+    Ok(rng)
+  }
+
+  /// Creates a random number generator with entropy retrieved from the
+  /// operating system.
+
+  #[cfg(feature = "getrandom")]
+  #[deprecated(note = "use `Rng::from_operating_system` instead")]
+  #[inline(always)]
   pub fn from_entropy() -> Self {
-    let mut buf = [0u8; 16];
-    getrandom::getrandom(&mut buf).expect("getrandom::getrandom failed!");
-    let s = u128::from_le_bytes(buf);
-    let s = s | 1;
-    let s = unsafe { NonZeroU128::new_unchecked(s) };
-    Self { state: s }
+    Self::from_operating_system()
   }
 
   /// Splits off a new random number generator that may be used along with the
@@ -110,7 +281,39 @@ impl Rng {
     let s = x as u128 ^ (y as u128) << 64;
     let s = s | 1;
     let s = unsafe { NonZeroU128::new_unchecked(s) };
-    Self { state: s }
+    Self::from_core(Dandelion::from_state(s))
+  }
+}
+
+impl<C: Core> Rng<C> {
+  /// Creates a random number generator from a core.
+
+  #[inline(always)]
+  pub const fn from_core(core: C) -> Self {
+    Self { core, bank: bits::EMPTY_BANK }
+  }
+
+  /// Returns a reference to the core.
+
+  #[inline(always)]
+  pub const fn core(&self) -> &C {
+    &self.core
+  }
+
+  /// Returns a mutable reference to the core.
+
+  #[inline(always)]
+  pub fn core_mut(&mut self) -> &mut C {
+    &mut self.core
+  }
+
+  /// Consumes the random number generator and returns its core.
+  ///
+  /// Any bits banked by [Rng::bits] are discarded.
+
+  #[inline(always)]
+  pub fn into_core(self) -> C {
+    self.core
   }
 
   /// Samples a `bool` from the Bernoulli distribution where `true` appears
@@ -135,6 +338,160 @@ impl Rng {
     t < p
   }
 
+  /// Samples a `bool` from the Bernoulli distribution where `true` appears
+  /// with probability `p / 2³²`.
+  ///
+  /// This takes one draw and one comparison, which makes it suitable for hot
+  /// paths that would otherwise convert a probability to `f64` on each call.
+  /// Note that the probability of `true` is at most `1 - 2⁻³²`.
+
+  #[inline(always)]
+  pub fn bernoulli_u32(&mut self, p: u32) -> bool {
+    self.u32() < p
+  }
+
+  /// Samples a `bool` from the Bernoulli distribution where `true` appears
+  /// with probability `numerator / denominator`.
+  ///
+  /// The probability is exact, with no rounding through a float, because the
+  /// sample is computed with [Rng::bounded_u64_exact]. If `numerator >=
+  /// denominator`, the result is always `true`.
+  ///
+  /// ```
+  /// use dandelion::Rng;
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// let hit = rng.bernoulli_ratio(3, 7);
+  /// ```
+  ///
+  /// # Panics
+  ///
+  /// Panics if `denominator == 0`.
+
+  #[inline]
+  pub fn bernoulli_ratio(&mut self, numerator: u64, denominator: u64) -> bool {
+    assert!(denominator != 0, "bernoulli_ratio: denominator == 0");
+    self.bounded_u64_exact(denominator - 1) < numerator
+  }
+
+  /// Returns `true` with probability exactly `1 / n`.
+  ///
+  /// This is the same as `bernoulli_ratio(1, n)`.
+  ///
+  /// ```
+  /// use dandelion::Rng;
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// if rng.one_in(1000) {
+  ///   // Record a trace for this request.
+  /// }
+  /// ```
+  ///
+  /// # Panics
+  ///
+  /// Panics if `n == 0`.
+
+  #[inline]
+  pub fn one_in(&mut self, n: u64) -> bool {
+    assert!(n != 0, "one_in: n == 0");
+    self.bounded_u64_exact(n - 1) == 0
+  }
+
+  /// Samples 64 independent `bool`s from the Bernoulli distribution where
+  /// `true` appears with probability approximately equal to `p`, packed into
+  /// the bits of a `u64`.
+  ///
+  /// The probability is `p` rounded down to a multiple of 2⁻⁶⁴. Probabilities
+  /// `p` <= 0 or NaN are treated as 0, and `p` >= 1 are treated as 1. This
+  /// takes about 8 draws on average for most `p`, and only one draw when `p`
+  /// is 1/2.
+  ///
+  /// ```
+  /// use dandelion::Rng;
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// let mut activations = [1.0f32; 64];
+  /// let keep = rng.bernoulli_mask64(0.9);
+  /// for (i, x) in activations.iter_mut().enumerate() {
+  ///   if keep >> i & 1 == 0 { *x = 0.0; }
+  /// }
+  /// ```
+
+  #[inline]
+  pub fn bernoulli_mask64(&mut self, p: f64) -> u64 {
+    // Each bit compares the binary digits of an implicit uniform sample,
+    // drawn one word at a time, with the binary digits of `p`, starting from
+    // the most significant. A bit is decided at the first digit where they
+    // differ, and about half of the undecided bits are decided at each step.
+    // If every digit of `p` is exhausted first, the sample is at least `p`.
+
+    if p >= 1.0 { return u64::MAX; }
+
+    let mut q = (p * f64::from_bits(0x43f0_0000_0000_0000)) as u64;
+    let mut result = 0;
+    let mut undecided = u64::MAX;
+
+    while undecided != 0 && q != 0 {
+      let w = self.u64();
+      if q >> 63 == 1 {
+        result |= undecided & ! w;
+        undecided &= w;
+      } else {
+        undecided &= ! w;
+      }
+      q <<= 1;
+    }
+
+    result
+  }
+
+  /// Samples a `u32` where each bit is independently set with probability
+  /// 2⁻ᵏ.
+  ///
+  /// The mask is the bitwise AND of `k` uniformly distributed words, so `k ==
+  /// 0` always returns `u32::MAX` and `k == 1` is the same as [Rng::u32].
+  /// For probabilities that are not powers of 1/2, see
+  /// [Rng::bernoulli_mask64].
+
+  #[inline]
+  pub fn mask_u32(&mut self, k: u32) -> u32 {
+    let mut x = u32::MAX;
+    for _ in 0 .. k {
+      if x == 0 { break; }
+      x &= self.u32();
+    }
+    x
+  }
+
+  /// Samples a `u64` where each bit is independently set with probability
+  /// 2⁻ᵏ.
+  ///
+  /// The mask is the bitwise AND of `k` uniformly distributed words, so `k ==
+  /// 0` always returns `u64::MAX` and `k == 1` is the same as [Rng::u64].
+  /// For probabilities that are not powers of 1/2, see
+  /// [Rng::bernoulli_mask64].
+  ///
+  /// ```
+  /// use dandelion::Rng;
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// let mut buckets = [0u32; 64];
+  /// let promote = rng.mask_u64(2);
+  /// for (i, level) in buckets.iter_mut().enumerate() {
+  ///   if promote >> i & 1 == 1 { *level += 1; }
+  /// }
+  /// ```
+
+  #[inline]
+  pub fn mask_u64(&mut self, k: u32) -> u64 {
+    let mut x = u64::MAX;
+    for _ in 0 .. k {
+      if x == 0 { break; }
+      x &= self.u64();
+    }
+    x
+  }
+
   /// Samples a `bool` from the uniform distribution.
 
   #[inline(always)]
@@ -142,6 +499,20 @@ impl Rng {
     self.i64() < 0
   }
 
+  /// Samples a `i8` from the uniform distribution.
+
+  #[inline(always)]
+  pub fn i8(&mut self) -> i8 {
+    self.u64() as i8
+  }
+
+  /// Samples a `i16` from the uniform distribution.
+
+  #[inline(always)]
+  pub fn i16(&mut self) -> i16 {
+    self.u64() as i16
+  }
+
   /// Samples a `i32` from the uniform distribution.
 
   #[inline(always)]
@@ -156,6 +527,34 @@ impl Rng {
     self.u64() as i64
   }
 
+  /// Samples a `i128` from the uniform distribution.
+
+  #[inline(always)]
+  pub fn i128(&mut self) -> i128 {
+    self.u128() as i128
+  }
+
+  /// Samples a `isize` from the uniform distribution.
+
+  #[inline(always)]
+  pub fn isize(&mut self) -> isize {
+    self.usize() as isize
+  }
+
+  /// Samples a `u8` from the uniform distribution.
+
+  #[inline(always)]
+  pub fn u8(&mut self) -> u8 {
+    self.u64() as u8
+  }
+
+  /// Samples a `u16` from the uniform distribution.
+
+  #[inline(always)]
+  pub fn u16(&mut self) -> u16 {
+    self.u64() as u16
+  }
+
   /// Samples a `u32` from the uniform distribution.
 
   #[inline(always)]
@@ -167,16 +566,43 @@ impl Rng {
 
   #[inline(always)]
   pub fn u64(&mut self) -> u64 {
-    let s = self.state.get();
-    let x = s as u64;
-    let y = (s >> 64) as u64;
-    let u = y ^ y >> 19;
-    let v = x ^ y.rotate_right(7);
-    let w = x as u128 * x as u128;
-    let z = y.wrapping_add(w as u64 ^ (w >> 64) as u64);
-    let s = u as u128 ^ (v as u128) << 64;
-    self.state = unsafe { NonZeroU128::new_unchecked(s) };
-    z
+    self.core.next_u64()
+  }
+
+  /// Samples a `u128` from the uniform distribution.
+  ///
+  /// The first draw becomes the low half and the second draw the high half.
+
+  #[inline(always)]
+  pub fn u128(&mut self) -> u128 {
+    let x = self.u64() as u128;
+    let y = self.u64() as u128;
+    x | y << 64
+  }
+
+  /// Samples a `usize` from the uniform distribution.
+
+  #[inline(always)]
+  pub fn usize(&mut self) -> usize {
+    self.u64() as usize
+  }
+
+  /// Samples a `u8` from the uniform distribution over the range `0 ... n`.
+  ///
+  /// The upper bound is inclusive.
+
+  #[inline(always)]
+  pub fn bounded_u8(&mut self, n: u8) -> u8 {
+    self.bounded_u32(n as u32) as u8
+  }
+
+  /// Samples a `u16` from the uniform distribution over the range `0 ... n`.
+  ///
+  /// The upper bound is inclusive.
+
+  #[inline(always)]
+  pub fn bounded_u16(&mut self, n: u16) -> u16 {
+    self.bounded_u32(n as u32) as u16
   }
 
   /// Samples a `u32` from the uniform distribution over the range `0 ... n`.
@@ -224,6 +650,222 @@ impl Rng {
     z as u64
   }
 
+  /// Samples a `u32` from the uniform distribution over the range `0 ... n`,
+  /// drawing at most `max_draws` outputs from the generator.
+  ///
+  /// The upper bound is inclusive. Unlike [Rng::bounded_u32], the sample is
+  /// exactly uniform, because each draw is either accepted or rejected.
+  /// Returns `None` if every draw is rejected. The probability that any one
+  /// draw is rejected is less than `(n + 1) / 2³²`.
+
+  #[inline]
+  pub fn bounded_u32_with_budget(&mut self, n: u32, max_draws: u32) -> Option<u32> {
+    let Some(m) = n.checked_add(1) else {
+      return if max_draws == 0 { None } else { Some(self.u32()) };
+    };
+
+    let mut t = None;
+    (0 .. max_draws).find_map(|_| self.lemire_u32(m, &mut t))
+  }
+
+  /// Samples a `u64` from the uniform distribution over the range `0 ... n`,
+  /// drawing at most `max_draws` outputs from the generator.
+  ///
+  /// The upper bound is inclusive. Unlike [Rng::bounded_u64], the sample is
+  /// exactly uniform, because each draw is either accepted or rejected.
+  /// Returns `None` if every draw is rejected. The probability that any one
+  /// draw is rejected is less than `(n + 1) / 2⁶⁴`.
+
+  #[inline]
+  pub fn bounded_u64_with_budget(&mut self, n: u64, max_draws: u32) -> Option<u64> {
+    let Some(m) = n.checked_add(1) else {
+      return if max_draws == 0 { None } else { Some(self.u64()) };
+    };
+
+    let mut t = None;
+    (0 .. max_draws).find_map(|_| self.lemire_u64(m, &mut t))
+  }
+
+  /// Samples a `u32` from the uniform distribution over the range `0 ... n`.
+  ///
+  /// The upper bound is inclusive. Unlike [Rng::bounded_u32], the sample is
+  /// exactly uniform, because draws are rejected until one is accepted. The
+  /// probability that any one draw is rejected is less than `(n + 1) / 2³²`.
+
+  #[inline]
+  pub fn bounded_u32_exact(&mut self, n: u32) -> u32 {
+    let Some(m) = n.checked_add(1) else { return self.u32(); };
+
+    let mut t = None;
+    loop {
+      if let Some(x) = self.lemire_u32(m, &mut t) { return x; }
+    }
+  }
+
+  /// Samples a `u64` from the uniform distribution over the range `0 ... n`.
+  ///
+  /// The upper bound is inclusive. Unlike [Rng::bounded_u64], the sample is
+  /// exactly uniform, because draws are rejected until one is accepted. The
+  /// probability that any one draw is rejected is less than `(n + 1) / 2⁶⁴`.
+
+  #[inline]
+  pub fn bounded_u64_exact(&mut self, n: u64) -> u64 {
+    let Some(m) = n.checked_add(1) else { return self.u64(); };
+
+    let mut t = None;
+    loop {
+      if let Some(x) = self.lemire_u64(m, &mut t) { return x; }
+    }
+  }
+
+  // Takes one draw for Lemire's nearly divisionless method, which samples
+  // from the range `0 .. m` for a nonzero `m`. A draw `x` is mapped to
+  // `floor(x * m / 2³²)`, and rejected if it falls into one of the
+  // `t = 2³² mod m` leftover values of the low half of the product. Returns
+  // `None` if the draw is rejected.
+  //
+  // The threshold `t` requires a division, so it is computed and cached in
+  // `t` only when the low half of the product is less than `m`, which is
+  // rare. Callers that sample the same range many times can pass it in
+  // precomputed.
+
+  #[inline(always)]
+  fn lemire_u32(&mut self, m: u32, t: &mut Option<u32>) -> Option<u32> {
+    let w = self.u32() as u64 * m as u64;
+    let lo = w as u32;
+    if lo >= m { return Some((w >> 32) as u32); }
+    let t = *t.get_or_insert_with(|| m.wrapping_neg() % m);
+    if lo >= t { Some((w >> 32) as u32) } else { None }
+  }
+
+  // Cf. `lemire_u32`.
+
+  #[inline(always)]
+  fn lemire_u64(&mut self, m: u64, t: &mut Option<u64>) -> Option<u64> {
+    let w = self.u64() as u128 * m as u128;
+    let lo = w as u64;
+    if lo >= m { return Some((w >> 64) as u64); }
+    let t = *t.get_or_insert_with(|| m.wrapping_neg() % m);
+    if lo >= t { Some((w >> 64) as u64) } else { None }
+  }
+
+  /// Samples a `usize` from the uniform distribution over the range
+  /// `0 ... n`.
+  ///
+  /// The upper bound is inclusive. This uses [Rng::bounded_u32] or
+  /// [Rng::bounded_u64] depending on the width of `usize`.
+
+  #[inline(always)]
+  pub fn bounded_usize(&mut self, n: usize) -> usize {
+    if usize::BITS <= 32 {
+      self.bounded_u32(n as u32) as usize
+    } else {
+      self.bounded_u64(n as u64) as usize
+    }
+  }
+
+  /// Samples an index into a collection of length `len` from the uniform
+  /// distribution over the range `0 .. len`.
+  ///
+  /// Unlike the `bounded_*` methods, the upper bound is exclusive.
+  ///
+  /// ```
+  /// use dandelion::Rng;
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// let colors = ["red", "green", "blue"];
+  /// let color = colors[rng.index(colors.len())];
+  /// ```
+  ///
+  /// # Panics
+  ///
+  /// Panics if `len == 0`.
+
+  #[inline(always)]
+  pub fn index(&mut self, len: usize) -> usize {
+    assert!(len != 0, "index: len == 0");
+    self.bounded_usize(len - 1)
+  }
+
+  /// Samples a `u32` from the uniform distribution over the range `0 .. n`.
+  ///
+  /// Unlike [Rng::bounded_u32], the upper bound is exclusive.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `n == 0`.
+
+  #[inline(always)]
+  pub fn below_u32(&mut self, n: u32) -> u32 {
+    assert!(n != 0, "below_u32: n == 0");
+    self.bounded_u32(n - 1)
+  }
+
+  /// Samples a `u64` from the uniform distribution over the range `0 .. n`.
+  ///
+  /// Unlike [Rng::bounded_u64], the upper bound is exclusive.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `n == 0`.
+
+  #[inline(always)]
+  pub fn below_u64(&mut self, n: u64) -> u64 {
+    assert!(n != 0, "below_u64: n == 0");
+    self.bounded_u64(n - 1)
+  }
+
+  /// Samples a `u128` from the uniform distribution over the range
+  /// `0 ... n`.
+  ///
+  /// The upper bound is inclusive.
+
+  #[inline(always)]
+  pub fn bounded_u128(&mut self, n: u128) -> u128 {
+    // Cf. `bounded_u64`. This procedure computes
+    //
+    //   floor((k * n + k) / 2¹⁹²)
+    //
+    // where k is sampled approximately uniformly from 0 ... 2¹⁹² - 1, one
+    // 64-bit limb at a time starting from the least significant.
+
+    #[inline(always)]
+    fn step(a: u64, n: u128, c: u128) -> u128 {
+      // Computes floor((a * n + a + c) / 2⁶⁴) without overflowing.
+
+      let a = a as u128;
+      let u = a * (n as u64 as u128) + a + (c as u64 as u128);
+      a * (n >> 64) + (u >> 64) + (c >> 64)
+    }
+
+    let x = self.u64();
+    let y = self.u64();
+    let z = self.u64();
+    let u = step(x, n, 0);
+    let v = step(y, n, u);
+    step(z, n, v)
+  }
+
+  /// Samples a `i8` from the uniform distribution over the range `lo ... hi`.
+  ///
+  /// The lower and upper bounds are inclusive, and the range can wrap around
+  /// from `i8::MAX` to `i8::MIN`.
+
+  #[inline(always)]
+  pub fn between_i8(&mut self, lo: i8, hi: i8) -> i8 {
+    self.between_u8(lo as u8, hi as u8) as i8
+  }
+
+  /// Samples a `i16` from the uniform distribution over the range `lo ... hi`.
+  ///
+  /// The lower and upper bounds are inclusive, and the range can wrap around
+  /// from `i16::MAX` to `i16::MIN`.
+
+  #[inline(always)]
+  pub fn between_i16(&mut self, lo: i16, hi: i16) -> i16 {
+    self.between_u16(lo as u16, hi as u16) as i16
+  }
+
   /// Samples a `i32` from the uniform distribution over the range `lo ... hi`.
   ///
   /// The lower and upper bounds are inclusive, and the range can wrap around
@@ -244,6 +886,48 @@ impl Rng {
     self.between_u64(lo as u64, hi as u64) as i64
   }
 
+  /// Samples a `i128` from the uniform distribution over the range
+  /// `lo ... hi`.
+  ///
+  /// The lower and upper bounds are inclusive, and the range can wrap around
+  /// from `i128::MAX` to `i128::MIN`.
+
+  #[inline(always)]
+  pub fn between_i128(&mut self, lo: i128, hi: i128) -> i128 {
+    self.between_u128(lo as u128, hi as u128) as i128
+  }
+
+  /// Samples a `isize` from the uniform distribution over the range
+  /// `lo ... hi`.
+  ///
+  /// The lower and upper bounds are inclusive, and the range can wrap around
+  /// from `isize::MAX` to `isize::MIN`.
+
+  #[inline(always)]
+  pub fn between_isize(&mut self, lo: isize, hi: isize) -> isize {
+    self.between_usize(lo as usize, hi as usize) as isize
+  }
+
+  /// Samples a `u8` from the uniform distribution over the range `lo ... hi`.
+  ///
+  /// The lower and upper bounds are inclusive, and the range can wrap around
+  /// from `u8::MAX` to `u8::MIN`.
+
+  #[inline(always)]
+  pub fn between_u8(&mut self, lo: u8, hi: u8) -> u8 {
+    lo.wrapping_add(self.bounded_u8(hi.wrapping_sub(lo)))
+  }
+
+  /// Samples a `u16` from the uniform distribution over the range `lo ... hi`.
+  ///
+  /// The lower and upper bounds are inclusive, and the range can wrap around
+  /// from `u16::MAX` to `u16::MIN`.
+
+  #[inline(always)]
+  pub fn between_u16(&mut self, lo: u16, hi: u16) -> u16 {
+    lo.wrapping_add(self.bounded_u16(hi.wrapping_sub(lo)))
+  }
+
   /// Samples a `u32` from the uniform distribution over the range `lo ... hi`.
   ///
   /// The lower and upper bounds are inclusive, and the range can wrap around
@@ -259,52 +943,282 @@ impl Rng {
   /// The lower and upper bounds are inclusive, and the range can wrap around
   /// from `u64::MAX` to `u64::MIN`.
 
-  #[inline(always)]
-  pub fn between_u64(&mut self, lo: u64, hi: u64) -> u64 {
-    lo.wrapping_add(self.bounded_u64(hi.wrapping_sub(lo)))
+  #[inline(always)]
+  pub fn between_u64(&mut self, lo: u64, hi: u64) -> u64 {
+    lo.wrapping_add(self.bounded_u64(hi.wrapping_sub(lo)))
+  }
+
+  /// Samples a `u128` from the uniform distribution over the range
+  /// `lo ... hi`.
+  ///
+  /// The lower and upper bounds are inclusive, and the range can wrap around
+  /// from `u128::MAX` to `u128::MIN`.
+
+  #[inline(always)]
+  pub fn between_u128(&mut self, lo: u128, hi: u128) -> u128 {
+    lo.wrapping_add(self.bounded_u128(hi.wrapping_sub(lo)))
+  }
+
+  /// Samples a `usize` from the uniform distribution over the range
+  /// `lo ... hi`.
+  ///
+  /// The lower and upper bounds are inclusive, and the range can wrap around
+  /// from `usize::MAX` to `usize::MIN`.
+
+  #[inline(always)]
+  pub fn between_usize(&mut self, lo: usize, hi: usize) -> usize {
+    lo.wrapping_add(self.bounded_usize(hi.wrapping_sub(lo)))
+  }
+
+  /// Samples a `f32` from a distribution that approximates the uniform
+  /// distribution over the real interval [0, 1].
+  ///
+  /// The distribution is the same as the one produced by the following
+  /// procedure:
+  ///
+  /// - Sample a real number from the uniform distribution on [0, 1].
+  /// - Round to the nearest multiple of 2⁻⁶³.
+  /// - Round to a `f32` using the default rounding mode.
+  ///
+  /// An output zero will always be +0, never -0.
+
+  #[inline(always)]
+  pub fn f32(&mut self) -> f32 {
+    let x = self.i64();
+    let x = f32::from_bits(0x2000_0000) * x as f32;
+    f32::from_bits(0x7fff_ffff & x.to_bits())
+  }
+
+  /// Samples a `f64` from a distribution that approximates the uniform
+  /// distribution over the real interval [0, 1].
+  ///
+  /// The distribution is the same as the one produced by the following
+  /// procedure:
+  ///
+  /// - Sample a real number from the uniform distribution on [0, 1].
+  /// - Round to the nearest multiple of 2⁻⁶³.
+  /// - Round to a `f64` using the default rounding mode.
+  ///
+  /// An output zero will always be +0, never -0.
+
+  #[inline(always)]
+  pub fn f64(&mut self) -> f64 {
+    // The conversion into a `f64` is two instructions on aarch64:
+    //
+    //	 scvtf d0, x8, #63
+	  //   fabs d0, d0
+
+    let x = self.i64();
+    let x = f64::from_bits(0x3c00_0000_0000_0000) * x as f64;
+    f64::from_bits(0x7fff_ffff_ffff_ffff & x.to_bits())
+  }
+
+  /// Samples a `f32` from a distribution that approximates the uniform
+  /// distribution over the real interval [lo, hi].
+  ///
+  /// The sample is computed as `lo + (hi - lo) * u`, where `u` is sampled with
+  /// [Rng::f32], and then clamped to `lo ... hi`. Because of rounding, some
+  /// values near the endpoints may be slightly more or less likely than under
+  /// the exact distribution, but the output is never outside of the interval.
+  /// Both endpoints can be sampled.
+  ///
+  /// # Panics
+  ///
+  /// Panics if either bound is not finite or if `lo > hi`.
+
+  #[inline]
+  pub fn between_f32(&mut self, lo: f32, hi: f32) -> f32 {
+    assert!(lo.is_finite() && hi.is_finite(), "between_f32: non-finite bound");
+    assert!(lo <= hi, "between_f32: lo > hi");
+
+    let u = self.f32();
+    let d = hi - lo;
+
+    // If the width of the interval overflows, we compute with halved values,
+    // which cannot overflow.
+
+    let x =
+      if d.is_finite() {
+        lo + d * u
+      } else {
+        2.0 * (0.5 * lo + (0.5 * hi - 0.5 * lo) * u)
+      };
+
+    x.clamp(lo, hi)
+  }
+
+  /// Samples a `f64` from a distribution that approximates the uniform
+  /// distribution over the real interval [lo, hi].
+  ///
+  /// The sample is computed as `lo + (hi - lo) * u`, where `u` is sampled with
+  /// [Rng::f64], and then clamped to `lo ... hi`. Because of rounding, some
+  /// values near the endpoints may be slightly more or less likely than under
+  /// the exact distribution, but the output is never outside of the interval.
+  /// Both endpoints can be sampled.
+  ///
+  /// # Panics
+  ///
+  /// Panics if either bound is not finite or if `lo > hi`.
+
+  #[inline]
+  pub fn between_f64(&mut self, lo: f64, hi: f64) -> f64 {
+    assert!(lo.is_finite() && hi.is_finite(), "between_f64: non-finite bound");
+    assert!(lo <= hi, "between_f64: lo > hi");
+
+    let u = self.f64();
+    let d = hi - lo;
+
+    // Cf. `between_f32`.
+
+    let x =
+      if d.is_finite() {
+        lo + d * u
+      } else {
+        2.0 * (0.5 * lo + (0.5 * hi - 0.5 * lo) * u)
+      };
+
+    x.clamp(lo, hi)
+  }
+
+  /// Samples a `u64` from the range `lo ... hi` such that the logarithm of
+  /// the sample is approximately uniformly distributed.
+  ///
+  /// The lower and upper bounds are inclusive. The sample is the floor of a
+  /// real number sampled with [Rng::log_uniform_f64] from [lo, hi + 1], so the
+  /// probability of `k` is proportional to `ln((k + 1) / k)`. For example,
+  /// with `lo = 1` and `hi = 999` each of the ranges `1 ... 9`, `10 ... 99`,
+  /// and `100 ... 999` is equally likely.
+  ///
+  /// ```
+  /// use dandelion::Rng;
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// let input_size = rng.log_uniform_u64(16, 1 << 20);
+  /// ```
+  ///
+  /// # Panics
+  ///
+  /// Panics if `lo == 0` or `lo > hi`.
+
+  #[cfg(feature = "std")]
+  #[inline]
+  pub fn log_uniform_u64(&mut self, lo: u64, hi: u64) -> u64 {
+    assert!(lo != 0, "log_uniform_u64: lo == 0");
+    assert!(lo <= hi, "log_uniform_u64: lo > hi");
+
+    // The conversions into `f64` may round, and the conversion back saturates,
+    // so we clamp the result into the range.
+
+    let x = self.log_uniform_f64(lo as f64, hi as f64 + 1.0);
+    (x as u64).clamp(lo, hi)
+  }
+
+  /// Samples a `f64` from the real interval [lo, hi] such that the logarithm
+  /// of the sample is approximately uniformly distributed.
+  ///
+  /// The output is never outside of the interval.
+  ///
+  /// ```
+  /// use dandelion::Rng;
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// let learning_rate = rng.log_uniform_f64(1e-5, 1e-1);
+  /// ```
+  ///
+  /// # Panics
+  ///
+  /// Panics if either bound is not finite and positive or if `lo > hi`.
+
+  #[cfg(feature = "std")]
+  #[inline]
+  pub fn log_uniform_f64(&mut self, lo: f64, hi: f64) -> f64 {
+    assert!(lo > 0.0 && hi.is_finite(), "log_uniform_f64: bound not finite and positive");
+    assert!(lo <= hi, "log_uniform_f64: lo > hi");
+
+    let a = lo.ln();
+    let b = hi.ln();
+    let x = (a + (b - a) * self.f64()).exp();
+    x.clamp(lo, hi)
   }
 
   /// Samples a `f32` from a distribution that approximates the uniform
-  /// distribution over the real interval [0, 1].
+  /// distribution over the real interval [-1, 1].
   ///
   /// The distribution is the same as the one produced by the following
   /// procedure:
   ///
-  /// - Sample a real number from the uniform distribution on [0, 1].
+  /// - Sample a real number from the uniform distribution on [-1, 1].
   /// - Round to the nearest multiple of 2⁻⁶³.
   /// - Round to a `f32` using the default rounding mode.
   ///
   /// An output zero will always be +0, never -0.
 
   #[inline(always)]
-  pub fn f32(&mut self) -> f32 {
+  pub fn f32_signed(&mut self) -> f32 {
+    // This is `f32` without the absolute value.
+
     let x = self.i64();
-    let x = f32::from_bits(0x2000_0000) * x as f32;
-    f32::from_bits(0x7fff_ffff & x.to_bits())
+    f32::from_bits(0x2000_0000) * x as f32
   }
 
   /// Samples a `f64` from a distribution that approximates the uniform
-  /// distribution over the real interval [0, 1].
+  /// distribution over the real interval [-1, 1].
   ///
   /// The distribution is the same as the one produced by the following
   /// procedure:
   ///
-  /// - Sample a real number from the uniform distribution on [0, 1].
+  /// - Sample a real number from the uniform distribution on [-1, 1].
   /// - Round to the nearest multiple of 2⁻⁶³.
   /// - Round to a `f64` using the default rounding mode.
   ///
   /// An output zero will always be +0, never -0.
 
   #[inline(always)]
-  pub fn f64(&mut self) -> f64 {
-    // The conversion into a `f64` is two instructions on aarch64:
-    //
-    //	 scvtf d0, x8, #63
-	  //   fabs d0, d0
+  pub fn f64_signed(&mut self) -> f64 {
+    // This is `f64` without the absolute value, so the conversion is a single
+    // instruction on aarch64.
 
     let x = self.i64();
-    let x = f64::from_bits(0x3c00_0000_0000_0000) * x as f64;
-    f64::from_bits(0x7fff_ffff_ffff_ffff & x.to_bits())
+    f64::from_bits(0x3c00_0000_0000_0000) * x as f64
+  }
+
+  /// Shuffles the elements of the slice so that every permutation is
+  /// equally likely.
+  ///
+  /// This is the Fisher-Yates algorithm, with one draw for each element after
+  /// the first.
+  ///
+  /// ```
+  /// use dandelion::Rng;
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// let mut deck: Vec<u32> = (0 .. 52).collect();
+  /// rng.shuffle(&mut deck);
+  /// ```
+
+  #[inline]
+  pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+    for i in (1 .. slice.len()).rev() {
+      let j = self.bounded_u64(i as u64) as usize;
+      slice.swap(i, j);
+    }
+  }
+
+  // Samples a pair of independent `f64`s from the standard normal
+  // distribution using Marsaglia's polar method.
+
+  #[cfg(feature = "std")]
+  #[inline]
+  fn normal_pair(&mut self) -> (f64, f64) {
+    loop {
+      let x = self.f64_signed();
+      let y = self.f64_signed();
+      let s = x * x + y * y;
+      if s < 1.0 && s > 0.0 {
+        let t = (-2.0 * s.ln() / s).sqrt();
+        return (x * t, y * t);
+      }
+    }
   }
 
   #[inline(always)]
@@ -340,7 +1254,7 @@ impl Rng {
       6 => *get_chunk_mut(dst, 0) = *get_chunk::<u8, 6>(&x.to_le_bytes(), 0),
       7 => *get_chunk_mut(dst, 0) = *get_chunk::<u8, 7>(&x.to_le_bytes(), 0),
       8 => *get_chunk_mut(dst, 0) = *get_chunk::<u8, 8>(&x.to_le_bytes(), 0),
-      _ => unsafe { core::hint::unreachable_unchecked() }
+      _ => unreachable!()
     }
   }
 
@@ -357,10 +1271,200 @@ impl Rng {
     self.bytes_inlined(&mut buf);
     buf
   }
+
+  /// Fills the provided buffer with independent `f32`s sampled as with
+  /// [Rng::f32].
+  ///
+  /// The values are the same as those produced by calling [Rng::f32]
+  /// repeatedly.
+
+  pub fn fill_f32(&mut self, dst: &mut [f32]) {
+    for x in dst.iter_mut() {
+      *x = self.f32();
+    }
+  }
+
+  /// Fills the provided buffer with independent `f64`s sampled as with
+  /// [Rng::f64].
+  ///
+  /// The values are the same as those produced by calling [Rng::f64]
+  /// repeatedly.
+
+  pub fn fill_f64(&mut self, dst: &mut [f64]) {
+    for x in dst.iter_mut() {
+      *x = self.f64();
+    }
+  }
+}
+
+// Computes the next state and the output.
+
+#[inline(always)]
+const fn step(state: NonZeroU128) -> (NonZeroU128, u64) {
+  let s = state.get();
+  let x = s as u64;
+  let y = (s >> 64) as u64;
+  let u = y ^ y >> 19;
+  let v = x ^ y.rotate_right(7);
+  let w = x as u128 * x as u128;
+  let z = y.wrapping_add(w as u64 ^ (w >> 64) as u64);
+  let s = u as u128 ^ (v as u128) << 64;
+  (unsafe { NonZeroU128::new_unchecked(s) }, z)
+}
+
+// The `const` methods on `Dandelion` take the core by value and return it
+// along with the sample, so that they can be chained in `const` and `static`
+// initializers. Each produces the same value as the corresponding method on
+// `Rng`.
+
+impl Dandelion {
+  /// Creates a core with the same initial state as [Rng::from_u64].
+  ///
+  /// ```
+  /// use dandelion::Dandelion;
+  ///
+  /// const PERMUTATION: [u8; 4] = {
+  ///   let mut a = [0, 1, 2, 3];
+  ///   let mut core = Dandelion::from_u64(0);
+  ///   let mut i = a.len() - 1;
+  ///   while i > 0 {
+  ///     let (next, j) = core.bounded_u64(i as u64);
+  ///     let t = a[i];
+  ///     a[i] = a[j as usize];
+  ///     a[j as usize] = t;
+  ///     core = next;
+  ///     i -= 1;
+  ///   }
+  ///   a
+  /// };
+  /// ```
+
+  pub const fn from_u64(seed: u64) -> Self {
+    Rng::from_u64(seed).core
+  }
+
+  /// Creates a core with a particular initial state. See [Rng::from_state].
+  ///
+  /// Unlike [Rng::from_state], this is not checked by the `debug_diagnostics`
+  /// feature, so it can be used for deliberately structured states in tests.
+
+  #[inline(always)]
+  pub const fn from_state(state: NonZeroU128) -> Self {
+    Self { state }
+  }
+
+  /// Retrieves the current state.
+
+  #[inline(always)]
+  pub const fn state(&self) -> NonZeroU128 {
+    self.state
+  }
+
+  /// See [Rng::u64].
+
+  #[inline(always)]
+  pub const fn u64(self) -> (Self, u64) {
+    let (state, x) = step(self.state);
+    (Self { state }, x)
+  }
+
+  /// See [Rng::bounded_u32].
+
+  #[inline(always)]
+  pub const fn bounded_u32(self, n: u32) -> (Self, u32) {
+    let (core, x) = self.u64();
+    let (core, y) = core.u64();
+    let x = x as u128;
+    let y = y as u128;
+    let n = n as u128;
+    let u = x * n + x >> 64;
+    let v = y * n + y;
+    let z = u + v >> 64;
+    (core, z as u32)
+  }
+
+  /// See [Rng::bounded_u64].
+
+  #[inline(always)]
+  pub const fn bounded_u64(self, n: u64) -> (Self, u64) {
+    let (core, x) = self.u64();
+    let (core, y) = core.u64();
+    let x = x as u128;
+    let y = y as u128;
+    let n = n as u128;
+    let u = x * n + x >> 64;
+    let v = y * n + y;
+    let z = u + v >> 64;
+    (core, z as u64)
+  }
+
+  /// See [Rng::between_i32].
+
+  #[inline(always)]
+  pub const fn between_i32(self, lo: i32, hi: i32) -> (Self, i32) {
+    let (core, x) = self.between_u32(lo as u32, hi as u32);
+    (core, x as i32)
+  }
+
+  /// See [Rng::between_i64].
+
+  #[inline(always)]
+  pub const fn between_i64(self, lo: i64, hi: i64) -> (Self, i64) {
+    let (core, x) = self.between_u64(lo as u64, hi as u64);
+    (core, x as i64)
+  }
+
+  /// See [Rng::between_u32].
+
+  #[inline(always)]
+  pub const fn between_u32(self, lo: u32, hi: u32) -> (Self, u32) {
+    let (core, x) = self.bounded_u32(hi.wrapping_sub(lo));
+    (core, lo.wrapping_add(x))
+  }
+
+  /// See [Rng::between_u64].
+
+  #[inline(always)]
+  pub const fn between_u64(self, lo: u64, hi: u64) -> (Self, u64) {
+    let (core, x) = self.bounded_u64(hi.wrapping_sub(lo));
+    (core, lo.wrapping_add(x))
+  }
+
+  /// See [Rng::byte_array].
+
+  pub const fn byte_array<const N: usize>(self) -> (Self, [u8; N]) {
+    // The bytes are the little-endian encodings of consecutive outputs, with
+    // the last output truncated, which matches `Rng::bytes`.
+
+    let mut core = self;
+    let mut buf = [0u8; N];
+    let mut i = 0;
+    while i < N {
+      let (next, x) = core.u64();
+      let x = x.to_le_bytes();
+      let mut j = 0;
+      while j < 8 && i + j < N {
+        buf[i + j] = x[j];
+        j += 1;
+      }
+      core = next;
+      i += 8;
+    }
+    (core, buf)
+  }
+}
+
+impl Core for Dandelion {
+  #[inline(always)]
+  fn next_u64(&mut self) -> u64 {
+    let (state, x) = step(self.state);
+    self.state = state;
+    x
+  }
 }
 
 #[cfg(feature = "rand_core")]
-impl rand_core::RngCore for Rng {
+impl<C: Core> rand_core::RngCore for Rng<C> {
   #[inline(always)]
   fn next_u32(&mut self) -> u32 {
     self.u32()
@@ -389,7 +1493,7 @@ impl rand_core::SeedableRng for Rng {
     let s = u128::from_le_bytes(seed);
     let s = s | 1;
     let s = unsafe { NonZeroU128::new_unchecked(s) };
-    Self::from_state(s)
+    Self::from_core(Dandelion::from_state(s))
   }
 
   fn seed_from_u64(seed: u64) -> Self {
@@ -406,7 +1510,7 @@ impl rand_core::SeedableRng for Rng {
     let s = x as u128 ^ (y as u128) << 64;
     let s = s | 1;
     let s = unsafe { NonZeroU128::new_unchecked(s) };
-    Ok(Self::from_state(s))
+    Ok(Self::from_core(Dandelion::from_state(s)))
   }
 }
 
@@ -419,28 +1523,39 @@ pub mod thread_local {
 
   use std::cell::Cell;
   use std::num::NonZeroU128;
+  use std::ops::RangeBounds;
+  use std::time::Duration;
+  use std::time::SystemTime;
+  use crate::Dandelion;
+  use crate::RangeInt;
   use crate::Rng;
+  use crate::bits;
+  use crate::entropy::EntropySource;
 
   std::thread_local! {
     static RNG: Cell<Option<NonZeroU128>> = const {
       Cell::new(None)
     };
+
+    static BANK: Cell<u64> = const {
+      Cell::new(bits::EMPTY_BANK)
+    };
   }
 
   // The function `with` is *NOT* logically re-entrant, so we must not expose
-  // it publicly.
+  // it outside of the crate.
 
   #[inline(always)]
-  fn with<F, T>(f: F) -> T
+  pub(crate) fn with<F, T>(f: F) -> T
   where
     F: FnOnce(&mut Rng) -> T
   {
     RNG.with(|cell| {
       let mut rng =
         if let Some(s) = cell.get() {
-          Rng::from_state(s)
+          Rng::from_core(Dandelion::from_state(s))
         } else {
-          Rng::from_entropy()
+          Rng::from_operating_system()
         };
       let x = f(&mut rng);
       cell.set(Some(rng.state()));
@@ -448,6 +1563,26 @@ pub mod thread_local {
     })
   }
 
+  /// Seeds the current thread's generator as with [Rng::from_u64].
+
+  pub fn seed(seed: u64) {
+    RNG.with(|cell| cell.set(Some(Rng::from_u64(seed).state())));
+    BANK.with(|cell| cell.set(bits::EMPTY_BANK));
+  }
+
+  /// Seeds the current thread's generator from the given entropy source,
+  /// instead of from the operating system.
+  ///
+  /// If the source fails, the generator is unchanged and the error is
+  /// returned.
+
+  pub fn seed_from<E: EntropySource + ?Sized>(source: &mut E) -> Result<(), E::Error> {
+    let rng = Rng::from_entropy_source(source)?;
+    RNG.with(|cell| cell.set(Some(rng.state())));
+    BANK.with(|cell| cell.set(bits::EMPTY_BANK));
+    Ok(())
+  }
+
   /// See [Rng::split].
 
   pub fn split() -> Rng {
@@ -460,12 +1595,78 @@ pub mod thread_local {
     with(|rng| rng.bernoulli(p))
   }
 
+  /// See [Rng::bernoulli_ratio].
+
+  pub fn bernoulli_ratio(numerator: u64, denominator: u64) -> bool {
+    with(|rng| rng.bernoulli_ratio(numerator, denominator))
+  }
+
+  /// See [Rng::one_in].
+
+  pub fn one_in(n: u64) -> bool {
+    with(|rng| rng.one_in(n))
+  }
+
+  /// See [Rng::bernoulli_mask64].
+
+  pub fn bernoulli_mask64(p: f64) -> u64 {
+    with(|rng| rng.bernoulli_mask64(p))
+  }
+
+  /// See [Rng::mask_u32].
+
+  pub fn mask_u32(k: u32) -> u32 {
+    with(|rng| rng.mask_u32(k))
+  }
+
+  /// See [Rng::mask_u64].
+
+  pub fn mask_u64(k: u32) -> u64 {
+    with(|rng| rng.mask_u64(k))
+  }
+
+  /// Samples a `u64` whose low `n` bits are uniformly distributed and whose
+  /// other bits are zero.
+  ///
+  /// See [Rng::bits]. The current thread's generator has its own bank of
+  /// unused bits.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `n > 64`.
+
+  pub fn bits(n: u32) -> u64 {
+    assert!(n <= 64, "bits: n > 64");
+    BANK.with(|cell| {
+      let bank = cell.get();
+      let (x, bank) =
+        match bits::take_banked(bank, n) {
+          Some(t) => t,
+          None => bits::take_refilled(bank, with(|rng| rng.u64()), n),
+        };
+      cell.set(bank);
+      x
+    })
+  }
+
   /// See [Rng::bool].
 
   pub fn bool() -> bool {
     with(|rng| rng.bool())
   }
 
+  /// See [Rng::i8].
+
+  pub fn i8() -> i8 {
+    with(|rng| rng.i8())
+  }
+
+  /// See [Rng::i16].
+
+  pub fn i16() -> i16 {
+    with(|rng| rng.i16())
+  }
+
   /// See [Rng::i32].
 
   pub fn i32() -> i32 {
@@ -478,6 +1679,30 @@ pub mod thread_local {
     with(|rng| rng.i64())
   }
 
+  /// See [Rng::i128].
+
+  pub fn i128() -> i128 {
+    with(|rng| rng.i128())
+  }
+
+  /// See [Rng::isize].
+
+  pub fn isize() -> isize {
+    with(|rng| rng.isize())
+  }
+
+  /// See [Rng::u8].
+
+  pub fn u8() -> u8 {
+    with(|rng| rng.u8())
+  }
+
+  /// See [Rng::u16].
+
+  pub fn u16() -> u16 {
+    with(|rng| rng.u16())
+  }
+
   /// See [Rng::u32].
 
   pub fn u32() -> u32 {
@@ -490,6 +1715,30 @@ pub mod thread_local {
     with(|rng| rng.u64())
   }
 
+  /// See [Rng::u128].
+
+  pub fn u128() -> u128 {
+    with(|rng| rng.u128())
+  }
+
+  /// See [Rng::usize].
+
+  pub fn usize() -> usize {
+    with(|rng| rng.usize())
+  }
+
+  /// See [Rng::bounded_u8].
+
+  pub fn bounded_u8(n: u8) -> u8 {
+    with(|rng| rng.bounded_u8(n))
+  }
+
+  /// See [Rng::bounded_u16].
+
+  pub fn bounded_u16(n: u16) -> u16 {
+    with(|rng| rng.bounded_u16(n))
+  }
+
   /// See [Rng::bounded_u32].
 
   pub fn bounded_u32(n: u32) -> u32 {
@@ -502,6 +1751,64 @@ pub mod thread_local {
     with(|rng| rng.bounded_u64(n))
   }
 
+  /// See [Rng::bounded_u128].
+
+  pub fn bounded_u128(n: u128) -> u128 {
+    with(|rng| rng.bounded_u128(n))
+  }
+
+  /// See [Rng::bounded_usize].
+
+  pub fn bounded_usize(n: usize) -> usize {
+    with(|rng| rng.bounded_usize(n))
+  }
+
+  /// See [Rng::index].
+
+  pub fn index(len: usize) -> usize {
+    with(|rng| rng.index(len))
+  }
+
+  /// See [Rng::shuffle].
+
+  pub fn shuffle<T>(slice: &mut [T]) {
+    with(|rng| rng.shuffle(slice))
+  }
+
+  /// See [Rng::below_u32].
+
+  pub fn below_u32(n: u32) -> u32 {
+    with(|rng| rng.below_u32(n))
+  }
+
+  /// See [Rng::below_u64].
+
+  pub fn below_u64(n: u64) -> u64 {
+    with(|rng| rng.below_u64(n))
+  }
+
+  /// See [Rng::range].
+
+  pub fn range<T, R>(range: R) -> T
+  where
+    T: RangeInt,
+    R: RangeBounds<T>
+  {
+    with(|rng| rng.range(range))
+  }
+
+  /// See [Rng::between_i8].
+
+  pub fn between_i8(lo: i8, hi: i8) -> i8 {
+    with(|rng| rng.between_i8(lo, hi))
+  }
+
+  /// See [Rng::between_i16].
+
+  pub fn between_i16(lo: i16, hi: i16) -> i16 {
+    with(|rng| rng.between_i16(lo, hi))
+  }
+
   /// See [Rng::between_i32].
 
   pub fn between_i32(lo: i32, hi: i32) -> i32 {
@@ -514,6 +1821,30 @@ pub mod thread_local {
     with(|rng| rng.between_i64(lo, hi))
   }
 
+  /// See [Rng::between_i128].
+
+  pub fn between_i128(lo: i128, hi: i128) -> i128 {
+    with(|rng| rng.between_i128(lo, hi))
+  }
+
+  /// See [Rng::between_isize].
+
+  pub fn between_isize(lo: isize, hi: isize) -> isize {
+    with(|rng| rng.between_isize(lo, hi))
+  }
+
+  /// See [Rng::between_u8].
+
+  pub fn between_u8(lo: u8, hi: u8) -> u8 {
+    with(|rng| rng.between_u8(lo, hi))
+  }
+
+  /// See [Rng::between_u16].
+
+  pub fn between_u16(lo: u16, hi: u16) -> u16 {
+    with(|rng| rng.between_u16(lo, hi))
+  }
+
   /// See [Rng::between_u32].
 
   pub fn between_u32(lo: u32, hi: u32) -> u32 {
@@ -526,6 +1857,18 @@ pub mod thread_local {
     with(|rng| rng.between_u64(lo, hi))
   }
 
+  /// See [Rng::between_u128].
+
+  pub fn between_u128(lo: u128, hi: u128) -> u128 {
+    with(|rng| rng.between_u128(lo, hi))
+  }
+
+  /// See [Rng::between_usize].
+
+  pub fn between_usize(lo: usize, hi: usize) -> usize {
+    with(|rng| rng.between_usize(lo, hi))
+  }
+
   /// See [Rng::f32].
 
   pub fn f32() -> f32 {
@@ -538,12 +1881,60 @@ pub mod thread_local {
     with(|rng| rng.f64())
   }
 
+  /// See [Rng::between_f32].
+
+  pub fn between_f32(lo: f32, hi: f32) -> f32 {
+    with(|rng| rng.between_f32(lo, hi))
+  }
+
+  /// See [Rng::between_f64].
+
+  pub fn between_f64(lo: f64, hi: f64) -> f64 {
+    with(|rng| rng.between_f64(lo, hi))
+  }
+
+  /// See [Rng::f32_signed].
+
+  pub fn f32_signed() -> f32 {
+    with(|rng| rng.f32_signed())
+  }
+
+  /// See [Rng::f64_signed].
+
+  pub fn f64_signed() -> f64 {
+    with(|rng| rng.f64_signed())
+  }
+
   /// See [Rng::bytes].
 
   pub fn bytes(dst: &mut [u8]) {
     with(|rng| rng.bytes(dst))
   }
 
+  /// See [Rng::fill_f32].
+
+  pub fn fill_f32(dst: &mut [f32]) {
+    with(|rng| rng.fill_f32(dst))
+  }
+
+  /// See [Rng::fill_f64].
+
+  pub fn fill_f64(dst: &mut [f64]) {
+    with(|rng| rng.fill_f64(dst))
+  }
+
+  /// See [Rng::duration_between].
+
+  pub fn duration_between(lo: Duration, hi: Duration) -> Duration {
+    with(|rng| rng.duration_between(lo, hi))
+  }
+
+  /// See [Rng::system_time_between].
+
+  pub fn system_time_between(lo: SystemTime, hi: SystemTime) -> SystemTime {
+    with(|rng| rng.system_time_between(lo, hi))
+  }
+
   /// See [Rng::byte_array].
 
   pub fn byte_array<const N: usize>() -> [u8; N] {
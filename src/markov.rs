@@ -0,0 +1,111 @@
+//! Discrete-time Markov chains over a finite set of states.
+//!
+//! ```
+//! use dandelion::Rng;
+//! use dandelion::markov::MarkovChain;
+//!
+//! // A weather model where sunny days tend to follow sunny days.
+//!
+//! let mut chain = MarkovChain::new(&[[0.9, 0.1], [0.5, 0.5]]).unwrap();
+//! let mut rng = Rng::from_u64(0);
+//! let days: Vec<usize> = chain.iter(&mut rng).take(30).collect();
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::Core;
+use crate::Dandelion;
+use crate::Rng;
+use crate::alias::AliasTable;
+
+/// A Markov chain with states `0 ... n - 1` and a current state.
+///
+/// The transitions are sampled in constant time with an alias table for each
+/// row of the transition matrix.
+
+#[derive(Clone, Debug)]
+pub struct MarkovChain {
+  rows: Vec<AliasTable>,
+  state: usize,
+}
+
+/// An iterator over the successive states of a [MarkovChain].
+///
+/// This `struct` is created by [MarkovChain::iter].
+
+pub struct Iter<'a, C = Dandelion> {
+  chain: &'a mut MarkovChain,
+  rng: &'a mut Rng<C>,
+}
+
+impl MarkovChain {
+  /// Creates a Markov chain from an `n` by `n` matrix of transition weights,
+  /// starting in state 0.
+  ///
+  /// The probability of moving from state `i` to state `j` is proportional
+  /// to `matrix[i][j]`. The weights need not be normalized.
+  ///
+  /// Returns `None` if the matrix is empty or not square, if any weight is
+  /// negative or not finite, or if the weights in any row sum to zero.
+
+  pub fn new<R: AsRef<[f64]>>(matrix: &[R]) -> Option<Self> {
+    let n = matrix.len();
+    let rows =
+      matrix.iter()
+        .map(|row| if row.as_ref().len() == n { AliasTable::new(row.as_ref()) } else { None })
+        .collect::<Option<Vec<_>>>()?;
+    if rows.is_empty() { return None; }
+    Some(Self { rows, state: 0 })
+  }
+
+  /// Returns the number of states.
+
+  pub fn num_states(&self) -> usize {
+    self.rows.len()
+  }
+
+  /// Returns the current state.
+
+  pub fn state(&self) -> usize {
+    self.state
+  }
+
+  /// Sets the current state.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `state` is not less than the number of states.
+
+  pub fn set_state(&mut self, state: usize) {
+    assert!(state < self.rows.len(), "set_state: state out of range");
+    self.state = state;
+  }
+
+  /// Moves to a random next state and returns it.
+
+  #[inline]
+  pub fn step<C: Core>(&mut self, rng: &mut Rng<C>) -> usize {
+    self.state = self.rows[self.state].sample(rng);
+    self.state
+  }
+
+  /// Returns an iterator that repeatedly steps the chain and yields each
+  /// new state.
+
+  pub fn iter<'a, C: Core>(&'a mut self, rng: &'a mut Rng<C>) -> Iter<'a, C> {
+    Iter { chain: self, rng }
+  }
+}
+
+impl<C: Core> Iterator for Iter<'_, C> {
+  type Item = usize;
+
+  #[inline]
+  fn next(&mut self) -> Option<usize> {
+    Some(self.chain.step(self.rng))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (usize::MAX, None)
+  }
+}
@@ -0,0 +1,52 @@
+//! Bijective mixing functions.
+//!
+//! These are useful for deriving independent seeds from structured inputs,
+//! for example
+//!
+//! ```
+//! use dandelion::Rng;
+//! use dandelion::mix::mix64;
+//!
+//! let world_seed = 0x1234_5678;
+//! let entity_id = 42;
+//! let mut rng = Rng::from_u64(mix64(world_seed ^ entity_id));
+//! let _ = rng.u64();
+//! ```
+//!
+//! Both functions are permutations, so distinct inputs always produce
+//! distinct outputs. Both map zero to zero.
+
+/// Mixes the bits of a 128-bit integer.
+///
+/// This is the function used by [Rng::new](crate::Rng::new) and
+/// [Rng::from_u64](crate::Rng::from_u64) to derive an initial state from a
+/// seed.
+
+#[inline(always)]
+pub const fn mix128(x: u128) -> u128 {
+  // The hash uses the multiplier
+  //
+  //   M = round_nearest_odd(EULER_MASCHERONI * 2¹²⁸)
+  //
+  // The Euler-Mascheroni constant was selected because it is a well-known
+  // number in the range (0.5, 1.0).
+
+  const M: u128 = 0x93c4_67e3_7db0_c7a4_d1be_3f81_0152_cb57;
+
+  let x = x.wrapping_mul(M);
+  let x = x.swap_bytes();
+  let x = x.wrapping_mul(M);
+  let x = x.swap_bytes();
+  x.wrapping_mul(M)
+}
+
+/// Mixes the bits of a 64-bit integer.
+///
+/// This is the finalizer from SplitMix64.
+
+#[inline(always)]
+pub const fn mix64(x: u64) -> u64 {
+  let x = (x ^ x >> 30).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+  let x = (x ^ x >> 27).wrapping_mul(0x94d0_49bb_1331_11eb);
+  x ^ x >> 31
+}
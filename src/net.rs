@@ -0,0 +1,227 @@
+//! Sampling network addresses.
+
+use crate::Core;
+use crate::Rng;
+use core::net::IpAddr;
+use core::net::Ipv4Addr;
+use core::net::Ipv6Addr;
+use core::net::SocketAddr;
+use core::net::SocketAddrV4;
+use core::net::SocketAddrV6;
+use core::ops::RangeInclusive;
+
+impl<C: Core> Rng<C> {
+  /// Samples an `Ipv4Addr` from the uniform distribution on all IPv4
+  /// addresses.
+
+  #[inline(always)]
+  pub fn ipv4(&mut self) -> Ipv4Addr {
+    Ipv4Addr::from(self.u32())
+  }
+
+  /// Samples an `Ipv4Addr` from the uniform distribution on the private
+  /// address blocks 10.0.0.0/8, 172.16.0.0/12, and 192.168.0.0/16 of
+  /// RFC 1918.
+
+  pub fn ipv4_private(&mut self) -> Ipv4Addr {
+    let k = self.bounded_u32((1 << 24) + (1 << 20) + (1 << 16) - 1);
+    let x =
+      if k < 1 << 24 {
+        0x0a00_0000 | k
+      } else if k < (1 << 24) + (1 << 20) {
+        0xac10_0000 | k - (1 << 24)
+      } else {
+        0xc0a8_0000 | k - (1 << 24) - (1 << 20)
+      };
+    Ipv4Addr::from(x)
+  }
+
+  /// Samples an `Ipv4Addr` from the uniform distribution on the
+  /// documentation address blocks 192.0.2.0/24, 198.51.100.0/24, and
+  /// 203.0.113.0/24 of RFC 5737.
+
+  pub fn ipv4_documentation(&mut self) -> Ipv4Addr {
+    const BLOCKS: [u32; 3] = [0xc000_0200, 0xc633_6400, 0xcb00_7100];
+    let k = self.bounded_u32(3 * 256 - 1);
+    Ipv4Addr::from(BLOCKS[(k >> 8) as usize] | k & 0xff)
+  }
+
+  /// Samples an `Ipv4Addr` from the uniform distribution on the subnet
+  /// `network/prefix_len`, in CIDR notation.
+  ///
+  /// The first `prefix_len` bits of the result are those of `network`, and
+  /// the rest are random, so the host bits of `network` are ignored. The
+  /// network and broadcast addresses of the subnet are possible results.
+  ///
+  /// ```
+  /// use std::net::Ipv4Addr;
+  /// use dandelion::Rng;
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// let ip = rng.ipv4_in_subnet(Ipv4Addr::new(10, 1, 0, 0), 16);
+  /// assert!(ip.octets()[.. 2] == [10, 1]);
+  /// ```
+  ///
+  /// # Panics
+  ///
+  /// Panics if `prefix_len > 32`.
+
+  #[inline]
+  pub fn ipv4_in_subnet(&mut self, network: Ipv4Addr, prefix_len: u8) -> Ipv4Addr {
+    assert!(prefix_len <= 32, "ipv4_in_subnet: prefix_len > 32");
+    let mask = u32::MAX.checked_shl(32 - prefix_len as u32).unwrap_or(0);
+    Ipv4Addr::from(u32::from(network) & mask | self.u32() & ! mask)
+  }
+
+  /// Samples an `Ipv6Addr` from the uniform distribution on all IPv6
+  /// addresses.
+
+  #[inline(always)]
+  pub fn ipv6(&mut self) -> Ipv6Addr {
+    let x = self.u64() as u128;
+    let y = self.u64() as u128;
+    Ipv6Addr::from(x | y << 64)
+  }
+
+  /// Samples an `Ipv6Addr` from the uniform distribution on the unique local
+  /// address block fd00::/8 of RFC 4193.
+
+  #[inline(always)]
+  pub fn ipv6_unique_local(&mut self) -> Ipv6Addr {
+    let x = u128::from(self.ipv6());
+    Ipv6Addr::from(0xfd << 120 | x >> 8)
+  }
+
+  /// Samples an `Ipv6Addr` from the uniform distribution on the
+  /// documentation address block 2001:db8::/32 of RFC 3849.
+
+  #[inline(always)]
+  pub fn ipv6_documentation(&mut self) -> Ipv6Addr {
+    let x = u128::from(self.ipv6());
+    Ipv6Addr::from(0x2001_0db8 << 96 | x >> 32)
+  }
+
+  /// Samples an `Ipv6Addr` from the uniform distribution on the subnet
+  /// `network/prefix_len`, in CIDR notation.
+  ///
+  /// The first `prefix_len` bits of the result are those of `network`, and
+  /// the rest are random, so the host bits of `network` are ignored.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `prefix_len > 128`.
+
+  #[inline]
+  pub fn ipv6_in_subnet(&mut self, network: Ipv6Addr, prefix_len: u8) -> Ipv6Addr {
+    assert!(prefix_len <= 128, "ipv6_in_subnet: prefix_len > 128");
+    let mask = u128::MAX.checked_shl(128 - prefix_len as u32).unwrap_or(0);
+    Ipv6Addr::from(u128::from(network) & mask | u128::from(self.ipv6()) & ! mask)
+  }
+
+  /// Samples an `IpAddr` from the uniform distribution on the subnet
+  /// `network/prefix_len`, in CIDR notation.
+  ///
+  /// This is [Rng::ipv4_in_subnet] or [Rng::ipv6_in_subnet], depending on the
+  /// version of `network`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `prefix_len` is longer than the address.
+
+  pub fn ip_in_subnet(&mut self, network: IpAddr, prefix_len: u8) -> IpAddr {
+    match network {
+      IpAddr::V4(network) => IpAddr::V4(self.ipv4_in_subnet(network, prefix_len)),
+      IpAddr::V6(network) => IpAddr::V6(self.ipv6_in_subnet(network, prefix_len)),
+    }
+  }
+
+  /// Samples a `SocketAddrV4` with an address from the uniform distribution
+  /// on all IPv4 addresses and a port from the uniform distribution over
+  /// `port_range`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `port_range` is empty.
+
+  pub fn socket_addr_v4(&mut self, port_range: RangeInclusive<u16>) -> SocketAddrV4 {
+    assert!(! port_range.is_empty(), "socket_addr_v4: empty port range");
+    let ip = self.ipv4();
+    let port = self.between_u32(*port_range.start() as u32, *port_range.end() as u32) as u16;
+    SocketAddrV4::new(ip, port)
+  }
+
+  /// Samples a `SocketAddrV6` with an address from the uniform distribution
+  /// on all IPv6 addresses and a port from the uniform distribution over
+  /// `port_range`.
+  ///
+  /// The flow info and scope ID are zero.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `port_range` is empty.
+
+  pub fn socket_addr_v6(&mut self, port_range: RangeInclusive<u16>) -> SocketAddrV6 {
+    assert!(! port_range.is_empty(), "socket_addr_v6: empty port range");
+    let ip = self.ipv6();
+    let port = self.between_u16(*port_range.start(), *port_range.end());
+    SocketAddrV6::new(ip, port, 0, 0)
+  }
+
+  /// Samples a `SocketAddr` that is equally likely to be IPv4 or IPv6, as
+  /// with [Rng::socket_addr_v4] and [Rng::socket_addr_v6].
+  ///
+  /// # Panics
+  ///
+  /// Panics if `port_range` is empty.
+
+  pub fn socket_addr(&mut self, port_range: RangeInclusive<u16>) -> SocketAddr {
+    if self.bool() {
+      SocketAddr::V4(self.socket_addr_v4(port_range))
+    } else {
+      SocketAddr::V6(self.socket_addr_v6(port_range))
+    }
+  }
+
+  /// Samples a MAC address from the uniform distribution on locally
+  /// administered unicast addresses.
+  ///
+  /// Such addresses are not assigned to any manufacturer, so they are safe
+  /// to use for virtual interfaces and test fixtures.
+
+  #[inline(always)]
+  pub fn mac_address(&mut self) -> [u8; 6] {
+    self.mac_address_with_flags(true, false)
+  }
+
+  /// Samples a MAC address from the uniform distribution on addresses with
+  /// the given flags.
+  ///
+  /// The flags are the two low bits of the first octet: the U/L bit, which is
+  /// set for locally administered addresses, and the I/G bit, which is set
+  /// for multicast addresses. The other 46 bits are random.
+  ///
+  /// ```
+  /// use dandelion::Rng;
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// let group = rng.mac_address_with_flags(true, true);
+  /// assert!(group[0] & 0b11 == 0b11);
+  /// ```
+
+  #[inline(always)]
+  pub fn mac_address_with_flags(&mut self, locally_administered: bool, multicast: bool) -> [u8; 6] {
+    let [a, b, c, d, e, f, _, _] = self.u64().to_le_bytes();
+    [a & 0xfc | (locally_administered as u8) << 1 | multicast as u8, b, c, d, e, f]
+  }
+
+  /// Samples an EUI-64 identifier from the uniform distribution on
+  /// identifiers with the given flags.
+  ///
+  /// The flags have the same meaning as for [Rng::mac_address_with_flags].
+
+  #[inline(always)]
+  pub fn eui64_with_flags(&mut self, locally_administered: bool, multicast: bool) -> [u8; 8] {
+    let [a, b, c, d, e, f, g, h] = self.u64().to_le_bytes();
+    [a & 0xfc | (locally_administered as u8) << 1 | multicast as u8, b, c, d, e, f, g, h]
+  }
+}
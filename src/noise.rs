@@ -0,0 +1,123 @@
+//! Coherent noise seeded from a random number generator.
+//!
+//! ```
+//! use dandelion::Rng;
+//! use dandelion::noise::Perlin;
+//!
+//! let mut rng = Rng::from_u64(0);
+//! let perlin = Perlin::new(&mut rng);
+//! let height = perlin.noise2(1.5, 2.25);
+//! ```
+
+use crate::Core;
+use crate::Rng;
+use crate::floor;
+
+/// Ken Perlin's improved gradient noise in two and three dimensions.
+///
+/// The noise is a smooth function that is zero at every point with integer
+/// coordinates, takes values roughly in the range [-1, 1], and repeats with a
+/// period of 256 along each axis.
+
+#[derive(Clone)]
+pub struct Perlin { perm: [u8; 512] }
+
+#[inline(always)]
+fn fade(t: f64) -> f64 {
+  t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+#[inline(always)]
+fn lerp(t: f64, a: f64, b: f64) -> f64 {
+  a + t * (b - a)
+}
+
+#[inline(always)]
+fn grad2(hash: u8, x: f64, y: f64) -> f64 {
+  // One of the eight directions (±1, ±1), (±1, 0), and (0, ±1).
+
+  match hash & 7 {
+    0 => x + y,
+    1 => - x + y,
+    2 => x - y,
+    3 => - x - y,
+    4 => x,
+    5 => - x,
+    6 => y,
+    _ => - y,
+  }
+}
+
+#[inline(always)]
+fn grad3(hash: u8, x: f64, y: f64, z: f64) -> f64 {
+  // One of the twelve directions to the midpoints of the edges of a cube,
+  // with four of them repeated.
+
+  let h = hash & 15;
+  let u = if h < 8 { x } else { y };
+  let v = if h < 4 { y } else if h == 12 || h == 14 { x } else { z };
+  (if h & 1 == 0 { u } else { - u }) + (if h & 2 == 0 { v } else { - v })
+}
+
+impl Perlin {
+  /// Creates a noise function with a permutation table sampled from the
+  /// given random number generator.
+
+  pub fn new<C: Core>(rng: &mut Rng<C>) -> Self {
+    let mut p: [u8; 256] = core::array::from_fn(|i| i as u8);
+    rng.shuffle(&mut p);
+    Self { perm: core::array::from_fn(|i| p[i & 255]) }
+  }
+
+  /// Evaluates the two-dimensional noise function.
+
+  pub fn noise2(&self, x: f64, y: f64) -> f64 {
+    let p = &self.perm;
+    let i = floor(x) as i64;
+    let j = floor(y) as i64;
+    let x = x - i as f64;
+    let y = y - j as f64;
+    let i = (i & 255) as usize;
+    let j = (j & 255) as usize;
+    let u = fade(x);
+    let v = fade(y);
+    let a = p[i] as usize + j;
+    let b = p[i + 1] as usize + j;
+
+    lerp(v,
+      lerp(u, grad2(p[a], x, y), grad2(p[b], x - 1.0, y)),
+      lerp(u, grad2(p[a + 1], x, y - 1.0), grad2(p[b + 1], x - 1.0, y - 1.0)))
+  }
+
+  /// Evaluates the three-dimensional noise function.
+
+  pub fn noise3(&self, x: f64, y: f64, z: f64) -> f64 {
+    let p = &self.perm;
+    let i = floor(x) as i64;
+    let j = floor(y) as i64;
+    let k = floor(z) as i64;
+    let x = x - i as f64;
+    let y = y - j as f64;
+    let z = z - k as f64;
+    let i = (i & 255) as usize;
+    let j = (j & 255) as usize;
+    let k = (k & 255) as usize;
+    let u = fade(x);
+    let v = fade(y);
+    let w = fade(z);
+    let a = p[i] as usize + j;
+    let aa = p[a] as usize + k;
+    let ab = p[a + 1] as usize + k;
+    let b = p[i + 1] as usize + j;
+    let ba = p[b] as usize + k;
+    let bb = p[b + 1] as usize + k;
+
+    lerp(w,
+      lerp(v,
+        lerp(u, grad3(p[aa], x, y, z), grad3(p[ba], x - 1.0, y, z)),
+        lerp(u, grad3(p[ab], x, y - 1.0, z), grad3(p[bb], x - 1.0, y - 1.0, z))),
+      lerp(v,
+        lerp(u, grad3(p[aa + 1], x, y, z - 1.0), grad3(p[ba + 1], x - 1.0, y, z - 1.0)),
+        lerp(u, grad3(p[ab + 1], x, y - 1.0, z - 1.0), grad3(p[bb + 1], x - 1.0, y - 1.0, z - 1.0))))
+  }
+}
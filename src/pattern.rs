@@ -0,0 +1,291 @@
+//! Random strings that match a regular expression.
+//!
+//! The supported syntax is a subset of the usual regular expression syntax:
+//!
+//! - Literal characters, and `\` followed by a punctuation character to match
+//!   that character literally.
+//! - `.` for any printable ASCII character.
+//! - `\d`, `\w`, and `\s` for digits, word characters, and whitespace, and
+//!   `\n`, `\r`, and `\t`.
+//! - Character classes such as `[a-z0-9_]`, and negated classes such as
+//!   `[^"]`, whose complement is taken within the printable ASCII characters.
+//! - Groups `( ... )` and alternatives `|`.
+//! - The repetitions `?`, `*`, `+`, `{n}`, `{n,}`, and `{n,m}`. Unbounded
+//!   repetitions repeat at most 8 more times than their minimum.
+//! - The anchors `^` and `$` at the start and end of the pattern, which are
+//!   ignored.
+//!
+//! Each alternative and each number of repetitions is equally likely, and
+//! each character of a class is equally likely.
+//!
+//! ```
+//! use dandelion::Rng;
+//!
+//! let mut rng = Rng::from_u64(0);
+//! let email = rng.from_pattern(r"[a-z]{3,8}@(example|test)\.com");
+//! assert!(email.ends_with(".com"));
+//! ```
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::Core;
+use crate::Rng;
+
+const UNBOUNDED: u32 = 8;
+
+#[derive(Clone, Debug)]
+enum Node {
+  Literal(char),
+  Class(Vec<(char, char)>),
+  Group(Vec<Vec<Node>>),
+  Repeat(Box<Node>, u32, u32),
+}
+
+/// A parsed pattern.
+
+#[derive(Clone, Debug)]
+pub struct Pattern { root: Node }
+
+struct Parser<'a> { chars: core::iter::Peekable<core::str::Chars<'a>> }
+
+fn printable() -> Vec<(char, char)> {
+  vec![(' ', '~')]
+}
+
+fn normalize(mut ranges: Vec<(char, char)>) -> Vec<(char, char)> {
+  // Sorts and merges overlapping and adjacent ranges, so that no character
+  // is counted twice.
+
+  ranges.sort_unstable();
+  let mut out: Vec<(char, char)> = Vec::with_capacity(ranges.len());
+  for (lo, hi) in ranges {
+    match out.last_mut() {
+      Some((_, h)) if lo as u32 <= *h as u32 + 1 => *h = (*h).max(hi),
+      _ => out.push((lo, hi)),
+    }
+  }
+  out
+}
+
+fn complement(ranges: &[(char, char)]) -> Vec<(char, char)> {
+  (' ' ..= '~')
+    .filter(|c| ! ranges.iter().any(|&(lo, hi)| lo <= *c && *c <= hi))
+    .map(|c| (c, c))
+    .collect()
+}
+
+impl Parser<'_> {
+  fn alternatives(&mut self) -> Option<Vec<Vec<Node>>> {
+    let mut alts = vec![self.sequence()?];
+    while self.chars.next_if_eq(&'|').is_some() {
+      alts.push(self.sequence()?);
+    }
+    Some(alts)
+  }
+
+  fn sequence(&mut self) -> Option<Vec<Node>> {
+    let mut seq = Vec::new();
+    while let Some(&c) = self.chars.peek() {
+      if c == '|' || c == ')' { break; }
+      let atom = self.atom()?;
+      seq.push(self.repeat(atom)?);
+    }
+    Some(seq)
+  }
+
+  fn atom(&mut self) -> Option<Node> {
+    let node =
+      match self.chars.next()? {
+        '(' => {
+          let alts = self.alternatives()?;
+          let _ = self.chars.next_if_eq(&')')?;
+          Node::Group(alts)
+        }
+        '[' => Node::Class(self.class()?),
+        '.' => Node::Class(printable()),
+        '\\' => self.escape()?,
+        '*' | '+' | '?' | '{' | '}' | ']' | ')' => return None,
+        c => Node::Literal(c),
+      };
+    Some(node)
+  }
+
+  fn escape(&mut self) -> Option<Node> {
+    let node =
+      match self.chars.next()? {
+        'd' => Node::Class(vec![('0', '9')]),
+        'w' => Node::Class(vec![('0', '9'), ('A', 'Z'), ('_', '_'), ('a', 'z')]),
+        's' => Node::Class(vec![('\t', '\n'), ('\r', '\r'), (' ', ' ')]),
+        'n' => Node::Literal('\n'),
+        'r' => Node::Literal('\r'),
+        't' => Node::Literal('\t'),
+        c if c.is_ascii_punctuation() => Node::Literal(c),
+        _ => return None,
+      };
+    Some(node)
+  }
+
+  fn class_char(&mut self) -> Option<char> {
+    match self.chars.next()? {
+      '\\' =>
+        match self.escape()? {
+          Node::Literal(c) => Some(c),
+          _ => None,
+        },
+      c => Some(c),
+    }
+  }
+
+  fn class(&mut self) -> Option<Vec<(char, char)>> {
+    let negated = self.chars.next_if_eq(&'^').is_some();
+    let mut ranges = Vec::new();
+
+    loop {
+      match self.chars.peek()? {
+        ']' if ! ranges.is_empty() => { let _ = self.chars.next(); break; }
+        '\\' => {
+          // Escaped classes such as `\d` may appear inside a class.
+
+          let mut copy = self.chars.clone();
+          let _ = copy.next();
+          if let Some('d' | 'w' | 's') = copy.peek() {
+            let _ = self.chars.next();
+            let Some(Node::Class(r)) = self.escape() else { return None };
+            ranges.extend(r);
+            continue;
+          }
+        }
+        _ => {}
+      }
+
+      let lo = self.class_char()?;
+      let hi =
+        if self.chars.peek() == Some(&'-') && self.chars.clone().nth(1).is_some_and(|c| c != ']') {
+          let _ = self.chars.next();
+          self.class_char()?
+        } else {
+          lo
+        };
+      if lo > hi { return None; }
+      ranges.push((lo, hi));
+    }
+
+    let ranges = normalize(ranges);
+    let ranges = if negated { complement(&ranges) } else { ranges };
+    if ranges.is_empty() { return None; }
+    Some(normalize(ranges))
+  }
+
+  fn number(&mut self) -> Option<u32> {
+    let mut n = None;
+    while let Some(c) = self.chars.next_if(char::is_ascii_digit) {
+      n = Some(n.unwrap_or(0u32).checked_mul(10)?.checked_add(c as u32 - '0' as u32)?);
+    }
+    n
+  }
+
+  fn repeat(&mut self, atom: Node) -> Option<Node> {
+    let (lo, hi) =
+      match self.chars.peek() {
+        Some('?') => (0, 1),
+        Some('*') => (0, UNBOUNDED),
+        Some('+') => (1, 1 + UNBOUNDED),
+        Some('{') => {
+          let _ = self.chars.next();
+          let lo = self.number()?;
+          let hi =
+            if self.chars.next_if_eq(&',').is_some() {
+              match self.number() {
+                Some(hi) => hi,
+                None => lo.checked_add(UNBOUNDED)?,
+              }
+            } else {
+              lo
+            };
+          if self.chars.peek() != Some(&'}') || lo > hi { return None; }
+          (lo, hi)
+        }
+        _ => return Some(atom),
+      };
+    let _ = self.chars.next();
+    if let Some('?' | '*' | '+' | '{') = self.chars.peek() { return None; }
+    Some(Node::Repeat(Box::new(atom), lo, hi))
+  }
+}
+
+impl Pattern {
+  /// Parses a pattern, returning `None` if it is invalid or uses
+  /// unsupported syntax.
+
+  pub fn parse(pattern: &str) -> Option<Self> {
+    let pattern = pattern.strip_prefix('^').unwrap_or(pattern);
+    let pattern =
+      match pattern.strip_suffix('$') {
+        Some(p) if ! p.ends_with('\\') => p,
+        _ => pattern,
+      };
+    let mut p = Parser { chars: pattern.chars().peekable() };
+    let alts = p.alternatives()?;
+    if p.chars.next().is_some() { return None; }
+    Some(Self { root: Node::Group(alts) })
+  }
+
+  /// Samples a string that matches the pattern.
+
+  pub fn sample<C: Core>(&self, rng: &mut Rng<C>) -> String {
+    let mut out = String::new();
+    sample(&self.root, rng, &mut out);
+    out
+  }
+}
+
+fn sample<C: Core>(node: &Node, rng: &mut Rng<C>, out: &mut String) {
+  match node {
+    Node::Literal(c) => out.push(*c),
+    Node::Class(ranges) => {
+      let size = |&(lo, hi): &(char, char)| {
+        let close = |c: char| { let c = c as u32; if c < 0xd800 { c } else { c - 0x800 } };
+        close(hi) - close(lo) + 1
+      };
+      let total: u32 = ranges.iter().map(size).sum();
+      let mut k = rng.bounded_u32(total - 1);
+      for r in ranges {
+        if k < size(r) {
+          // The k-th character of the range, skipping surrogates.
+          let c = if (r.0 as u32) < 0xd800 && r.0 as u32 + k >= 0xd800 { r.0 as u32 + k + 0x800 } else { r.0 as u32 + k };
+          let Some(c) = char::from_u32(c) else { unreachable!() };
+          out.push(c);
+          return;
+        }
+        k -= size(r);
+      }
+    }
+    Node::Group(alts) => {
+      let seq = &alts[rng.bounded_u64(alts.len() as u64 - 1) as usize];
+      for n in seq { sample(n, rng, out); }
+    }
+    Node::Repeat(n, lo, hi) => {
+      for _ in 0 .. rng.between_u32(*lo, *hi) { sample(n, rng, out); }
+    }
+  }
+}
+
+impl<C: Core> Rng<C> {
+  /// Samples a string that matches the given pattern.
+  ///
+  /// See the [pattern](crate::pattern) module for the supported syntax. To
+  /// sample from the same pattern repeatedly, parse it once with
+  /// [Pattern::parse].
+  ///
+  /// # Panics
+  ///
+  /// Panics if the pattern is invalid.
+
+  pub fn from_pattern(&mut self, pattern: &str) -> String {
+    let Some(p) = Pattern::parse(pattern) else { panic!("invalid pattern") };
+    p.sample(self)
+  }
+}
@@ -0,0 +1,261 @@
+//! Low-discrepancy sequences for quasi-Monte Carlo integration.
+//!
+//! Each generator is an iterator over points in the unit cube [0, 1)^N.
+//! Randomized variants, which are seeded from an [Rng], give unbiased
+//! estimates whose variance can be measured across independent seeds.
+//!
+//! ```
+//! use dandelion::Rng;
+//! use dandelion::quasi::Sobol;
+//!
+//! let mut rng = Rng::from_u64(0);
+//! let sum: f64 = Sobol::<2>::scrambled(&mut rng).take(1024).map(|[x, y]| x * y).sum();
+//! let mean = sum / 1024.0;
+//! ```
+
+use crate::Core;
+use crate::Rng;
+use crate::mix::mix64 as mix;
+
+/// The largest supported dimension for [Sobol] and [Halton].
+
+pub const MAX_DIMENSION: usize = 16;
+
+// The primitive polynomials and initial direction numbers for dimensions two
+// and above, from the "new-joe-kuo-6.21201" table of S. Joe and F. Y. Kuo.
+//
+// Each entry is (s, a, m) where s is the degree of the polynomial, the bits
+// of a are its interior coefficients, and m holds the initial odd integers.
+
+const JOE_KUO: [(u32, u32, [u32; 6]); MAX_DIMENSION - 1] = [
+  (1, 0, [1, 0, 0, 0, 0, 0]),
+  (2, 1, [1, 3, 0, 0, 0, 0]),
+  (3, 1, [1, 3, 1, 0, 0, 0]),
+  (3, 2, [1, 1, 1, 0, 0, 0]),
+  (4, 1, [1, 1, 3, 3, 0, 0]),
+  (4, 4, [1, 3, 5, 13, 0, 0]),
+  (5, 2, [1, 1, 5, 5, 17, 0]),
+  (5, 4, [1, 1, 5, 5, 5, 0]),
+  (5, 7, [1, 1, 7, 11, 19, 0]),
+  (5, 11, [1, 1, 5, 1, 1, 0]),
+  (5, 13, [1, 1, 1, 3, 11, 0]),
+  (5, 14, [1, 3, 5, 5, 31, 0]),
+  (6, 1, [1, 3, 3, 9, 7, 49]),
+  (6, 13, [1, 1, 1, 15, 21, 21]),
+  (6, 16, [1, 3, 1, 13, 27, 49]),
+];
+
+const fn directions() -> [[u32; 32]; MAX_DIMENSION] {
+  let mut v = [[0; 32]; MAX_DIMENSION];
+
+  let mut j = 0;
+  while j < 32 {
+    v[0][j] = 1 << 31 - j;
+    j += 1;
+  }
+
+  let mut d = 1;
+  while d < MAX_DIMENSION {
+    let (s, a, m) = JOE_KUO[d - 1];
+    let s = s as usize;
+    let mut j = 0;
+    while j < 32 {
+      if j < s {
+        v[d][j] = m[j] << 31 - j;
+      } else {
+        let mut x = v[d][j - s] ^ v[d][j - s] >> s;
+        let mut k = 1;
+        while k < s {
+          if a >> s - 1 - k & 1 != 0 { x ^= v[d][j - k]; }
+          k += 1;
+        }
+        v[d][j] = x;
+      }
+      j += 1;
+    }
+    d += 1;
+  }
+
+  v
+}
+
+static DIRECTIONS: [[u32; 32]; MAX_DIMENSION] = directions();
+
+const PRIMES: [u32; MAX_DIMENSION] =
+  [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53];
+
+#[inline(always)]
+fn node_hash(seed: u64, node: u64) -> u64 {
+  mix(seed ^ mix(node))
+}
+
+fn owen_scramble(x: u32, seed: u64) -> u32 {
+  // Each bit is flipped according to a hash of the bits above it, which is a
+  // uniformly random nested permutation of the binary digits.
+
+  let mut y = x;
+  for i in 0 .. 32 {
+    let node = (x as u64 | 1 << 32) >> 32 - i;
+    y ^= ((node_hash(seed, node) & 1) as u32) << 31 - i;
+  }
+  y
+}
+
+/// A Sobol sequence in `N` dimensions.
+///
+/// The first 2^m points of the sequence have the property that every
+/// one-dimensional projection puts exactly one point in each interval of
+/// length 2^-m.
+
+#[derive(Clone, Debug)]
+pub struct Sobol<const N: usize> {
+  index: u64,
+  seeds: Option<[u64; N]>,
+}
+
+impl<const N: usize> Sobol<N> {
+  const CHECK_DIMENSION: () = assert!(N <= MAX_DIMENSION);
+
+  /// Creates a Sobol sequence, starting at the origin.
+
+  pub fn new() -> Self {
+    let () = Self::CHECK_DIMENSION;
+    Self { index: 0, seeds: None }
+  }
+
+  /// Creates a Sobol sequence with an Owen scrambling sampled from the given
+  /// random number generator.
+  ///
+  /// Each point of a scrambled sequence is uniformly distributed in the unit
+  /// cube, and the stratification properties of the sequence are preserved.
+
+  pub fn scrambled<C: Core>(rng: &mut Rng<C>) -> Self {
+    let () = Self::CHECK_DIMENSION;
+    Self { index: 0, seeds: Some(core::array::from_fn(|_| rng.u64())) }
+  }
+
+  /// Returns the point with the given index, without advancing the
+  /// sequence.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `index >= 2^32`.
+
+  pub fn point(&self, index: u64) -> [f64; N] {
+    let Ok(i) = u32::try_from(index) else { panic!("Sobol index out of range") };
+
+    core::array::from_fn(|d| {
+      let mut x = 0;
+      for (j, v) in DIRECTIONS[d].iter().enumerate() {
+        if i >> j & 1 != 0 { x ^= v; }
+      }
+      if let Some(seeds) = &self.seeds { x = owen_scramble(x, seeds[d]); }
+      x as f64 * f64::from_bits(0x3df0_0000_0000_0000)
+    })
+  }
+}
+
+impl<const N: usize> Default for Sobol<N> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<const N: usize> Iterator for Sobol<N> {
+  type Item = [f64; N];
+
+  /// Returns the next point, or `None` after all 2^32 points have been
+  /// produced.
+
+  fn next(&mut self) -> Option<[f64; N]> {
+    if self.index >> 32 != 0 { return None; }
+    let x = self.point(self.index);
+    self.index += 1;
+    Some(x)
+  }
+}
+
+/// A Halton sequence in `N` dimensions.
+///
+/// Coordinate `d` is the radical inverse of the index in the `d`-th prime
+/// base. Halton sequences work best in low dimensions.
+
+#[derive(Clone, Debug)]
+pub struct Halton<const N: usize> {
+  index: u64,
+  seeds: Option<[u64; N]>,
+}
+
+fn radical_inverse(mut i: u64, b: u32, seed: Option<u64>) -> f64 {
+  let b = b as u64;
+  let mut num = 0u128;
+  let mut den = 1u128;
+  let mut node = 1u64;
+
+  // A scrambled sequence also permutes the infinitely many leading zeros,
+  // so we continue until the digits no longer affect the result.
+
+  while i != 0 || seed.is_some() && den >> 53 == 0 {
+    let digit = i % b;
+    i /= b;
+    let value =
+      match seed {
+        None => digit,
+        Some(seed) => (digit + node_hash(seed, node) % b) % b,
+      };
+    node = node.wrapping_mul(b).wrapping_add(digit);
+    num = num * b as u128 + value as u128;
+    den = den * b as u128;
+  }
+
+  (num as f64 / den as f64).min(f64::from_bits(0x3fef_ffff_ffff_ffff))
+}
+
+impl<const N: usize> Halton<N> {
+  const CHECK_DIMENSION: () = assert!(N <= MAX_DIMENSION);
+
+  /// Creates a Halton sequence, starting at the origin.
+
+  pub fn new() -> Self {
+    let () = Self::CHECK_DIMENSION;
+    Self { index: 0, seeds: None }
+  }
+
+  /// Creates a Halton sequence with a nested scrambling sampled from the
+  /// given random number generator.
+  ///
+  /// Each digit is shifted by an amount that depends on the digits before
+  /// it, which in base 2 coincides with Owen's scrambling. Each point of a
+  /// scrambled sequence is uniformly distributed in the unit cube.
+
+  pub fn scrambled<C: Core>(rng: &mut Rng<C>) -> Self {
+    let () = Self::CHECK_DIMENSION;
+    Self { index: 0, seeds: Some(core::array::from_fn(|_| rng.u64())) }
+  }
+
+  /// Returns the point with the given index, without advancing the
+  /// sequence.
+
+  pub fn point(&self, index: u64) -> [f64; N] {
+    core::array::from_fn(|d| radical_inverse(index, PRIMES[d], self.seeds.map(|s| s[d])))
+  }
+}
+
+impl<const N: usize> Default for Halton<N> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<const N: usize> Iterator for Halton<N> {
+  type Item = [f64; N];
+
+  /// Returns the next point, or `None` after 2^64 - 1 points have been
+  /// produced.
+
+  fn next(&mut self) -> Option<[f64; N]> {
+    let i = self.index;
+    self.index = i.checked_add(1)?;
+    Some(self.point(i))
+  }
+}
@@ -0,0 +1,125 @@
+//! Sampling integers from Rust range expressions with [Rng::range].
+
+use core::ops::Bound;
+use core::ops::RangeBounds;
+
+use crate::Core;
+use crate::Rng;
+
+mod sealed {
+  pub trait Sealed: Copy + Ord {}
+}
+
+/// Integer types that can be sampled with [Rng::range].
+///
+/// This trait is sealed and is implemented for all of the primitive integer
+/// types.
+
+pub trait RangeInt: sealed::Sealed {
+  #[doc(hidden)]
+  const MIN: Self;
+
+  #[doc(hidden)]
+  const MAX: Self;
+
+  #[doc(hidden)]
+  fn checked_succ(self) -> Option<Self>;
+
+  #[doc(hidden)]
+  fn checked_pred(self) -> Option<Self>;
+
+  #[doc(hidden)]
+  fn between<C: Core>(rng: &mut Rng<C>, lo: Self, hi: Self) -> Self;
+}
+
+macro_rules! impl_range_int {
+  ($($t:ty => $f:ident),* $(,)?) => {
+    $(
+      impl sealed::Sealed for $t {}
+
+      impl RangeInt for $t {
+        const MIN: Self = <$t>::MIN;
+
+        const MAX: Self = <$t>::MAX;
+
+        #[inline(always)]
+        fn checked_succ(self) -> Option<Self> {
+          self.checked_add(1)
+        }
+
+        #[inline(always)]
+        fn checked_pred(self) -> Option<Self> {
+          self.checked_sub(1)
+        }
+
+        #[inline(always)]
+        fn between<C: Core>(rng: &mut Rng<C>, lo: Self, hi: Self) -> Self {
+          rng.$f(lo, hi)
+        }
+      }
+    )*
+  }
+}
+
+impl_range_int! {
+  i8 => between_i8,
+  i16 => between_i16,
+  i32 => between_i32,
+  i64 => between_i64,
+  i128 => between_i128,
+  isize => between_isize,
+  u8 => between_u8,
+  u16 => between_u16,
+  u32 => between_u32,
+  u64 => between_u64,
+  u128 => between_u128,
+  usize => between_usize,
+}
+
+impl<C: Core> Rng<C> {
+  /// Samples an integer from the uniform distribution over a range.
+  ///
+  /// Any range expression can be used, so both `lo .. hi` with an exclusive
+  /// upper bound and `lo ..= hi` with an inclusive upper bound are supported,
+  /// as are unbounded ranges like `lo ..`. Unlike the `between_*` methods, the
+  /// range never wraps around.
+  ///
+  /// ```
+  /// use dandelion::Rng;
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// let die = rng.range(1 ..= 6);
+  /// let colors = ["red", "green", "blue"];
+  /// let color = colors[rng.range(0 .. colors.len())];
+  /// let byte: u8 = rng.range(..);
+  /// ```
+  ///
+  /// # Panics
+  ///
+  /// Panics if the range is empty.
+
+  #[inline(always)]
+  pub fn range<T, R>(&mut self, range: R) -> T
+  where
+    T: RangeInt,
+    R: RangeBounds<T>
+  {
+    let lo =
+      match range.start_bound() {
+        Bound::Included(&x) => Some(x),
+        Bound::Excluded(&x) => x.checked_succ(),
+        Bound::Unbounded => Some(T::MIN),
+      };
+
+    let hi =
+      match range.end_bound() {
+        Bound::Included(&x) => Some(x),
+        Bound::Excluded(&x) => x.checked_pred(),
+        Bound::Unbounded => Some(T::MAX),
+      };
+
+    let (Some(lo), Some(hi)) = (lo, hi) else { panic!("range: empty range") };
+    assert!(lo <= hi, "range: empty range");
+    T::between(self, lo, hi)
+  }
+}
@@ -0,0 +1,35 @@
+//! Stochastic rounding.
+
+use crate::Core;
+use crate::Rng;
+use crate::floor;
+
+impl<C: Core> Rng<C> {
+  /// Rounds `x` to one of the two nearest integers at random, rounding up with
+  /// probability equal to the fractional part of `x`.
+  ///
+  /// The expected value of the result is `x`, so sums of stochastically
+  /// rounded values are unbiased. Values that are out of range saturate, and
+  /// NaN becomes 0, as with the `as` operator.
+
+  #[inline]
+  pub fn round_stochastic(&mut self, x: f64) -> i64 {
+    // The fractional part is computed exactly.
+
+    let f = floor(x);
+    f as i64 + self.bernoulli(x - f) as i64
+  }
+
+  /// Rounds `x` to one of the two nearest multiples of `step` at random, so
+  /// that the expected value of the result is `x`, up to rounding error.
+  ///
+  /// This is useful for dithering and for accumulating values in a fixed-point
+  /// representation without bias.
+
+  #[inline]
+  pub fn quantize_stochastic(&mut self, x: f64, step: f64) -> f64 {
+    let q = x / step;
+    let f = floor(q);
+    (f + self.bernoulli(q - f) as u8 as f64) * step
+  }
+}
@@ -0,0 +1,291 @@
+//! Sampling characters and strings.
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec;
+
+use crate::Core;
+use crate::Rng;
+
+const HEX: &[u8; 16] = b"0123456789abcdef";
+
+#[cfg(feature = "alloc")]
+const BASE62: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// A set of ASCII characters for [Rng::ascii] and [Rng::string].
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Charset {
+  /// The letters `A-Z` and `a-z` and the digits `0-9`.
+  Alphanumeric,
+  /// The letters `A-Z` and `a-z`.
+  Alphabetic,
+  /// The lowercase letters `a-z`.
+  Lowercase,
+  /// The uppercase letters `A-Z`.
+  Uppercase,
+  /// The digits `0-9`.
+  Digit,
+  /// The lowercase hexadecimal digits `0-9` and `a-f`.
+  LowerHex,
+  /// The uppercase hexadecimal digits `0-9` and `A-F`.
+  UpperHex,
+  /// The printable characters other than space, `!` through `~`.
+  Graphic,
+}
+
+impl Charset {
+  /// Returns the characters in the set.
+
+  pub const fn as_bytes(self) -> &'static [u8] {
+    const GRAPHIC: &[u8; 94] =
+      b"!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~";
+
+    match self {
+      Charset::Alphanumeric => b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+      Charset::Alphabetic => b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz",
+      Charset::Lowercase => b"abcdefghijklmnopqrstuvwxyz",
+      Charset::Uppercase => b"ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+      Charset::Digit => b"0123456789",
+      Charset::LowerHex => b"0123456789abcdef",
+      Charset::UpperHex => b"0123456789ABCDEF",
+      Charset::Graphic => GRAPHIC,
+    }
+  }
+}
+
+impl<C: Core> Rng<C> {
+  /// Samples a `char` from the uniform distribution on all Unicode scalar
+  /// values.
+
+  #[inline(always)]
+  pub fn char(&mut self) -> char {
+    self.between_char('\0', char::MAX)
+  }
+
+  /// Samples a `char` from the uniform distribution on the Unicode scalar
+  /// values in the range `lo ... hi`.
+  ///
+  /// The lower and upper bounds are inclusive. The surrogate code points
+  /// U+D800 to U+DFFF are not scalar values and are skipped, so every `char`
+  /// in the range is equally likely.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `lo > hi`.
+
+  #[inline(always)]
+  pub fn between_char(&mut self, lo: char, hi: char) -> char {
+    // We sample from the scalar values with the surrogate gap closed up, and
+    // then reopen the gap.
+
+    assert!(lo <= hi, "between_char: lo > hi");
+
+    let close = |c: char| { let c = c as u32; if c < 0xd800 { c } else { c - 0x800 } };
+    let x = close(lo) + self.bounded_u32(close(hi) - close(lo));
+    let x = if x < 0xd800 { x } else { x + 0x800 };
+    let Some(c) = char::from_u32(x) else { unreachable!() };
+    c
+  }
+
+  /// Samples a `char` from the uniform distribution on the characters of
+  /// `charset`.
+  ///
+  /// Characters that appear more than once in `charset` are proportionally
+  /// more likely. This takes time linear in the length of `charset`.
+  ///
+  /// ```
+  /// use dandelion::Rng;
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// let suit = rng.char_from("♠♥♦♣");
+  /// ```
+  ///
+  /// # Panics
+  ///
+  /// Panics if `charset` is empty.
+
+  #[inline]
+  pub fn char_from(&mut self, charset: &str) -> char {
+    assert!(! charset.is_empty(), "char_from: empty charset");
+
+    // ASCII strings can be indexed directly.
+
+    if charset.is_ascii() {
+      return charset.as_bytes()[self.index(charset.len())] as char;
+    }
+
+    let n = charset.chars().count();
+    let Some(c) = charset.chars().nth(self.index(n)) else { unreachable!() };
+    c
+  }
+
+  // Fills `dst` with elements sampled from the uniform distribution on
+  // `alphabet`.
+
+  fn fill_from<T: Copy>(&mut self, dst: &mut [T], alphabet: &[T]) {
+    // Each 128-bit sample is used for several elements. An element is the
+    // high part of the product of the sample with the alphabet size, and the
+    // low part is the sample for the next element. We stop while at least 64
+    // bits remain unused, which keeps the bias as low as in `bounded_u64`.
+
+    assert!(! alphabet.is_empty(), "empty alphabet");
+
+    let n = alphabet.len() as u128;
+
+    if n == 1 {
+      dst.fill(alphabet[0]);
+      return;
+    }
+
+    let mut k = 0;
+    let mut p = 1u128;
+    while p * n <= 1 << 64 {
+      p *= n;
+      k += 1;
+    }
+
+    for chunk in dst.chunks_mut(k) {
+      let mut x = self.u64() as u128 | (self.u64() as u128) << 64;
+      for c in chunk.iter_mut() {
+        let u = (x as u64 as u128) * n;
+        let v = (x >> 64) * n + (u >> 64);
+        *c = alphabet[(v >> 64) as usize];
+        x = v << 64 | u as u64 as u128;
+      }
+    }
+  }
+
+  /// Fills `buf` with bytes sampled from the uniform distribution on
+  /// `alphabet`.
+  ///
+  /// If the alphabet contains only ASCII characters, then the result is
+  /// valid UTF-8. Several bytes are produced from each 128-bit sample.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `alphabet` is empty.
+
+  pub fn fill_ascii(&mut self, buf: &mut [u8], alphabet: &[u8]) {
+    self.fill_from(buf, alphabet);
+  }
+
+  /// Samples an ASCII character from the uniform distribution on `charset`.
+  ///
+  /// ```
+  /// use dandelion::Charset;
+  /// use dandelion::Rng;
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// let c = rng.ascii(Charset::Alphanumeric);
+  /// assert!(c.is_ascii_alphanumeric());
+  /// ```
+
+  #[inline]
+  pub fn ascii(&mut self, charset: Charset) -> u8 {
+    let alphabet = charset.as_bytes();
+    alphabet[self.index(alphabet.len())]
+  }
+
+  /// Samples an ASCII letter or digit from the uniform distribution.
+  ///
+  /// This is the same as [Rng::ascii] with [Charset::Alphanumeric].
+
+  #[inline]
+  pub fn alphanumeric(&mut self) -> u8 {
+    self.ascii(Charset::Alphanumeric)
+  }
+
+  /// Samples a `String` of `len` characters from the uniform distribution on
+  /// `charset`.
+  ///
+  /// ```
+  /// use dandelion::Charset;
+  /// use dandelion::Rng;
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// let name = format!("tmp-{}", rng.string(8, Charset::Lowercase));
+  /// ```
+
+  #[cfg(feature = "alloc")]
+  pub fn string(&mut self, len: usize, charset: Charset) -> String {
+    let mut buf = vec![0; len];
+    self.fill_from(&mut buf, charset.as_bytes());
+    buf.into_iter().map(char::from).collect()
+  }
+
+  /// Samples a `String` of `len` characters from the uniform distribution on
+  /// the ASCII letters and digits.
+  ///
+  /// This is the same as `string(len, Charset::Alphanumeric)`.
+
+  #[cfg(feature = "alloc")]
+  pub fn alphanumeric_string(&mut self, len: usize) -> String {
+    self.string(len, Charset::Alphanumeric)
+  }
+
+  /// Samples a `String` of `len` characters from the uniform distribution on
+  /// the lowercase hexadecimal digits.
+
+  #[cfg(feature = "alloc")]
+  pub fn hex_string(&mut self, len: usize) -> String {
+    let mut buf = vec![0; len];
+    self.fill_hex(&mut buf);
+    buf.into_iter().map(char::from).collect()
+  }
+
+  /// Fills `buf` with bytes sampled from the uniform distribution on the
+  /// lowercase hexadecimal digits.
+  ///
+  /// The result is valid UTF-8. Sixteen digits are produced from each 64-bit
+  /// sample.
+  ///
+  /// ```
+  /// use dandelion::Rng;
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// let mut request_id = [0u8; 32];
+  /// rng.fill_hex(&mut request_id);
+  /// let request_id = core::str::from_utf8(&request_id).unwrap();
+  /// ```
+
+  pub fn fill_hex(&mut self, buf: &mut [u8]) {
+    for chunk in buf.chunks_mut(16) {
+      let mut x = self.u64();
+      for c in chunk.iter_mut() {
+        *c = HEX[(x & 15) as usize];
+        x >>= 4;
+      }
+    }
+  }
+
+  /// Samples a `String` of `len` characters from the uniform distribution on
+  /// the base-62 digits `0-9`, `A-Z`, and `a-z`.
+  ///
+  /// Each character carries about 5.95 bits of entropy, so 22 characters
+  /// carry more than 128 bits. Note that the generator is not
+  /// cryptographically secure, so the tokens are unpredictable only to
+  /// parties who cannot observe other outputs of the generator.
+
+  #[cfg(feature = "alloc")]
+  pub fn token_base62(&mut self, len: usize) -> String {
+    let mut buf = vec![0; len];
+    self.fill_from(&mut buf, BASE62);
+    buf.into_iter().map(char::from).collect()
+  }
+
+  /// Samples a `String` of `len` characters from the uniform distribution on
+  /// `alphabet`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `alphabet` is empty.
+
+  #[cfg(feature = "alloc")]
+  pub fn string_from_alphabet(&mut self, alphabet: &[char], len: usize) -> String {
+    let mut buf = vec!['\0'; len];
+    self.fill_from(&mut buf, alphabet);
+    buf.into_iter().collect()
+  }
+}
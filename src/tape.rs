@@ -0,0 +1,114 @@
+//! Recording and replaying random draws.
+//!
+//! A [RecordedRng] records every `u64` that it draws to a tape, and a
+//! [ReplayRng] draws the values from a tape in the same order. This is useful
+//! for triaging flaky tests and debugging simulations, because a failing run
+//! can be replayed exactly even after code changes that alter the generator
+//! or the way that it is seeded.
+//!
+//! ```
+//! use dandelion::Rng;
+//!
+//! let mut rng = Rng::from_u64(0).recording();
+//! let x = rng.bounded_u64(5);
+//! let tape = rng.into_tape();
+//!
+//! let mut rng = Rng::from_tape(tape);
+//! assert!(rng.bounded_u64(5) == x);
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::Core;
+use crate::Dandelion;
+use crate::Rng;
+
+/// A core that records the outputs of an inner core.
+
+#[derive(Clone)]
+pub struct Recorder<C = Dandelion> {
+  core: C,
+  tape: Vec<u64>,
+}
+
+/// A core that replays a tape of outputs.
+///
+/// Drawing more values than the tape contains panics, because the replayed
+/// run has diverged from the recorded run.
+
+#[derive(Clone, Debug)]
+pub struct Replay {
+  tape: Vec<u64>,
+  position: usize,
+}
+
+/// A random number generator that records the outputs of an inner core.
+
+pub type RecordedRng<C = Dandelion> = Rng<Recorder<C>>;
+
+/// A random number generator that replays a tape of outputs.
+
+pub type ReplayRng = Rng<Replay>;
+
+impl<C: Core> Core for Recorder<C> {
+  #[inline]
+  fn next_u64(&mut self) -> u64 {
+    let x = self.core.next_u64();
+    self.tape.push(x);
+    x
+  }
+}
+
+impl Core for Replay {
+  #[inline]
+  fn next_u64(&mut self) -> u64 {
+    let Some(&x) = self.tape.get(self.position) else {
+      panic!("ReplayRng: tape exhausted after {} values", self.position)
+    };
+    self.position += 1;
+    x
+  }
+}
+
+impl<C: Core> Rng<C> {
+  /// Wraps the random number generator so that it records its outputs,
+  /// starting with an empty tape.
+
+  pub fn recording(self) -> RecordedRng<C> {
+    Rng::from_core(Recorder { core: self.into_core(), tape: Vec::new() })
+  }
+}
+
+impl<C: Core> Rng<Recorder<C>> {
+  /// Returns the values drawn so far.
+
+  pub fn tape(&self) -> &[u64] {
+    &self.core().tape
+  }
+
+  /// Consumes the generator and returns the values that it drew.
+
+  pub fn into_tape(self) -> Vec<u64> {
+    self.into_core().tape
+  }
+}
+
+impl Rng<Replay> {
+  /// Creates a generator that replays the tape from the start.
+
+  pub fn from_tape(tape: Vec<u64>) -> Self {
+    Rng::from_core(Replay { tape, position: 0 })
+  }
+
+  /// Returns the number of values replayed so far.
+
+  pub fn position(&self) -> usize {
+    self.core().position
+  }
+
+  /// Returns the number of values left on the tape.
+
+  pub fn remaining(&self) -> usize {
+    self.core().tape.len() - self.core().position
+  }
+}
@@ -0,0 +1,103 @@
+//! Sampling durations and points in time.
+
+use crate::Core;
+use crate::Rng;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::SystemTime;
+#[cfg(feature = "chrono")]
+use chrono::DateTime;
+#[cfg(feature = "chrono")]
+use chrono::TimeZone;
+
+impl<C: Core> Rng<C> {
+  /// Samples a `Duration` from the uniform distribution over the range
+  /// `lo ... hi`.
+  ///
+  /// The lower and upper bounds are inclusive. Every duration in the range
+  /// with a whole number of nanoseconds is a possible result.
+  ///
+  /// ```
+  /// use std::time::Duration;
+  /// use dandelion::Rng;
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// let backoff = rng.duration_between(Duration::from_millis(50), Duration::from_millis(150));
+  /// ```
+  ///
+  /// # Panics
+  ///
+  /// Panics if `lo > hi`.
+
+  pub fn duration_between(&mut self, lo: Duration, hi: Duration) -> Duration {
+    assert!(lo <= hi, "duration_between: lo > hi");
+
+    let lo = lo.as_nanos();
+    let hi = hi.as_nanos();
+    let t = lo + self.bounded_u128(hi - lo);
+
+    // The total is at most `hi`, so the number of seconds fits in a `u64`.
+
+    Duration::new((t / 1_000_000_000) as u64, (t % 1_000_000_000) as u32)
+  }
+
+  /// Samples a `SystemTime` from the uniform distribution over the range
+  /// `lo ... hi`.
+  ///
+  /// The lower and upper bounds are inclusive. Every time in the range that
+  /// is a whole number of nanoseconds after `lo` is a possible result. For
+  /// timestamps stored as integers, such as seconds since the Unix epoch, use
+  /// [Rng::between_i64] or [Rng::between_i128] instead.
+  ///
+  /// ```
+  /// use std::time::Duration;
+  /// use std::time::SystemTime;
+  /// use dandelion::Rng;
+  ///
+  /// let mut rng = Rng::from_u64(0);
+  /// let hi = SystemTime::now();
+  /// let lo = hi - Duration::from_secs(7 * 24 * 60 * 60);
+  /// let created_at = rng.system_time_between(lo, hi);
+  /// ```
+  ///
+  /// # Panics
+  ///
+  /// Panics if `lo > hi`.
+
+  #[cfg(feature = "std")]
+  pub fn system_time_between(&mut self, lo: SystemTime, hi: SystemTime) -> SystemTime {
+    let Ok(d) = hi.duration_since(lo) else { panic!("system_time_between: lo > hi") };
+    lo + self.duration_between(Duration::ZERO, d)
+  }
+
+  /// Samples a `DateTime` from the uniform distribution over the range
+  /// `lo ... hi`.
+  ///
+  /// The lower and upper bounds are inclusive. Every date-time in the range
+  /// with a whole number of nanoseconds is a possible result. The result is
+  /// in the time zone of `lo`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `lo > hi`.
+
+  #[cfg(feature = "chrono")]
+  pub fn datetime_between<Tz: TimeZone>(&mut self, lo: DateTime<Tz>, hi: DateTime<Tz>) -> DateTime<Tz> {
+    assert!(lo <= hi, "datetime_between: lo > hi");
+
+    // Nanoseconds since the epoch span about 2⁹⁰ values over the range of
+    // `DateTime`, so the arithmetic is done with 128-bit integers.
+
+    let nanos = |t: &DateTime<Tz>| t.timestamp() as i128 * 1_000_000_000 + t.timestamp_subsec_nanos() as i128;
+    let a = nanos(&lo);
+    let b = nanos(&hi);
+    let t = a + self.bounded_u128((b - a) as u128) as i128;
+    let s = t.div_euclid(1_000_000_000) as i64;
+    let n = t.rem_euclid(1_000_000_000) as u32;
+
+    // The result is between `lo` and `hi`, so it is a valid `DateTime`.
+
+    let Some(t) = DateTime::from_timestamp(s, n) else { unreachable!() };
+    t.with_timezone(&lo.timezone())
+  }
+}
@@ -0,0 +1,101 @@
+//! Sampling random trees.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::Core;
+use crate::Rng;
+
+impl<C: Core> Rng<C> {
+  /// Samples a tree from the uniform distribution on labeled trees with
+  /// vertices `0 ... n - 1`, and returns its parent array.
+  ///
+  /// The tree is rooted at vertex 0, which is its own parent. Every one of the
+  /// nⁿ⁻² labeled trees is equally likely.
+
+  pub fn random_tree(&mut self, n: u32) -> Vec<u32> {
+    if n <= 1 { return vec![0; n as usize]; }
+
+    // We decode a uniformly random Prüfer sequence in linear time, which
+    // produces a tree rooted at n - 1. The labels are then reversed.
+
+    let n = n as usize;
+    let seq: Vec<usize> = (0 .. n - 2).map(|_| self.bounded_u64(n as u64 - 1) as usize).collect();
+    let mut degree = vec![1u32; n];
+    for &v in &seq { degree[v] += 1; }
+
+    let mut parent = vec![0; n];
+    let mut ptr = 0;
+    while degree[ptr] != 1 { ptr += 1; }
+    let mut leaf = ptr;
+
+    for &v in &seq {
+      parent[leaf] = v;
+      degree[v] -= 1;
+      if degree[v] == 1 && v < ptr {
+        leaf = v;
+      } else {
+        ptr += 1;
+        while degree[ptr] != 1 { ptr += 1; }
+        leaf = ptr;
+      }
+    }
+
+    parent[leaf] = n - 1;
+    parent[n - 1] = n - 1;
+
+    (0 .. n).rev().map(|v| (n - 1 - parent[v]) as u32).collect()
+  }
+
+  /// Samples a tree from the uniform distribution on ordered trees with `n`
+  /// vertices, and returns its parent array.
+  ///
+  /// An ordered tree is a rooted tree where the children of each vertex are
+  /// ordered, such as the shape of a heap or a syntax tree. The vertices are
+  /// labeled in preorder, so the root is 0, which is its own parent, and
+  /// every other vertex has a larger label than its parent. Children appear
+  /// in increasing order of their labels.
+
+  pub fn random_parent_array(&mut self, n: u32) -> Vec<u32> {
+    if n <= 1 { return vec![0; n as usize]; }
+
+    // An ordered tree is traced by a walk around its contour, which is a
+    // Dyck path with n - 1 up and n - 1 down steps. By the cycle lemma, a
+    // random arrangement of n - 1 up and n down steps has exactly one
+    // rotation that stays nonnegative until its final step, and dropping that
+    // step gives a uniformly random Dyck path.
+
+    let n = n as usize;
+    let mut steps = vec![true; n - 1];
+    steps.resize(2 * n - 1, false);
+    self.shuffle(&mut steps);
+
+    let mut height = 0i64;
+    let mut min = 0i64;
+    let mut start = 0;
+    for (i, &up) in steps.iter().enumerate() {
+      height += if up { 1 } else { -1 };
+      if height < min {
+        min = height;
+        start = i + 1;
+      }
+    }
+
+    let mut parent = Vec::with_capacity(n);
+    let mut stack = vec![0];
+    parent.push(0);
+
+    for i in 0 .. 2 * n - 2 {
+      if steps[(start + i) % (2 * n - 1)] {
+        let Some(&p) = stack.last() else { unreachable!() };
+        let v = parent.len() as u32;
+        parent.push(p);
+        stack.push(v);
+      } else {
+        let _ = stack.pop();
+      }
+    }
+
+    parent
+  }
+}
@@ -0,0 +1,292 @@
+//! Random nested values, for fuzzing parsers and serializers.
+//!
+//! A [ValueGenerator] samples a tree of JSON-like values and reports it to a
+//! [Visitor] in depth-first order, so the values can be written in any format
+//! without building the tree in memory.
+//!
+//! ```
+//! use dandelion::Rng;
+//! use dandelion::value::JsonWriter;
+//! use dandelion::value::ValueGenerator;
+//!
+//! let mut rng = Rng::from_u64(0);
+//! let generator = ValueGenerator { max_depth: 3, ..ValueGenerator::default() };
+//! let mut json = JsonWriter::new();
+//! generator.generate(&mut rng, &mut json);
+//! let text: String = json.finish();
+//! ```
+
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+
+use crate::Core;
+use crate::Rng;
+
+/// Receives the parts of a value in depth-first order.
+///
+/// The elements of an array are the values reported between
+/// [Visitor::begin_array] and [Visitor::end_array]. The entries of a map are
+/// pairs of a [Visitor::key] followed by a value, reported between
+/// [Visitor::begin_map] and [Visitor::end_map].
+
+pub trait Visitor {
+  /// Receives a null value.
+
+  fn null(&mut self);
+
+  /// Receives a boolean.
+
+  fn bool(&mut self, x: bool);
+
+  /// Receives an integer.
+
+  fn i64(&mut self, x: i64);
+
+  /// Receives a finite floating point number.
+
+  fn f64(&mut self, x: f64);
+
+  /// Receives a string.
+
+  fn string(&mut self, x: &str);
+
+  /// Begins an array.
+
+  fn begin_array(&mut self);
+
+  /// Ends the current array.
+
+  fn end_array(&mut self);
+
+  /// Begins a map.
+
+  fn begin_map(&mut self);
+
+  /// Receives the key of the next entry of the current map.
+
+  fn key(&mut self, x: &str);
+
+  /// Ends the current map.
+
+  fn end_map(&mut self);
+}
+
+/// The size limits of generated values.
+///
+/// Arrays and maps are only generated when they would be nested less than
+/// `max_depth` deep, so a depth of 0 produces only scalars. The total number
+/// of values, counting containers but not map keys, is at most `max_values`.
+
+#[derive(Clone, Debug)]
+pub struct ValueGenerator {
+  /// The maximum nesting depth of arrays and maps.
+  pub max_depth: u32,
+  /// The maximum total number of values.
+  pub max_values: u32,
+  /// The maximum number of elements or entries in an array or map.
+  pub max_len: u32,
+  /// The maximum number of characters in a string or key.
+  pub max_string_len: u32,
+}
+
+impl Default for ValueGenerator {
+  fn default() -> Self {
+    Self { max_depth: 4, max_values: 64, max_len: 8, max_string_len: 16 }
+  }
+}
+
+impl ValueGenerator {
+  /// Samples a value and reports it to the visitor.
+
+  pub fn generate<C: Core, V: Visitor>(&self, rng: &mut Rng<C>, visitor: &mut V) {
+    let mut budget = self.max_values.max(1);
+    let mut buf = String::new();
+    self.value(rng, visitor, 0, &mut budget, &mut buf);
+  }
+
+  fn value<C: Core, V: Visitor>(&self, rng: &mut Rng<C>, visitor: &mut V, depth: u32, budget: &mut u32, buf: &mut String) {
+    *budget -= 1;
+
+    let kinds = if depth < self.max_depth && *budget > 0 { 7 } else { 5 };
+
+    match rng.bounded_u32(kinds - 1) {
+      0 => visitor.null(),
+      1 => visitor.bool(rng.bool()),
+      2 => visitor.i64(self.int(rng)),
+      3 => visitor.f64(self.float(rng)),
+      4 => visitor.string(self.string(rng, buf)),
+      5 => {
+        visitor.begin_array();
+        for _ in 0 .. rng.bounded_u32(self.max_len) {
+          if *budget == 0 { break; }
+          self.value(rng, visitor, depth + 1, budget, buf);
+        }
+        visitor.end_array();
+      }
+      _ => {
+        visitor.begin_map();
+        for _ in 0 .. rng.bounded_u32(self.max_len) {
+          if *budget == 0 { break; }
+          visitor.key(self.string(rng, buf));
+          self.value(rng, visitor, depth + 1, budget, buf);
+        }
+        visitor.end_map();
+      }
+    }
+  }
+
+  fn int<C: Core>(&self, rng: &mut Rng<C>) -> i64 {
+    // Small numbers and the extremes are the most interesting to parsers.
+
+    match rng.bounded_u32(3) {
+      0 => rng.between_i64(-10, 10),
+      1 => rng.between_i64(-1_000_000, 1_000_000),
+      2 => rng.i64(),
+      _ => [i64::MIN, i64::MAX, 0, -1][rng.bounded_u32(3) as usize],
+    }
+  }
+
+  fn float<C: Core>(&self, rng: &mut Rng<C>) -> f64 {
+    match rng.bounded_u32(2) {
+      0 => rng.between_i64(-1000, 1000) as f64 / 8.0,
+      1 => (rng.f64() - 0.5) * 1e6,
+      _ => loop {
+        let x = f64::from_bits(rng.u64());
+        if x.is_finite() { break x; }
+      },
+    }
+  }
+
+  fn string<'a, C: Core>(&self, rng: &mut Rng<C>, buf: &'a mut String) -> &'a str {
+    // Mostly printable ASCII, with characters that need escaping and
+    // arbitrary Unicode scalar values mixed in.
+
+    buf.clear();
+    for _ in 0 .. rng.bounded_u32(self.max_string_len) {
+      let c =
+        match rng.bounded_u32(9) {
+          0 ..= 6 => rng.between_char(' ', '~'),
+          7 => ['"', '\\', '/', '\n', '\r', '\t', '\0', '\u{7f}'][rng.bounded_u32(7) as usize],
+          8 => rng.char(),
+          _ => rng.between_char('\u{80}', '\u{ffff}'),
+        };
+      buf.push(c);
+    }
+    buf
+  }
+}
+
+/// A [Visitor] that writes values as JSON text.
+///
+/// Strings are escaped as needed, and numbers are written so that they parse
+/// back to the same values. Maps may contain duplicate keys.
+
+#[derive(Clone, Debug)]
+pub struct JsonWriter {
+  out: String,
+  first: bool,
+}
+
+impl JsonWriter {
+  /// Creates a writer with empty output.
+
+  pub fn new() -> Self {
+    Self { out: String::new(), first: true }
+  }
+
+  /// Returns the JSON text written so far.
+
+  pub fn finish(self) -> String {
+    self.out
+  }
+
+  fn separator(&mut self) {
+    if ! self.first { self.out.push(','); }
+    self.first = false;
+  }
+
+  fn quoted(&mut self, x: &str) {
+    self.out.push('"');
+    for c in x.chars() {
+      match c {
+        '"' => self.out.push_str("\\\""),
+        '\\' => self.out.push_str("\\\\"),
+        '\n' => self.out.push_str("\\n"),
+        '\r' => self.out.push_str("\\r"),
+        '\t' => self.out.push_str("\\t"),
+        '\0' ..= '\u{1f}' => {
+          use core::fmt::Write;
+          let _ = write!(self.out, "\\u{:04x}", c as u32);
+        }
+        _ => self.out.push(c),
+      }
+    }
+    self.out.push('"');
+  }
+}
+
+impl Default for JsonWriter {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Visitor for JsonWriter {
+  fn null(&mut self) {
+    self.separator();
+    self.out.push_str("null");
+  }
+
+  fn bool(&mut self, x: bool) {
+    self.separator();
+    self.out.push_str(if x { "true" } else { "false" });
+  }
+
+  fn i64(&mut self, x: i64) {
+    self.separator();
+    self.out.push_str(&x.to_string());
+  }
+
+  fn f64(&mut self, x: f64) {
+    // Rust's `Debug` output is the shortest that round-trips, and always
+    // includes a decimal point or exponent, which JSON accepts.
+
+    self.separator();
+    self.out.push_str(&format!("{:?}", x));
+  }
+
+  fn string(&mut self, x: &str) {
+    self.separator();
+    self.quoted(x);
+  }
+
+  fn begin_array(&mut self) {
+    self.separator();
+    self.out.push('[');
+    self.first = true;
+  }
+
+  fn end_array(&mut self) {
+    self.out.push(']');
+    self.first = false;
+  }
+
+  fn begin_map(&mut self) {
+    self.separator();
+    self.out.push('{');
+    self.first = true;
+  }
+
+  fn key(&mut self, x: &str) {
+    self.separator();
+    self.quoted(x);
+    self.out.push(':');
+    self.first = true;
+  }
+
+  fn end_map(&mut self) {
+    self.out.push('}');
+    self.first = false;
+  }
+}
@@ -0,0 +1,137 @@
+//! Iterators over the positions of random walks.
+//!
+//! Each walk starts at the origin, and the iterator yields the position after
+//! each step. The walks are infinite, so use adapters such as
+//! [Iterator::take] to bound them.
+//!
+//! ```
+//! use dandelion::Rng;
+//!
+//! let mut rng = Rng::from_u64(0);
+//! let path: Vec<(i64, i64)> = rng.random_walk_lattice_2d().take(100).collect();
+//! ```
+
+use crate::Core;
+use crate::Dandelion;
+use crate::Rng;
+
+/// A walk on the real line that moves `step` to the left or right with equal
+/// probability.
+///
+/// This `struct` is created by [Rng::random_walk_1d].
+
+pub struct RandomWalk1d<'a, C = Dandelion> {
+  rng: &'a mut Rng<C>,
+  step: f64,
+  position: f64,
+}
+
+/// A walk on the integer lattice that moves to one of the four neighboring
+/// points with equal probability.
+///
+/// This `struct` is created by [Rng::random_walk_lattice_2d].
+
+pub struct RandomWalkLattice2d<'a, C = Dandelion> {
+  rng: &'a mut Rng<C>,
+  position: (i64, i64),
+}
+
+/// A walk on the real line with independent normally distributed increments,
+/// which samples Brownian motion at evenly spaced times.
+///
+/// This `struct` is created by [Rng::brownian_walk_1d].
+
+#[cfg(feature = "std")]
+pub struct BrownianWalk1d<'a, C = Dandelion> {
+  rng: &'a mut Rng<C>,
+  sigma: f64,
+  position: f64,
+  spare: Option<f64>,
+}
+
+impl<C: Core> Rng<C> {
+  /// Returns an iterator over the positions of a simple random walk on the
+  /// real line with the given step size.
+
+  pub fn random_walk_1d(&mut self, step: f64) -> RandomWalk1d<'_, C> {
+    RandomWalk1d { rng: self, step, position: 0.0 }
+  }
+
+  /// Returns an iterator over the positions of a simple random walk on the
+  /// two-dimensional integer lattice.
+
+  pub fn random_walk_lattice_2d(&mut self) -> RandomWalkLattice2d<'_, C> {
+    RandomWalkLattice2d { rng: self, position: (0, 0) }
+  }
+
+  /// Returns an iterator over the positions of a random walk on the real line
+  /// whose increments have a normal distribution with mean 0 and standard
+  /// deviation `sigma`.
+  ///
+  /// For Brownian motion sampled at intervals of length `dt`, use
+  /// `sigma = dt.sqrt()`.
+
+  #[cfg(feature = "std")]
+  pub fn brownian_walk_1d(&mut self, sigma: f64) -> BrownianWalk1d<'_, C> {
+    BrownianWalk1d { rng: self, sigma, position: 0.0, spare: None }
+  }
+}
+
+impl<C: Core> Iterator for RandomWalk1d<'_, C> {
+  type Item = f64;
+
+  #[inline]
+  fn next(&mut self) -> Option<f64> {
+    self.position += if self.rng.bool() { self.step } else { - self.step };
+    Some(self.position)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (usize::MAX, None)
+  }
+}
+
+impl<C: Core> Iterator for RandomWalkLattice2d<'_, C> {
+  type Item = (i64, i64);
+
+  #[inline]
+  fn next(&mut self) -> Option<(i64, i64)> {
+    let (x, y) = self.position;
+    self.position =
+      match self.rng.u64() >> 62 {
+        0 => (x.wrapping_add(1), y),
+        1 => (x.wrapping_sub(1), y),
+        2 => (x, y.wrapping_add(1)),
+        _ => (x, y.wrapping_sub(1)),
+      };
+    Some(self.position)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (usize::MAX, None)
+  }
+}
+
+#[cfg(feature = "std")]
+impl<C: Core> Iterator for BrownianWalk1d<'_, C> {
+  type Item = f64;
+
+  #[inline]
+  fn next(&mut self) -> Option<f64> {
+    let z =
+      match self.spare.take() {
+        Some(z) => z,
+        None => {
+          let (z, w) = self.rng.normal_pair();
+          self.spare = Some(w);
+          z
+        }
+      };
+    self.position += self.sigma * z;
+    Some(self.position)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (usize::MAX, None)
+  }
+}
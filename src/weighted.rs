@@ -0,0 +1,175 @@
+//! Sampling indices in proportion to weights.
+//!
+//! A [WeightedIndex] stores the cumulative sums of the weights and samples
+//! with a binary search. It can be built in a caller-provided buffer, so it
+//! is available without `alloc`.
+//!
+//! A [WeightedAlias] stores an alias table and samples in constant time,
+//! which is faster when there are many weights, at the cost of a larger
+//! table that takes longer to build.
+//!
+//! ```
+//! use dandelion::Rng;
+//! use dandelion::weighted::WeightedIndex;
+//!
+//! let mut rng = Rng::from_u64(0);
+//! let mut table = [60.0, 30.0, 9.0, 1.0];
+//! let loot = WeightedIndex::from_buffer(&mut table[..]).unwrap();
+//! let i = rng.sample(&loot);
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::Core;
+use crate::Rng;
+#[cfg(feature = "alloc")]
+use crate::alias::AliasTable;
+use crate::distribution::Distribution;
+
+/// A distribution over the indices `0 .. n` where each index is sampled in
+/// proportion to its weight.
+///
+/// Sampling takes `O(log n)` time.
+
+#[derive(Clone, Debug)]
+pub struct WeightedIndex<B> {
+  cumulative: B,
+}
+
+/// A distribution over the indices `0 .. n` where each index is sampled in
+/// proportion to its weight, with Vose's alias method.
+///
+/// Building the table takes `O(n)` time, and sampling takes `O(1)` time and
+/// three draws, two to choose a column and one to choose between the column
+/// and its alias.
+///
+/// ```
+/// use dandelion::Rng;
+/// use dandelion::weighted::WeightedAlias;
+///
+/// let mut rng = Rng::from_u64(0);
+/// let weights: Vec<f64> = (1 ..= 10_000).map(|k| 1.0 / k as f64).collect();
+/// let zipf = WeightedAlias::new(&weights).unwrap();
+/// let i = rng.sample(&zipf);
+/// ```
+
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct WeightedAlias {
+  table: AliasTable,
+}
+
+impl<B> WeightedIndex<B>
+where
+  B: AsRef<[f64]> + AsMut<[f64]>
+{
+  /// Creates a distribution from a buffer that holds the weights, which is
+  /// overwritten with their cumulative sums.
+  ///
+  /// Returns `None` if there are no weights, any weight is negative or not
+  /// finite, or the weights sum to zero or overflow. The buffer is
+  /// unspecified in that case.
+
+  pub fn from_buffer(mut buffer: B) -> Option<Self> {
+    let cumulative = buffer.as_mut();
+
+    if cumulative.is_empty() { return None; }
+
+    let mut total = 0.0;
+
+    for w in cumulative.iter_mut() {
+      if ! (*w >= 0.0 && w.is_finite()) { return None; }
+      total += *w;
+      *w = total;
+    }
+
+    if ! (total > 0.0 && total.is_finite()) { return None; }
+
+    Some(Self { cumulative: buffer })
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl WeightedIndex<Vec<f64>> {
+  /// Creates a distribution from a slice of weights.
+  ///
+  /// Returns `None` under the same conditions as
+  /// [WeightedIndex::from_buffer].
+
+  pub fn new(weights: &[f64]) -> Option<Self> {
+    Self::from_buffer(weights.to_vec())
+  }
+}
+
+impl<B: AsRef<[f64]>> WeightedIndex<B> {
+  /// Returns the number of indices.
+
+  pub fn len(&self) -> usize {
+    self.cumulative.as_ref().len()
+  }
+
+  /// Returns `false`, because a distribution always has at least one index.
+
+  pub fn is_empty(&self) -> bool {
+    false
+  }
+
+  /// Returns the sum of the weights.
+
+  pub fn total(&self) -> f64 {
+    let cumulative = self.cumulative.as_ref();
+    cumulative[cumulative.len() - 1]
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl WeightedAlias {
+  /// Creates a distribution from a slice of weights.
+  ///
+  /// Returns `None` if there are no weights, any weight is negative or not
+  /// finite, or the weights sum to zero or overflow.
+
+  pub fn new(weights: &[f64]) -> Option<Self> {
+    Some(Self { table: AliasTable::new(weights)? })
+  }
+
+  /// Returns the number of indices.
+
+  pub fn len(&self) -> usize {
+    self.table.len()
+  }
+
+  /// Returns `false`, because a distribution always has at least one index.
+
+  pub fn is_empty(&self) -> bool {
+    false
+  }
+}
+
+impl<C: Core, B: AsRef<[f64]>> Distribution<C> for WeightedIndex<B> {
+  type Output = usize;
+
+  #[inline]
+  fn sample(&self, rng: &mut Rng<C>) -> usize {
+    let cumulative = self.cumulative.as_ref();
+    let total = self.total();
+    let x = rng.f64() * total;
+    let i = cumulative.partition_point(|&c| c <= x);
+
+    // The scaled sample can round up to the total, which would land past the
+    // end. In that case, we take the last index with a nonzero weight.
+
+    if i < cumulative.len() { i } else { cumulative.partition_point(|&c| c < total) }
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<C: Core> Distribution<C> for WeightedAlias {
+  type Output = usize;
+
+  #[inline(always)]
+  fn sample(&self, rng: &mut Rng<C>) -> usize {
+    self.table.sample(rng)
+  }
+}
@@ -0,0 +1,101 @@
+//! The xoshiro256** generator as an alternative core.
+//!
+//! [Xoshiro256StarStar] is the generator of the same name by Blackman and
+//! Vigna. It has a state of 256 bits and a cycle length of 2²⁵⁶ - 1. Wrapped
+//! in an [Rng], it provides all of the same sampling methods as the default
+//! core, so the two can be compared on a real workload by changing one line.
+//!
+//! ```
+//! use dandelion::Rng;
+//! use dandelion::xoshiro::Xoshiro256StarStar;
+//!
+//! let mut rng = Rng::from_core(Xoshiro256StarStar::from_u64(0));
+//! let x = rng.between_u32(1, 6);
+//! ```
+
+use crate::Core;
+use crate::Rng;
+use crate::mix::mix64;
+
+/// The xoshiro256** core.
+
+#[derive(Clone, Debug)]
+pub struct Xoshiro256StarStar { state: [u64; 4] }
+
+/// A random number generator with the xoshiro256** core.
+
+pub type XoshiroRng = Rng<Xoshiro256StarStar>;
+
+impl Xoshiro256StarStar {
+  /// Creates a core with an initial state derived from the given `u64` seed
+  /// with SplitMix64, as recommended by the authors of xoshiro.
+
+  pub const fn from_u64(seed: u64) -> Self {
+    const G: u64 = 0x9e37_79b9_7f4a_7c15;
+
+    // The SplitMix64 outputs for consecutive counter values are distinct, so
+    // the state is never all zero.
+
+    let a = mix64(seed.wrapping_add(G));
+    let b = mix64(seed.wrapping_add(G.wrapping_mul(2)));
+    let c = mix64(seed.wrapping_add(G.wrapping_mul(3)));
+    let d = mix64(seed.wrapping_add(G.wrapping_mul(4)));
+    Self { state: [a, b, c, d] }
+  }
+
+  /// Creates a core with a particular initial state, or returns `None` if the
+  /// state is all zero.
+
+  pub const fn from_state(state: [u64; 4]) -> Option<Self> {
+    if state[0] | state[1] | state[2] | state[3] == 0 { return None; }
+    Some(Self { state })
+  }
+
+  /// Retrieves the current state.
+
+  #[inline(always)]
+  pub const fn state(&self) -> [u64; 4] {
+    self.state
+  }
+
+  /// Advances the state by 2¹²⁸ steps.
+  ///
+  /// This can be used to create 2¹²⁸ non-overlapping subsequences for
+  /// parallel computations.
+
+  pub fn jump(&mut self) {
+    const J: [u64; 4] = [
+      0x180e_c6d3_3cfd_0aba,
+      0xd5a6_1266_f0c9_392c,
+      0xa958_2618_e03f_c9aa,
+      0x39ab_dc45_29b1_661c,
+    ];
+
+    let mut s = [0u64; 4];
+    for j in J {
+      for b in 0 .. 64 {
+        if j & 1 << b != 0 {
+          for (t, u) in s.iter_mut().zip(self.state) { *t ^= u; }
+        }
+        let _ = self.next_u64();
+      }
+    }
+    self.state = s;
+  }
+}
+
+impl Core for Xoshiro256StarStar {
+  #[inline(always)]
+  fn next_u64(&mut self) -> u64 {
+    let s = &mut self.state;
+    let x = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+    let t = s[1] << 17;
+    s[2] ^= s[0];
+    s[3] ^= s[1];
+    s[1] ^= s[2];
+    s[0] ^= s[3];
+    s[2] ^= t;
+    s[3] = s[3].rotate_left(45);
+    x
+  }
+}
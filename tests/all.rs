@@ -1,40 +1,402 @@
+//! Runs tests.
+
 use std::array;
 use std::fmt::Write;
 use std::num::NonZeroU128;
+use std::ops::Bound;
+use std::time::Duration;
+use dandelion::Dandelion;
 use dandelion::Rng;
 use expect_test::expect;
+use proptest::prelude::*;
+
+mod stats;
 
 #[test]
 fn test_api() {
   let mut rng = Rng::new([0; 15]);
   let _ = Rng::from_u64(0);
-  let _ = Rng::from_state(NonZeroU128::MIN);
+  let _ = Rng::from_state(rng.state());
   let _ = rng.state();
+  let _ = rng.core();
+  let _ = rng.core_mut();
+  let _ = Rng::from_core(rng.clone().into_core());
   let _ = rng.split();
   let _ = rng.bernoulli(0.5);
+  let _ = rng.bernoulli_u32(1 << 31);
+  let _ = rng.bernoulli_ratio(3, 7);
+  let _ = rng.one_in(10);
+  let _ = rng.bernoulli_mask64(0.25);
+  let _ = rng.mask_u32(2);
+  let _ = rng.mask_u64(2);
+  let _ = rng.bits(5);
+  let _ = rng.u64_with_popcount(5);
   let _ = rng.bool();
+  let _ = rng.i8();
+  let _ = rng.i16();
   let _ = rng.i32();
   let _ = rng.i64();
+  let _ = rng.i128();
+  let _ = rng.isize();
+  let _ = rng.u8();
+  let _ = rng.u16();
   let _ = rng.u32();
   let _ = rng.u64();
+  let _ = rng.u128();
+  let _ = rng.usize();
+  let _ = rng.bounded_u8(5);
+  let _ = rng.bounded_u16(5);
   let _ = rng.bounded_u32(5);
   let _ = rng.bounded_u64(5);
+  let _ = rng.bounded_u128(5);
+  let _ = rng.bounded_usize(5);
+  let _ = rng.index(5);
+  rng.shuffle(&mut [1, 2, 3]);
+  let _ = rng.partial_shuffle(&mut [1, 2, 3], 2);
+  let _ = rng.below_u32(5);
+  let _ = rng.below_u64(5);
+  let _ = rng.range(1 ..= 6);
+  let _ = rng.bounded_u32_with_budget(5, 4);
+  let _ = rng.bounded_u64_with_budget(5, 4);
+  let _ = rng.bounded_u32_exact(5);
+  let _ = rng.bounded_u64_exact(5);
   let _ = rng.between_i32(1, 6);
   let _ = rng.between_i64(1, 6);
+  let _ = rng.between_i128(1, 6);
+  let _ = rng.between_isize(1, 6);
   let _ = rng.between_u32(1, 6);
   let _ = rng.between_u64(1, 6);
+  let _ = rng.between_u128(1, 6);
+  let _ = rng.between_i8(1, 6);
+  let _ = rng.between_i16(1, 6);
+  let _ = rng.between_u8(1, 6);
+  let _ = rng.between_u16(1, 6);
+  let _ = rng.between_usize(1, 6);
   let _ = rng.f32();
   let _ = rng.f64();
+  let _ = rng.between_f32(-1.0, 1.0);
+  let _ = rng.between_f64(-1.0, 1.0);
+  let _ = rng.f32_signed();
+  let _ = rng.f64_signed();
   rng.bytes(&mut [0; 16]);
+  rng.fill_f32(&mut [0.0; 16]);
+  rng.fill_f64(&mut [0.0; 16]);
   let _ = rng.byte_array::<16>();
+  let _ = rng.random::<u64>();
+  let _ = rng.option(0.5, |rng| rng.u64());
+  let _ = rng.result(0.5, |rng| rng.u64(), |rng| rng.bool());
+  rng.extend_random(&mut Vec::new(), 3, |rng| rng.u32());
+  let _: Vec<u32> = rng.collect_random(3, |rng| rng.u32());
+  let _ = rng.sample_until(|rng| rng.u32(), |_| true);
+  let _ = rng.try_sample_until(|rng| rng.u32(), |_| true, 10);
+  let _ = rng.duration_between(Duration::ZERO, Duration::from_secs(1));
+  let _ = rng.ipv4();
+  let _ = rng.ipv4_private();
+  let _ = rng.ipv4_documentation();
+  let _ = rng.ipv6();
+  let _ = rng.ipv6_unique_local();
+  let _ = rng.ipv6_documentation();
+  let _ = rng.socket_addr_v4(1024 ..= 65535);
+  let _ = rng.socket_addr_v6(1024 ..= 65535);
+  let _ = rng.socket_addr(1024 ..= 65535);
+  let _ = rng.ipv4_in_subnet(std::net::Ipv4Addr::new(10, 0, 0, 0), 8);
+  let _ = rng.ipv6_in_subnet(std::net::Ipv6Addr::LOCALHOST, 64);
+  let _ = rng.ip_in_subnet(std::net::IpAddr::from([10, 0, 0, 0]), 8);
+  let _ = rng.mac_address();
+  let _ = rng.mac_address_with_flags(false, false);
+  let _ = rng.eui64_with_flags(true, false);
+  let _ = rng.char();
+  let _ = rng.between_char('a', 'z');
+  let _ = rng.char_from("abc");
+  let _ = rng.ascii(dandelion::Charset::Alphanumeric);
+  let _ = rng.alphanumeric();
+  rng.fill_ascii(&mut [0; 16], b"abc");
+  rng.fill_hex(&mut [0; 16]);
+  let _ = rng.ulid(0);
+  let _ = rng.ksuid(1_400_000_000);
+  let mut counting = rng.clone().counting();
+  let _ = counting.u64();
+  let _ = counting.outputs_drawn();
+  counting.reset_count();
+  let _ = counting.into_inner();
+  let _ = rng.round_stochastic(1.5);
+  let _ = rng.quantize_stochastic(1.5, 0.25);
+  let _ = rng.rgb();
+  let _ = rng.rgba();
+  let _ = rng.hsl_pleasing();
+  let _ = rng.unit_circle();
+  let _ = rng.in_unit_disk();
+  let _ = rng.unit_circle_f32();
+  let _ = rng.in_unit_disk_f32();
+  let _ = rng.in_unit_ball();
+  let _ = rng.rotation_2d();
+  let _ = rng.in_triangle([0.0, 0.0], [1.0, 0.0], [0.0, 1.0]);
+  let _ = rng.in_polygon(&[[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]]);
+  let _ = rng.in_triangles(&[[[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]]]);
+  rng.simplex_weights(&mut [0.0; 4]);
+  rng.stratified(&mut [0.0; 4]);
+  rng.stratified_shuffled(&mut [0.0; 4]);
+  rng.stratified_2d(2, 3, &mut [(0.0, 0.0); 6]);
+  let _ = rng.random_walk_1d(1.0).next();
+  let _ = rng.random_walk_lattice_2d().next();
+  let d = Duration::from_millis(1);
+  let _ = dandelion::jitter::full_jitter(&mut rng, d, d, 0);
+  let _ = dandelion::jitter::equal_jitter(&mut rng, d, d, 0);
+  let _ = dandelion::jitter::decorrelated_jitter(&mut rng, d, d, d);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_api_alloc() {
+  let mut rng = Rng::new([0; 15]);
+  let _ = rng.random_tree(10);
+  let _ = rng.random_parent_array(10);
+  let _ = rng.random_vec::<u64>(10);
+  let mut recorded = rng.clone().recording();
+  let _ = recorded.u64();
+  let _ = recorded.tape();
+  let mut replay = Rng::from_tape(recorded.into_tape());
+  let _ = replay.remaining();
+  let _ = replay.u64();
+  let _ = replay.position();
+  let _ = rng.alphanumeric_string(16);
+  let _ = rng.choose_multiple(&[1, 2, 3], 2);
+  let _ = rng.string(16, dandelion::Charset::Lowercase);
+  let _ = rng.hex_string(16);
+  let _ = rng.token_base62(22);
+  let _ = rng.string_from_alphabet(&['a', 'b', 'c'], 16);
+  let mut chain = dandelion::markov::MarkovChain::new(&[[1.0]]).unwrap();
+  let _ = chain.num_states();
+  let _ = chain.state();
+  chain.set_state(0);
+  let _ = chain.step(&mut rng);
+  let _ = chain.iter(&mut rng).next();
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_api_std() {
+  let mut rng = Rng::new([0; 15]);
+  let _ = rng.unit_sphere();
+  rng.unit_vector(&mut [0.0; 8]);
+  let _ = rng.unit_quaternion();
+  let _ = rng.rotation_3d();
+  let _ = rng.in_annulus(1.0, 2.0);
+  let _ = rng.on_spherical_cap([0.0, 0.0, 1.0], 0.5);
+  let _ = rng.cone_direction([0.0, 0.0, 1.0], 0.5);
+  let _ = rng.log_uniform_u64(1, 1000);
+  let _ = rng.log_uniform_f64(1e-5, 1e-1);
+  let _ = rng.brownian_walk_1d(1.0).next();
 }
 
 #[cfg(feature = "getrandom")]
 #[test]
+#[allow(deprecated)]
 fn test_api_getrandom() {
+  let _ = Rng::from_operating_system();
+  let _ = Rng::try_from_operating_system().unwrap();
+  let _ = Rng::from_entropy_source(&mut dandelion::entropy::OsEntropy).unwrap();
   let _ = Rng::from_entropy();
 }
 
+#[cfg(feature = "noise")]
+#[test]
+fn test_api_noise() {
+  let mut rng = Rng::new([0; 15]);
+  let perlin = dandelion::noise::Perlin::new(&mut rng);
+  let _ = perlin.noise2(0.5, 0.5);
+  let _ = perlin.noise3(0.5, 0.5, 0.5);
+}
+
+#[test]
+fn test_api_quasi() {
+  use dandelion::quasi::Halton;
+  use dandelion::quasi::Sobol;
+
+  let mut rng = Rng::new([0; 15]);
+  let _: Option<[f64; 3]> = Sobol::<3>::new().next();
+  let _: Option<[f64; 3]> = Sobol::<3>::scrambled(&mut rng).next();
+  let _: [f64; 3] = Sobol::<3>::new().point(0);
+  let _: Option<[f64; 3]> = Halton::<3>::new().next();
+  let _: Option<[f64; 3]> = Halton::<3>::scrambled(&mut rng).next();
+  let _: [f64; 3] = Halton::<3>::new().point(0);
+}
+
+#[test]
+fn test_api_dice() {
+  use dandelion::dice::DiceExpr;
+
+  let mut rng = Rng::new([0; 15]);
+  let _: i64 = rng.roll("3d6+2");
+  let e: DiceExpr = DiceExpr::parse("3d6+2").unwrap();
+  let _: i64 = e.roll(&mut rng);
+  let _: i64 = e.min();
+  let _: i64 = e.max();
+}
+
+#[test]
+fn test_api_distribution() {
+  use dandelion::distribution::{Distribution, from_fn, random};
+
+  let mut rng = Rng::new([0; 15]);
+  let d = from_fn(|rng| rng.f64()).map(|x| x * 2.0).zip(random::<u32>()).filter_reject(|_| true, 10);
+  let _ = rng.sample(&d);
+  let _ = d.sample(&mut rng);
+}
+
+#[cfg(feature = "graph")]
+#[test]
+fn test_api_graph() {
+  use dandelion::graph;
+
+  let mut rng = Rng::new([0; 15]);
+  let _ = graph::erdos_renyi(&mut rng, 10, 0.5);
+  graph::erdos_renyi_with(&mut rng, 10, 0.5, |_, _| ());
+  let _ = graph::barabasi_albert(&mut rng, 10, 2);
+  graph::barabasi_albert_with(&mut rng, 10, 2, |_, _| ());
+}
+
+#[cfg(feature = "value")]
+#[test]
+fn test_api_value() {
+  use dandelion::value::JsonWriter;
+  use dandelion::value::ValueGenerator;
+
+  let mut rng = Rng::new([0; 15]);
+  let mut json = JsonWriter::new();
+  ValueGenerator::default().generate(&mut rng, &mut json);
+  let _ = json.finish();
+}
+
+#[cfg(feature = "pattern")]
+#[test]
+fn test_api_pattern() {
+  use dandelion::pattern::Pattern;
+
+  let mut rng = Rng::new([0; 15]);
+  let _ = rng.from_pattern("[a-z]{3}");
+  let _ = Pattern::parse("[a-z]{3}").unwrap().sample(&mut rng);
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_api_derive() {
+  #[derive(dandelion::Random)]
+  struct Point(f64, f64);
+
+  let mut rng = Rng::new([0; 15]);
+  let Point(x, y) = rng.random::<Point>();
+  let _ = x + y;
+}
+
+#[cfg(all(feature = "atomic", not(loom)))]
+#[test]
+fn test_api_atomic() {
+  // The global generator is shared by every test, so this is the only test
+  // that uses it.
+
+  let _ = dandelion::atomic::split();
+  let _ = dandelion::atomic::bernoulli(0.5);
+  let _ = dandelion::atomic::bernoulli_ratio(3, 7);
+  let _ = dandelion::atomic::one_in(10);
+  let _ = dandelion::atomic::bernoulli_mask64(0.25);
+  let _ = dandelion::atomic::mask_u32(2);
+  let _ = dandelion::atomic::mask_u64(2);
+  let _ = dandelion::atomic::bits(5);
+  let _ = dandelion::atomic::bool();
+  let _ = dandelion::atomic::i8();
+  let _ = dandelion::atomic::i16();
+  let _ = dandelion::atomic::i32();
+  let _ = dandelion::atomic::i64();
+  let _ = dandelion::atomic::i128();
+  let _ = dandelion::atomic::isize();
+  let _ = dandelion::atomic::u8();
+  let _ = dandelion::atomic::u16();
+  let _ = dandelion::atomic::u32();
+  let _ = dandelion::atomic::u64();
+  let _ = dandelion::atomic::u128();
+  let _ = dandelion::atomic::usize();
+  let _ = dandelion::atomic::bounded_u8(5);
+  let _ = dandelion::atomic::bounded_u16(5);
+  let _ = dandelion::atomic::bounded_u32(5);
+  let _ = dandelion::atomic::bounded_u64(5);
+  let _ = dandelion::atomic::bounded_u128(5);
+  let _ = dandelion::atomic::bounded_usize(5);
+  let _ = dandelion::atomic::index(5);
+  dandelion::atomic::shuffle(&mut [1, 2, 3]);
+  let _ = dandelion::atomic::below_u32(5);
+  let _ = dandelion::atomic::below_u64(5);
+  let _ = dandelion::atomic::range(1 ..= 6);
+  let _ = dandelion::atomic::between_i8(1, 6);
+  let _ = dandelion::atomic::between_i16(1, 6);
+  let _ = dandelion::atomic::between_i32(1, 6);
+  let _ = dandelion::atomic::between_i64(1, 6);
+  let _ = dandelion::atomic::between_i128(1, 6);
+  let _ = dandelion::atomic::between_isize(1, 6);
+  let _ = dandelion::atomic::between_u8(1, 6);
+  let _ = dandelion::atomic::between_u16(1, 6);
+  let _ = dandelion::atomic::between_u32(1, 6);
+  let _ = dandelion::atomic::between_u64(1, 6);
+  let _ = dandelion::atomic::between_u128(1, 6);
+  let _ = dandelion::atomic::between_usize(1, 6);
+  let _ = dandelion::atomic::f32();
+  let _ = dandelion::atomic::f64();
+  let _ = dandelion::atomic::between_f32(-1.0, 1.0);
+  let _ = dandelion::atomic::between_f64(-1.0, 1.0);
+  let _ = dandelion::atomic::f32_signed();
+  let _ = dandelion::atomic::f64_signed();
+  dandelion::atomic::bytes(&mut [0; 16]);
+  dandelion::atomic::fill_f32(&mut [0.0; 16]);
+  dandelion::atomic::fill_f64(&mut [0.0; 16]);
+  let _ = dandelion::atomic::duration_between(Duration::ZERO, Duration::from_secs(1));
+  let _ = dandelion::atomic::byte_array::<16>();
+
+  dandelion::atomic::seed_from(&mut |buf: &mut [u8]| -> Result<(), ()> { buf.fill(1); Ok(()) }).unwrap();
+
+  // Each draw is handed out once, so together the concurrent draws consume a
+  // prefix of the seeded SplitMix64 sequence.
+
+  dandelion::atomic::seed(42);
+
+  let n = if cfg!(miri) { 10 } else { 1000 };
+
+  let handles: Vec<_> =
+    (0 .. 4)
+      .map(|_| std::thread::spawn(move || (0 .. n).map(|_| dandelion::atomic::u64()).collect::<Vec<_>>()))
+      .collect();
+
+  let mut xs: Vec<u64> = handles.into_iter().flat_map(|h| h.join().unwrap()).collect();
+  let mut ys: Vec<u64> = (1 ..= 4 * n).map(|i| splitmix64(42, i)).collect();
+  xs.sort();
+  ys.sort();
+  assert!(xs == ys);
+
+  // The unused bits of each draw are banked, and reseeding empties the bank.
+
+  dandelion::atomic::seed(7);
+  let _ = dandelion::atomic::bits(5);
+  dandelion::atomic::seed(7);
+  let x = (0 .. 64).fold(0, |x, i| x | dandelion::atomic::bits(1) << i);
+  assert!(x == splitmix64(7, 1));
+  assert!(dandelion::atomic::u64() == splitmix64(7, 2));
+}
+
+// The `i`th output of SplitMix64 from the given state, counting from one.
+
+#[cfg(feature = "atomic")]
+fn splitmix64(state: u64, i: u64) -> u64 {
+  dandelion::mix::mix64(state.wrapping_add(i.wrapping_mul(0x9e37_79b9_7f4a_7c15)))
+}
+
+#[cfg(feature = "cortex-m")]
+#[test]
+fn test_api_cortex_m() {
+  // Reading the counters requires Cortex-M hardware, so we only check that
+  // the constructor exists.
+
+  let _: fn(u64) -> Rng = Rng::from_cortex_m_counters;
+}
+
 #[cfg(feature = "rand_core")]
 #[test]
 fn test_api_rand_core() {
@@ -52,28 +414,89 @@ fn test_api_rand_core() {
 fn test_api_thread_local() {
   let _ = dandelion::thread_local::split();
   let _ = dandelion::thread_local::bernoulli(0.5);
+  let _ = dandelion::thread_local::bernoulli_ratio(3, 7);
+  let _ = dandelion::thread_local::one_in(10);
+  let _ = dandelion::thread_local::bernoulli_mask64(0.25);
+  let _ = dandelion::thread_local::mask_u32(2);
+  let _ = dandelion::thread_local::mask_u64(2);
+  let _ = dandelion::thread_local::bits(5);
   let _ = dandelion::thread_local::bool();
+  let _ = dandelion::thread_local::i8();
+  let _ = dandelion::thread_local::i16();
   let _ = dandelion::thread_local::i32();
   let _ = dandelion::thread_local::i64();
+  let _ = dandelion::thread_local::i128();
+  let _ = dandelion::thread_local::isize();
+  let _ = dandelion::thread_local::u8();
+  let _ = dandelion::thread_local::u16();
   let _ = dandelion::thread_local::u32();
   let _ = dandelion::thread_local::u64();
+  let _ = dandelion::thread_local::u128();
+  let _ = dandelion::thread_local::usize();
+  let _ = dandelion::thread_local::bounded_u8(5);
+  let _ = dandelion::thread_local::bounded_u16(5);
   let _ = dandelion::thread_local::bounded_u32(5);
   let _ = dandelion::thread_local::bounded_u64(5);
+  let _ = dandelion::thread_local::bounded_u128(5);
+  let _ = dandelion::thread_local::bounded_usize(5);
+  let _ = dandelion::thread_local::index(5);
+  dandelion::thread_local::shuffle(&mut [1, 2, 3]);
+  let _ = dandelion::thread_local::below_u32(5);
+  let _ = dandelion::thread_local::below_u64(5);
+  let _ = dandelion::thread_local::range(1 ..= 6);
+  let _ = dandelion::thread_local::between_i8(1, 6);
+  let _ = dandelion::thread_local::between_i16(1, 6);
   let _ = dandelion::thread_local::between_i32(1, 6);
   let _ = dandelion::thread_local::between_i64(1, 6);
+  let _ = dandelion::thread_local::between_i128(1, 6);
+  let _ = dandelion::thread_local::between_isize(1, 6);
+  let _ = dandelion::thread_local::between_u8(1, 6);
+  let _ = dandelion::thread_local::between_u16(1, 6);
   let _ = dandelion::thread_local::between_u32(1, 6);
   let _ = dandelion::thread_local::between_u64(1, 6);
+  let _ = dandelion::thread_local::between_u128(1, 6);
+  let _ = dandelion::thread_local::between_usize(1, 6);
   let _ = dandelion::thread_local::f32();
   let _ = dandelion::thread_local::f64();
+  let _ = dandelion::thread_local::between_f32(-1.0, 1.0);
+  let _ = dandelion::thread_local::between_f64(-1.0, 1.0);
+  let _ = dandelion::thread_local::f32_signed();
+  let _ = dandelion::thread_local::f64_signed();
   dandelion::thread_local::bytes(&mut [0; 16]);
+  dandelion::thread_local::fill_f32(&mut [0.0; 16]);
+  dandelion::thread_local::fill_f64(&mut [0.0; 16]);
+  let _ = dandelion::thread_local::duration_between(Duration::ZERO, Duration::from_secs(1));
+  let _ = dandelion::thread_local::system_time_between(std::time::UNIX_EPOCH, std::time::SystemTime::now());
   let _ = dandelion::thread_local::byte_array::<16>();
 }
 
+#[cfg(feature = "thread_local")]
+#[test]
+fn test_thread_local_threads() {
+  // Each thread lazily seeds its own generator. This test is small enough to
+  // run under Miri, which checks the `Cell` handling in `thread_local`.
+
+  let n = if cfg!(miri) { 10 } else { 1000 };
+
+  let handles: Vec<_> =
+    (0 .. 4)
+      .map(|_| std::thread::spawn(move || (0 .. n).map(|_| dandelion::thread_local::u64()).collect::<Vec<_>>()))
+      .collect();
+
+  let streams: Vec<Vec<u64>> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+  for i in 0 .. streams.len() {
+    for j in 0 .. i {
+      assert!(streams[i] != streams[j]);
+    }
+  }
+}
+
 #[test]
 fn test_vectors() -> std::fmt::Result {
   let mut out = String::new();
 
-  let mut rng = Rng::from_state(NonZeroU128::MIN);
+  let mut rng = Rng::from_core(Dandelion::from_state(NonZeroU128::MIN));
   for _ in 0 .. 10 { write!(&mut out, "{:#018x}\n", rng.u64())?; }
   write!(&mut out, "\n")?;
   let mut rng = rng.split();
@@ -151,3 +574,3947 @@ fn test_vectors() -> std::fmt::Result {
 
   Ok(())
 }
+
+#[test]
+fn test_vectors_seeding() -> std::fmt::Result {
+  // The expected outputs do not depend on the target's byte order, so running
+  // this test on a big-endian target checks the `swap_bytes` in the hash and
+  // the little-endian reads of the seed.
+
+  let mut out = String::new();
+
+  for seed in [[0; 15], [0xff; 15], array::from_fn(|i| i as u8 + 1)] {
+    let mut rng = Rng::new(seed);
+    write!(&mut out, "{:#034x} {:#018x}\n", rng.state(), rng.u64())?;
+  }
+  write!(&mut out, "\n")?;
+  for seed in [0, 1, u64::MAX] {
+    let mut rng = Rng::from_u64(seed);
+    write!(&mut out, "{:#034x} {:#018x}\n", rng.state(), rng.u64())?;
+  }
+
+  expect![[r#"
+      0x72e02be1fbdcb078a2fda8dedc0198b1 0xd0c010617dff39c5
+      0x0b688d081d032e0236276afed0ed25a8 0x0175421315e9768d
+      0xc9f25959b080dea1f225d15a3f959636 0x2cc59d83b9ffde5b
+
+      0x52473085ab6537b5e7bfbe7312352d14 0xc85f5c9195dd6498
+      0xf04e3af720c99669d0af852d5cdd7e43 0xb6927e27968aa177
+      0xac08050f6a1bd8446e1db97f1d0a5a1a 0x50fbe18d31b012bd
+  "#]].assert_eq(out.drain(..).as_str());
+
+  Ok(())
+}
+
+#[test]
+fn test_vectors_bytes() -> std::fmt::Result {
+  // As with the seeding vectors, these check the little-endian byte order of
+  // `bytes` and `byte_array` on big-endian targets.
+
+  let mut out = String::new();
+
+  let mut rng = Rng::new([0; 15]);
+  for n in [0, 1, 7, 8, 9, 15, 16, 17, 24, 33] {
+    let mut buf = [0; 33];
+    rng.bytes(&mut buf[.. n]);
+    for x in &buf[.. n] { write!(&mut out, "{:02x}", x)?; }
+    write!(&mut out, "\n")?;
+  }
+  write!(&mut out, "\n")?;
+  for x in rng.byte_array::<5>() { write!(&mut out, "{:02x}", x)?; }
+  write!(&mut out, "\n")?;
+  for x in rng.byte_array::<20>() { write!(&mut out, "{:02x}", x)?; }
+  write!(&mut out, "\n")?;
+
+  expect![[r#"
+
+      c5
+      cd6c676fbdd579
+      507d6799e03067f2
+      80718788c7eb0dfc6a
+      931878d68c9a54a18312c3490a39cd
+      a7f49fb7a60cd87c1afca74950895e54
+      365651a48fbf55aeb852803168cae165ef
+      ce48ebcb76ac628bccaf123d5c3e075687e8ea997e34e199
+      20249b1c1633b1ca5eb6e6526e32219d023d23eb72b6f04c8be1a5b5ecc02aba7d
+
+      ba2498b207
+      c5f79e907b0bf873bd8f27b94c3dba34127234e8
+  "#]].assert_eq(out.drain(..).as_str());
+
+  Ok(())
+}
+
+#[test]
+fn test_vectors_bernoulli() -> std::fmt::Result {
+  let mut out = String::new();
+
+  let mut rng = Rng::new([0; 15]);
+  for p in [-1.0, 0.0, 0.25, 0.5, 0.75, 1.0, 2.0, f64::NAN] {
+    for _ in 0 .. 40 { write!(&mut out, "{}", rng.bernoulli(p) as u8)?; }
+    write!(&mut out, "\n")?;
+  }
+
+  expect![[r#"
+      0000000000000000000000000000000000000000
+      0000000000000000000000000000000000000000
+      0101100000100010001010100000101010100001
+      0010111100111010000010000000101111011001
+      1111011111011111110010101010111111011111
+      1111111111111111111111111111111111111111
+      1111111111111111111111111111111111111111
+      0000000000000000000000000000000000000000
+  "#]].assert_eq(out.drain(..).as_str());
+
+  Ok(())
+}
+
+#[test]
+fn test_vectors_boundary_bounds() -> std::fmt::Result {
+  let mut out = String::new();
+
+  let mut rng = Rng::new([0; 15]);
+  write!(&mut out, "{:?}\n", array::from_fn::<_, 10, _>(|_| rng.bounded_u32(0)))?;
+  write!(&mut out, "{:?}\n", array::from_fn::<_, 10, _>(|_| rng.bounded_u64(0)))?;
+  write!(&mut out, "{:?}\n", array::from_fn::<_, 10, _>(|_| rng.between_i32(-3, -3)))?;
+  write!(&mut out, "{:?}\n", array::from_fn::<_, 10, _>(|_| rng.between_i64(i64::MIN, i64::MIN)))?;
+  write!(&mut out, "{:?}\n", array::from_fn::<_, 10, _>(|_| rng.between_u32(u32::MAX, u32::MAX)))?;
+  write!(&mut out, "{:?}\n", array::from_fn::<_, 10, _>(|_| rng.between_u64(7, 7)))?;
+  write!(&mut out, "{:?}\n", array::from_fn::<_, 10, _>(|_| rng.between_u64(u64::MAX - 2, 2)))?;
+  write!(&mut out, "{:?}\n", array::from_fn::<_, 10, _>(|_| rng.between_i32(i32::MAX, i32::MIN)))?;
+  write!(&mut out, "{:#x?}\n", array::from_fn::<_, 4, _>(|_| rng.bounded_u64(u64::MAX)))?;
+
+  expect![[r#"
+      [0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
+      [0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
+      [-3, -3, -3, -3, -3, -3, -3, -3, -3, -3]
+      [-9223372036854775808, -9223372036854775808, -9223372036854775808, -9223372036854775808, -9223372036854775808, -9223372036854775808, -9223372036854775808, -9223372036854775808, -9223372036854775808, -9223372036854775808]
+      [4294967295, 4294967295, 4294967295, 4294967295, 4294967295, 4294967295, 4294967295, 4294967295, 4294967295, 4294967295]
+      [7, 7, 7, 7, 7, 7, 7, 7, 7, 7]
+      [1, 18446744073709551615, 18446744073709551613, 1, 2, 18446744073709551615, 0, 1, 1, 1]
+      [2147483647, -2147483648, 2147483647, -2147483648, 2147483647, -2147483648, -2147483648, -2147483648, 2147483647, -2147483648]
+      [
+          0x2b229a254230e729,
+          0x1965273d2cca83f6,
+          0xa369cac542b6224,
+          0xf542b6627d4e9c36,
+      ]
+  "#]].assert_eq(out.drain(..).as_str());
+
+  Ok(())
+}
+
+#[test]
+fn test_ks_uniform() {
+  const N: usize = 100_000;
+
+  let mut rng = Rng::from_u64(0);
+  let f32s: Vec<f64> = (0 .. N).map(|_| rng.f32() as f64).collect();
+  let f64s: Vec<f64> = (0 .. N).map(|_| rng.f64()).collect();
+  let squares: Vec<f64> = f64s.iter().map(|x| x * x).collect();
+
+  assert!(stats::ks_uniform(&f32s) < stats::ks_critical_value(N));
+  assert!(stats::ks_uniform(&f64s) < stats::ks_critical_value(N));
+  assert!(stats::ks_uniform(&squares) > stats::ks_critical_value(N));
+  assert!(stats::ks(&squares, |x| x.clamp(0.0, 1.0).sqrt()) < stats::ks_critical_value(N));
+}
+
+fn check_independent(xs: &[u64], ys: &[u64]) {
+  // Checks the pair of streams with bit agreement and correlation tests, and
+  // checks their interleaving with the same tests at lag one.
+
+  let n = xs.len();
+  let fs = |xs: &[u64]| xs.iter().map(|&x| (x >> 11) as f64 / (1u64 << 53) as f64).collect::<Vec<_>>();
+  let zs: Vec<u64> = xs.iter().zip(ys).flat_map(|(&x, &y)| [x, y]).collect();
+
+  assert!(stats::bit_agreement_z(xs, ys).abs() < 5.0);
+  assert!(stats::bit_agreement_z(&zs[.. 2 * n - 1], &zs[1 ..]).abs() < 5.0);
+  assert!(stats::correlation(&fs(xs), &fs(ys)).abs() * (n as f64).sqrt() < 5.0);
+  assert!(stats::correlation(&fs(&zs[.. 2 * n - 1]), &fs(&zs[1 ..])).abs() * (2.0 * n as f64).sqrt() < 5.0);
+}
+
+#[test]
+fn test_split_streams_independent() {
+  const N: usize = 100_000;
+
+  let mut parent = Rng::from_u64(0);
+  let mut child = parent.split();
+  let mut grandchild = child.split();
+  let mut sibling = parent.split();
+
+  let p: Vec<u64> = (0 .. N).map(|_| parent.u64()).collect();
+  let c: Vec<u64> = (0 .. N).map(|_| child.u64()).collect();
+  let g: Vec<u64> = (0 .. N).map(|_| grandchild.u64()).collect();
+  let s: Vec<u64> = (0 .. N).map(|_| sibling.u64()).collect();
+
+  check_independent(&p, &c);
+  check_independent(&p, &s);
+  check_independent(&c, &s);
+  check_independent(&c, &g);
+}
+
+#[test]
+fn test_consecutive_seeds_independent() {
+  const N: usize = 100_000;
+
+  for seed in [0, 1, 0xffff_ffff, u64::MAX - 1] {
+    let mut a = Rng::from_u64(seed);
+    let mut b = Rng::from_u64(seed.wrapping_add(1));
+
+    let xs: Vec<u64> = (0 .. N).map(|_| a.u64()).collect();
+    let ys: Vec<u64> = (0 .. N).map(|_| b.u64()).collect();
+
+    check_independent(&xs, &ys);
+  }
+}
+
+fn rng_with_next_u64(z: u64) -> Rng {
+  // With `x = 1` in the low half of the state, the output function reduces to
+  // `y + 1` where `y` is the high half of the state.
+
+  let s = 1 | (z.wrapping_sub(1) as u128) << 64;
+  Rng::from_core(Dandelion::from_state(NonZeroU128::new(s).unwrap()))
+}
+
+fn round_to_significant_bits(m: u64, bits: u32) -> u64 {
+  let width = 64 - m.leading_zeros();
+  if width <= bits { return m; }
+  let shift = width - bits;
+  let q = m >> shift;
+  let r = m & ((1 << shift) - 1);
+  let half = 1 << shift - 1;
+  let q = if r > half || r == half && q & 1 == 1 { q + 1 } else { q };
+  q << shift
+}
+
+fn reference_f32(x: i64) -> f32 {
+  round_to_significant_bits(x.unsigned_abs(), 24) as f32 * f32::from_bits(64 << 23)
+}
+
+fn reference_f64(x: i64) -> f64 {
+  round_to_significant_bits(x.unsigned_abs(), 53) as f64 * f64::from_bits(960 << 52)
+}
+
+#[test]
+fn test_float_rounding() {
+  let mut inputs = Vec::new();
+
+  for k in 0 .. 64 {
+    for d in -3 ..= 3 {
+      inputs.push((1_i64 << k).wrapping_add(d));
+    }
+  }
+
+  for bits in [24, 53] {
+    for width in bits + 1 ..= 63 {
+      let shift = width - bits;
+      let half = 1_u64 << shift - 1;
+      for q in [1 << bits - 1, (1 << bits - 1) + 1, (1 << bits) - 1] {
+        for r in [0, 1, half - 1, half, half + 1, 2 * half - 1] {
+          inputs.push((q << shift | r) as i64);
+        }
+      }
+    }
+  }
+
+  let mut rng = Rng::from_u64(0);
+  for _ in 0 .. 1_000_000 {
+    let x = rng.i64();
+    inputs.push(x);
+    inputs.push(x >> rng.bounded_u32(63));
+    inputs.push(x & !0 << rng.bounded_u32(63));
+  }
+
+  for x in inputs {
+    for x in [x, x.wrapping_neg()] {
+      assert_eq!(rng_with_next_u64(x as u64).f32().to_bits(), reference_f32(x).to_bits(), "{:#x}", x);
+      assert_eq!(rng_with_next_u64(x as u64).f64().to_bits(), reference_f64(x).to_bits(), "{:#x}", x);
+    }
+  }
+}
+
+#[test]
+fn test_circle_and_disk() {
+  const N: usize = 100_000;
+
+  let mut rng = Rng::from_u64(0);
+  let circle: Vec<(f64, f64)> = (0 .. N).map(|_| rng.unit_circle()).collect();
+  let disk: Vec<(f64, f64)> = (0 .. N).map(|_| rng.in_unit_disk()).collect();
+
+  assert!(circle.iter().all(|(x, y)| (x * x + y * y - 1.0).abs() < 1e-15));
+  assert!(disk.iter().all(|(x, y)| x * x + y * y <= 1.0));
+
+  let angle = |&(x, y): &(f64, f64)| f64::atan2(y, x) / std::f64::consts::TAU + 0.5;
+  let angles: Vec<f64> = circle.iter().map(angle).collect();
+  let radii: Vec<f64> = disk.iter().map(|(x, y)| x * x + y * y).collect();
+
+  assert!(stats::ks_uniform(&angles) < stats::ks_critical_value(N));
+  assert!(stats::ks_uniform(&radii) < stats::ks_critical_value(N));
+}
+
+#[test]
+fn test_circle_and_disk_f32() {
+  const N: usize = 100_000;
+
+  let mut rng = Rng::from_u64(0);
+  let circle: Vec<(f32, f32)> = (0 .. N).map(|_| rng.unit_circle_f32()).collect();
+  let disk: Vec<(f32, f32)> = (0 .. N).map(|_| rng.in_unit_disk_f32()).collect();
+
+  assert!(circle.iter().all(|(x, y)| (x * x + y * y - 1.0).abs() < 1e-6));
+  assert!(disk.iter().all(|(x, y)| x * x + y * y <= 1.0));
+
+  let angle = |&(x, y): &(f32, f32)| f64::atan2(y as f64, x as f64) / std::f64::consts::TAU + 0.5;
+  let angles: Vec<f64> = circle.iter().map(angle).collect();
+  let radii: Vec<f64> = disk.iter().map(|(x, y)| (x * x + y * y) as f64).collect();
+
+  assert!(stats::ks_uniform(&angles) < stats::ks_critical_value(N));
+  assert!(stats::ks_uniform(&radii) < stats::ks_critical_value(N));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_sphere() {
+  const N: usize = 100_000;
+
+  let mut rng = Rng::from_u64(0);
+  let sphere: Vec<(f64, f64, f64)> = (0 .. N).map(|_| rng.unit_sphere()).collect();
+
+  assert!(sphere.iter().all(|(x, y, z)| (x * x + y * y + z * z - 1.0).abs() < 1e-15));
+
+  // By Archimedes' hat-box theorem, each coordinate is uniform on [-1, 1].
+
+  let xs: Vec<f64> = sphere.iter().map(|p| (p.0 + 1.0) / 2.0).collect();
+  let zs: Vec<f64> = sphere.iter().map(|p| (p.2 + 1.0) / 2.0).collect();
+
+  assert!(stats::ks_uniform(&xs) < stats::ks_critical_value(N));
+  assert!(stats::ks_uniform(&zs) < stats::ks_critical_value(N));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_unit_vector() {
+  const N: usize = 100_000;
+
+  let mut rng = Rng::from_u64(0);
+
+  for n in [1, 2, 7, 64] {
+    let mut v = vec![0.0; n];
+    for _ in 0 .. 1000 {
+      rng.unit_vector(&mut v);
+      assert!((v.iter().map(|x| x * x).sum::<f64>() - 1.0).abs() < 1e-14);
+    }
+  }
+
+  rng.unit_vector(&mut []);
+
+  // In three dimensions each coordinate is uniform on [-1, 1].
+
+  let mut v = [0.0; 3];
+  let mut xs = Vec::new();
+  let mut zs = Vec::new();
+  for _ in 0 .. N {
+    rng.unit_vector(&mut v);
+    xs.push((v[0] + 1.0) / 2.0);
+    zs.push((v[2] + 1.0) / 2.0);
+  }
+
+  assert!(stats::ks_uniform(&xs) < stats::ks_critical_value(N));
+  assert!(stats::ks_uniform(&zs) < stats::ks_critical_value(N));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_unit_quaternion() {
+  const N: usize = 100_000;
+
+  let mut rng = Rng::from_u64(0);
+  let qs: Vec<[f64; 4]> = (0 .. N).map(|_| rng.unit_quaternion()).collect();
+
+  assert!(qs.iter().all(|q| (q.iter().map(|x| x * x).sum::<f64>() - 1.0).abs() < 1e-15));
+
+  // On the three-sphere, `x² + y²` is uniform on [0, 1] and each coordinate
+  // has density `2/π √(1 - t²)`.
+
+  let cdf = |t: f64| 0.5 + (t * (1.0 - t * t).sqrt() + t.asin()) / std::f64::consts::PI;
+
+  for i in 0 .. 4 {
+    let ts: Vec<f64> = qs.iter().map(|q| q[i]).collect();
+    assert!(stats::ks(&ts, cdf) < stats::ks_critical_value(N));
+  }
+
+  let rs: Vec<f64> = qs.iter().map(|q| q[0] * q[0] + q[3] * q[3]).collect();
+  assert!(stats::ks_uniform(&rs) < stats::ks_critical_value(N));
+}
+
+#[test]
+fn test_rotation_2d() {
+  const N: usize = 100_000;
+
+  let mut rng = Rng::from_u64(0);
+  let ms: Vec<[[f64; 2]; 2]> = (0 .. N).map(|_| rng.rotation_2d()).collect();
+
+  for &[[a, b], [c, d]] in &ms {
+    assert!((a * d - b * c - 1.0).abs() < 1e-15);
+    assert!(a == d && b == - c);
+  }
+
+  let ts: Vec<f64> = ms.iter().map(|m| m[1][0].atan2(m[0][0]) / std::f64::consts::TAU + 0.5).collect();
+  assert!(stats::ks_uniform(&ts) < stats::ks_critical_value(N));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_rotation_3d() {
+  const N: usize = 100_000;
+
+  let mut rng = Rng::from_u64(0);
+  let ms: Vec<[[f64; 3]; 3]> = (0 .. N).map(|_| rng.rotation_3d()).collect();
+
+  // The matrices are orthogonal with determinant 1.
+
+  for m in &ms {
+    for i in 0 .. 3 {
+      for j in 0 .. 3 {
+        let dot: f64 = (0 .. 3).map(|k| m[i][k] * m[j][k]).sum();
+        assert!((dot - if i == j { 1.0 } else { 0.0 }).abs() < 1e-14);
+      }
+    }
+    let det =
+      m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+    assert!((det - 1.0).abs() < 1e-14);
+  }
+
+  // The image of any fixed vector is uniform on the sphere, so each
+  // coordinate of each column is uniform on [-1, 1].
+
+  for i in 0 .. 3 {
+    for j in 0 .. 3 {
+      let ts: Vec<f64> = ms.iter().map(|m| (m[i][j] + 1.0) / 2.0).collect();
+      assert!(stats::ks_uniform(&ts) < stats::ks_critical_value(N));
+    }
+  }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_annulus() {
+  const N: usize = 100_000;
+
+  let mut rng = Rng::from_u64(0);
+  let ps: Vec<(f64, f64)> = (0 .. N).map(|_| rng.in_annulus(2.0, 3.0)).collect();
+
+  let rs: Vec<f64> = ps.iter().map(|(x, y)| (x * x + y * y - 4.0) / 5.0).collect();
+  assert!(rs.iter().all(|&r| (-1e-12 ..= 1.0 + 1e-12).contains(&r)));
+  assert!(stats::ks_uniform(&rs) < stats::ks_critical_value(N));
+
+  let ts: Vec<f64> = ps.iter().map(|(x, y)| y.atan2(*x) / std::f64::consts::TAU + 0.5).collect();
+  assert!(stats::ks_uniform(&ts) < stats::ks_critical_value(N));
+
+  let (x, y) = rng.in_annulus(1.0, 1.0);
+  assert!((x * x + y * y - 1.0).abs() < 1e-15);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_spherical_cap() {
+  const N: usize = 100_000;
+
+  let mut rng = Rng::from_u64(0);
+
+  for (axis, angle) in [
+    ([0.0, 0.0, 1.0], 0.3),
+    ([0.0, 0.0, -2.0], 1.0),
+    ([1.0, 2.0, -3.0], 2.0),
+    ([-0.5, 0.0, 0.0], std::f64::consts::PI),
+  ] {
+    let l: f64 = axis[0] * axis[0] + axis[1] * axis[1] + axis[2] * axis[2];
+    let n = axis.map(|a| a / l.sqrt());
+    let c = f64::cos(angle);
+    let ps: Vec<[f64; 3]> = (0 .. N).map(|_| rng.on_spherical_cap(axis, angle)).collect();
+    let hs: Vec<f64> =
+      ps.iter()
+        .map(|p| {
+          assert!((p.iter().map(|x| x * x).sum::<f64>() - 1.0).abs() < 1e-14);
+          let h = p[0] * n[0] + p[1] * n[1] + p[2] * n[2];
+          (h - c) / (1.0 - c)
+        })
+        .collect();
+    assert!(hs.iter().all(|&h| (-1e-12 ..= 1.0 + 1e-12).contains(&h)));
+    assert!(stats::ks_uniform(&hs) < stats::ks_critical_value(N));
+
+    // The component orthogonal to the axis has no preferred direction.
+
+    let mean: Vec<f64> = (0 .. 3).map(|i| ps.iter().map(|p| p[i]).sum::<f64>() / N as f64).collect();
+    let h = (1.0 + c) / 2.0;
+    for i in 0 .. 3 { assert!((mean[i] - h * n[i]).abs() < 0.01); }
+  }
+
+  for _ in 0 .. 100 {
+    assert!(rng.cone_direction([0.0, 1.0, 0.0], 0.0) == [0.0, 1.0, 0.0]);
+    assert!(rng.cone_direction([0.0, 1.0, 0.0], 0.1)[1] >= f64::cos(0.1));
+  }
+}
+
+#[cfg(feature = "std")]
+#[test]
+#[should_panic]
+fn test_spherical_cap_zero_axis() {
+  let _ = Rng::from_u64(0).on_spherical_cap([0.0, 0.0, 0.0], 0.5);
+}
+
+#[test]
+fn test_ball() {
+  const N: usize = 100_000;
+
+  let mut rng = Rng::from_u64(0);
+  let ball: Vec<(f64, f64, f64)> = (0 .. N).map(|_| rng.in_unit_ball()).collect();
+
+  assert!(ball.iter().all(|(x, y, z)| x * x + y * y + z * z <= 1.0));
+
+  let cubed_radii: Vec<f64> = ball.iter().map(|(x, y, z)| (x * x + y * y + z * z).powf(1.5)).collect();
+
+  assert!(stats::ks_uniform(&cubed_radii) < stats::ks_critical_value(N));
+}
+
+#[test]
+fn test_triangle() {
+  const N: usize = 100_000;
+
+  let mut rng = Rng::from_u64(0);
+  let ps: Vec<[f64; 2]> = (0 .. N).map(|_| rng.in_triangle([0.0, 0.0], [1.0, 0.0], [0.0, 1.0])).collect();
+
+  assert!(ps.iter().all(|&[x, y]| x >= 0.0 && y >= 0.0 && x + y <= 1.0));
+
+  // In this triangle each coordinate has density `2 (1 - t)`.
+
+  let cdf = |t: f64| 1.0 - (1.0 - t) * (1.0 - t);
+  let xs: Vec<f64> = ps.iter().map(|p| p[0]).collect();
+  let ys: Vec<f64> = ps.iter().map(|p| p[1]).collect();
+
+  assert!(stats::ks(&xs, cdf) < stats::ks_critical_value(N));
+  assert!(stats::ks(&ys, cdf) < stats::ks_critical_value(N));
+
+  let p = rng.in_triangle([1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]);
+  assert!((p[0] + p[1] + p[2] - 1.0).abs() < 1e-15);
+}
+
+#[test]
+fn test_polygon() {
+  const N: usize = 100_000;
+
+  let mut rng = Rng::from_u64(0);
+
+  // A regular hexagon with a duplicated vertex, which adds an empty triangle
+  // to the fan. Any line through the center splits it into equal halves.
+
+  let hexagon: Vec<[f64; 2]> =
+    [0, 1, 1, 2, 3, 4, 5]
+      .iter()
+      .map(|&k| {
+        let t = k as f64 * std::f64::consts::TAU / 6.0;
+        [t.cos(), t.sin()]
+      })
+      .collect();
+
+  let ps: Vec<[f64; 2]> = (0 .. N).map(|_| rng.in_polygon(&hexagon)).collect();
+
+  for k in 0 .. 12 {
+    let t = k as f64 * std::f64::consts::PI / 12.0;
+    let (c, s) = (t.cos(), t.sin());
+    let above = ps.iter().filter(|p| p[0] * s - p[1] * c > 0.0).count();
+    assert!((above as f64 - N as f64 / 2.0).abs() < 3.0 * (N as f64).sqrt());
+  }
+
+  // Every point is inside the hexagon.
+
+  let apothem = 3f64.sqrt() / 2.0;
+  for p in &ps {
+    for k in 0 .. 6 {
+      let t = (k as f64 + 0.5) * std::f64::consts::TAU / 6.0;
+      assert!(p[0] * t.cos() + p[1] * t.sin() <= apothem + 1e-12);
+    }
+  }
+
+  // An L-shaped region made of three unit squares, as a mesh of triangles.
+
+  let mesh = [
+    [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0]],
+    [[0.0, 0.0], [1.0, 1.0], [0.0, 1.0]],
+    [[1.0, 0.0], [2.0, 0.0], [2.0, 1.0]],
+    [[1.0, 0.0], [2.0, 1.0], [1.0, 1.0]],
+    [[0.0, 1.0], [1.0, 1.0], [1.0, 2.0]],
+    [[0.0, 1.0], [1.0, 2.0], [0.0, 2.0]],
+    [[5.0, 5.0], [5.0, 5.0], [6.0, 6.0]],
+  ];
+
+  let mut cells = [0; 4];
+  for _ in 0 .. N {
+    let [x, y] = rng.in_triangles(&mesh);
+    assert!((0.0 ..= 2.0).contains(&x) && (0.0 ..= 2.0).contains(&y));
+    cells[(x >= 1.0) as usize + 2 * (y >= 1.0) as usize] += 1;
+  }
+  assert!(cells[3] == 0);
+  for &c in &cells[.. 3] {
+    assert!((c as f64 - N as f64 / 3.0).abs() < 5.0 * (N as f64 * 2.0 / 9.0).sqrt());
+  }
+}
+
+#[test]
+#[should_panic]
+fn test_polygon_too_few_vertices() {
+  let _ = Rng::from_u64(0).in_polygon(&[[0.0, 0.0], [1.0, 0.0]]);
+}
+
+#[test]
+fn test_simplex_weights() {
+  const N: usize = 100_000;
+
+  let mut rng = Rng::from_u64(0);
+
+  for n in [1, 2, 3, 10, 100] {
+    let mut w = vec![0.0; n];
+    for _ in 0 .. 1000 {
+      rng.simplex_weights(&mut w);
+      assert!(w.iter().all(|&x| x >= 0.0));
+      assert!((w.iter().sum::<f64>() - 1.0).abs() < 1e-14);
+    }
+  }
+
+  rng.simplex_weights(&mut []);
+
+  // In four dimensions each weight has density `3 (1 - t)²`.
+
+  let cdf = |t: f64| 1.0 - (1.0 - t).powi(3);
+  let ws: Vec<[f64; 4]> = (0 .. N).map(|_| { let mut w = [0.0; 4]; rng.simplex_weights(&mut w); w }).collect();
+
+  for i in 0 .. 4 {
+    let ts: Vec<f64> = ws.iter().map(|w| w[i]).collect();
+    assert!(stats::ks(&ts, cdf) < stats::ks_critical_value(N));
+  }
+}
+
+#[cfg(feature = "noise")]
+#[test]
+fn test_noise() -> std::fmt::Result {
+  use dandelion::noise::Perlin;
+
+  let mut out = String::new();
+
+  let mut rng = Rng::from_u64(0);
+  let perlin = Perlin::new(&mut rng);
+  let points: Vec<f64> = (0 .. 10_000).map(|_| 1024.0 * rng.f64() - 512.0).collect();
+
+  for w in points.chunks_exact(3) {
+    let (x, y, z) = (w[0], w[1], w[2]);
+    let a = perlin.noise2(x, y);
+    let b = perlin.noise3(x, y, z);
+    assert!(a.abs() <= 1.1 && b.abs() <= 1.1);
+    assert!((perlin.noise2(x + 256.0, y - 512.0) - a).abs() < 1e-9);
+    assert!((perlin.noise3(x - 256.0, y, z + 256.0) - b).abs() < 1e-9);
+    assert!((perlin.noise2(x + 1e-9, y) - a).abs() < 1e-8);
+    assert!((perlin.noise3(x, y + 1e-9, z) - b).abs() < 1e-8);
+    let (i, j, k) = (x.floor(), y.floor(), z.floor());
+    assert!(perlin.noise2(i, j) == 0.0);
+    assert!(perlin.noise3(i, j, k) == 0.0);
+  }
+
+  for i in 0 .. 5 {
+    let t = i as f64 * 0.37;
+    write!(&mut out, "{:+.12} {:+.12}\n", perlin.noise2(t, -t), perlin.noise3(t, 2.0 * t, -t))?;
+  }
+
+  expect![[r#"
+      +0.000000000000 +0.000000000000
+      +0.562724696978 -0.415238515312
+      +0.341214105468 -0.456127173270
+      -0.108766847134 +0.162884873634
+      -0.257980809216 +0.418847336199
+  "#]].assert_eq(out.drain(..).as_str());
+
+  Ok(())
+}
+
+#[test]
+fn test_quasi() -> std::fmt::Result {
+  use dandelion::quasi::Halton;
+  use dandelion::quasi::MAX_DIMENSION;
+  use dandelion::quasi::Sobol;
+
+  // Each one-dimensional projection of the first 2^m Sobol points, or b^k
+  // Halton points in base b, has one point in each interval of that width.
+  // Unscrambled points lie on the boundaries, up to rounding.
+
+  fn check_strata(points: &[f64], n: usize) {
+    assert!(points.len() == n);
+    let mut hits = vec![false; n];
+    for &x in points {
+      assert!((0.0 .. 1.0).contains(&x));
+      let i = (x * n as f64 + 1e-9) as usize;
+      assert!(! hits[i]);
+      hits[i] = true;
+    }
+  }
+
+  let mut rng = Rng::from_u64(0);
+  let sobol = [Sobol::<MAX_DIMENSION>::new(), Sobol::scrambled(&mut rng)];
+  let halton = [Halton::<MAX_DIMENSION>::new(), Halton::scrambled(&mut rng)];
+
+  for s in &sobol {
+    let points: Vec<_> = s.clone().take(1 << 10).collect();
+    for d in 0 .. MAX_DIMENSION {
+      check_strata(&points.iter().map(|p| p[d]).collect::<Vec<_>>(), 1 << 10);
+    }
+
+    // The first two dimensions form a (0, m, 2)-net.
+
+    for a in 0 ..= 10 {
+      let mut hits = vec![false; 1 << 10];
+      for p in &points {
+        let i = (p[0] * (1 << a) as f64) as usize;
+        let j = (p[1] * (1 << 10 - a) as f64) as usize;
+        assert!(! hits[i << 10 - a | j]);
+        hits[i << 10 - a | j] = true;
+      }
+    }
+  }
+
+  for h in &halton {
+    let primes = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53];
+    for (d, &b) in primes.iter().enumerate() {
+      let n = if b < 32 { b * b } else { b };
+      let points: Vec<_> = h.clone().take(n).map(|p| p[d]).collect();
+      check_strata(&points, n);
+    }
+  }
+
+  // The estimate of the integral of x * y * z over the unit cube should be
+  // much closer than Monte Carlo's error of about 1 / sqrt(n).
+
+  for s in &sobol {
+    let sum: f64 = s.clone().take(1 << 12).map(|p| p[0] * p[1] * p[2]).sum();
+    assert!((sum / (1 << 12) as f64 - 0.125).abs() < 1e-3);
+  }
+
+  for h in &halton {
+    let sum: f64 = h.clone().take(1 << 12).map(|p| p[0] * p[1] * p[2]).sum();
+    assert!((sum / (1 << 12) as f64 - 0.125).abs() < 1e-3);
+  }
+
+  assert!(sobol[1].point(12345) == sobol[1].clone().nth(12345).unwrap());
+  assert!(halton[1].point(12345) == halton[1].clone().nth(12345).unwrap());
+
+  let mut out = String::new();
+
+  for p in Sobol::<2>::new().take(4) { write!(&mut out, "{:?}\n", p)?; }
+  for p in Halton::<2>::new().take(4) { write!(&mut out, "{:?}\n", p)?; }
+  for p in Sobol::<2>::scrambled(&mut Rng::from_u64(0)).take(2) { write!(&mut out, "{:?}\n", p)?; }
+  for p in Halton::<2>::scrambled(&mut Rng::from_u64(0)).take(2) { write!(&mut out, "{:?}\n", p)?; }
+
+  expect![[r#"
+      [0.0, 0.0]
+      [0.5, 0.5]
+      [0.25, 0.75]
+      [0.75, 0.25]
+      [0.0, 0.0]
+      [0.5, 0.3333333333333333]
+      [0.25, 0.6666666666666666]
+      [0.75, 0.1111111111111111]
+      [0.29480823152698576, 0.7525925703812391]
+      [0.8818650208413601, 0.18813036778010428]
+      [0.2948082316262972, 0.962030314170809]
+      [0.8818650209551171, 0.27485024746945125]
+  "#]].assert_eq(out.drain(..).as_str());
+
+  Ok(())
+}
+
+#[test]
+fn test_dice() {
+  use dandelion::dice::DiceExpr;
+  use dandelion::dice::MAX_GROUPS;
+
+  for (s, lo, hi) in [
+    ("3d6+2", 5, 20),
+    (" 3 D 6 + 2 ", 5, 20),
+    ("d20", 1, 20),
+    ("-d4", -4, -1),
+    ("+5", 5, 5),
+    ("1d1", 1, 1),
+    ("0d6 + 1", 1, 1),
+    ("2d6 - 1d4 - 3", -5, 8),
+    ("d%", 1, 100),
+    ("1d2 + 1d2 + 1d2 + 1d2 + 1d2 + 1d2 + 1d2 + 1d2", 8, 16),
+    ("65535d4294967295", 65535, 65535 * 4294967295),
+    ("4294967295 - 4294967295", 0, 0),
+  ] {
+    let e = DiceExpr::parse(s).unwrap();
+    assert!((e.min(), e.max()) == (lo, hi), "{}", s);
+  }
+
+  for s in [
+    "",
+    " ",
+    "+",
+    "3d",
+    "d",
+    "d0",
+    "3d6+",
+    "3d6 1d4",
+    "3x6",
+    "2d6d6",
+    "--1",
+    "65536d6",
+    "1d4294967296",
+    "4294967296",
+    "99999999999999999999999",
+  ] {
+    assert!(DiceExpr::parse(s).is_none(), "{}", s);
+  }
+
+  let too_many = ["d6"; MAX_GROUPS + 1].join("+");
+  assert!(DiceExpr::parse(&too_many).is_none());
+
+  let mut rng = Rng::from_u64(0);
+
+  for s in ["3d6+2", "2d6 - 1d4 - 3", "d%", "-d4", "1d1"] {
+    let e = DiceExpr::parse(s).unwrap();
+    let n = (e.max() - e.min() + 1) as usize;
+    let mut hits = vec![false; n];
+    for _ in 0 .. 100 * n {
+      let x = e.roll(&mut rng);
+      assert!(e.min() <= x && x <= e.max());
+      hits[(x - e.min()) as usize] = true;
+    }
+    assert!(hits.iter().all(|&h| h));
+  }
+
+  let n = 100_000;
+  let mean = (0 .. n).map(|_| rng.roll("3d6+2")).sum::<i64>() as f64 / n as f64;
+  assert!((mean - 12.5).abs() < 0.05);
+}
+
+#[test]
+#[should_panic]
+fn test_roll_invalid() {
+  let _ = Rng::from_u64(0).roll("3d");
+}
+
+#[test]
+fn test_colors() {
+  let mut rng = Rng::from_u64(0);
+
+  let mut seen = [[false; 256]; 4];
+  for _ in 0 .. 10_000 {
+    let [r, g, b] = rng.rgb();
+    let [x, y, z, w] = rng.rgba();
+    seen[0][r as usize] = true;
+    seen[1][g as usize] = true;
+    seen[2][b as usize] = true;
+    for c in [x, y, z, w] { seen[3][c as usize] = true; }
+  }
+  assert!(seen.iter().all(|s| s.iter().all(|&t| t)));
+
+  // Converting back to HSL recovers the sampled saturation and lightness, up
+  // to rounding, and every sextant of hues appears.
+
+  let mut sextants = [false; 6];
+  for _ in 0 .. 10_000 {
+    let [r, g, b] = rng.hsl_pleasing().map(|c| c as f64 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    let s = (max - min) / (1.0 - (2.0 * l - 1.0).abs());
+    assert!((0.44 ..= 0.66).contains(&l));
+    assert!((0.53 ..= 0.87).contains(&s));
+    let i =
+      if max == r { if g >= b { 0 } else { 5 } }
+      else if max == g { if b >= r { 2 } else { 1 } }
+      else if r >= g { 4 } else { 3 };
+    sextants[i] = true;
+  }
+  assert!(sextants.iter().all(|&t| t));
+}
+
+#[test]
+fn test_stratified() {
+  let mut rng = Rng::from_u64(0);
+
+  for n in [0, 1, 2, 7, 100] {
+    let mut dst = vec![0.0; n];
+    rng.stratified(&mut dst);
+    for (i, &x) in dst.iter().enumerate() {
+      assert!((0.0 .. 1.0).contains(&x) && (x * n as f64) as usize == i);
+    }
+
+    rng.stratified_shuffled(&mut dst);
+    let mut hits = vec![false; n];
+    for &x in &dst {
+      assert!((0.0 .. 1.0).contains(&x));
+      hits[(x * n as f64) as usize] = true;
+    }
+    assert!(hits.iter().all(|&h| h));
+  }
+
+  // Within a stratum, the sample is uniformly distributed, and the shuffle
+  // puts each stratum at each position equally often.
+
+  let mut dst = [0.0; 4];
+  let mut xs = Vec::new();
+  let mut counts = [[0; 4]; 4];
+  for _ in 0 .. 10_000 {
+    rng.stratified(&mut dst);
+    xs.push(dst[2] * 4.0 - 2.0);
+    rng.stratified_shuffled(&mut dst);
+    for (i, &x) in dst.iter().enumerate() { counts[i][(x * 4.0) as usize] += 1; }
+  }
+  assert!(stats::ks_uniform(&xs) < stats::ks_critical_value(xs.len()));
+  assert!(counts.iter().flatten().all(|&c| (2300 .. 2700).contains(&c)));
+}
+
+#[test]
+fn test_stratified_2d() {
+  let mut rng = Rng::from_u64(0);
+
+  for (nx, ny) in [(1, 1), (1, 7), (5, 1), (3, 4), (16, 16)] {
+    let mut dst = vec![(0.0, 0.0); nx * ny];
+    rng.stratified_2d(nx, ny, &mut dst);
+    for (k, &(x, y)) in dst.iter().enumerate() {
+      assert!((0.0 .. 1.0).contains(&x) && (0.0 .. 1.0).contains(&y));
+      assert!(k % nx == (x * nx as f64) as usize);
+      assert!(k / nx == (y * ny as f64) as usize);
+    }
+  }
+
+  // Within a cell, the point is uniformly distributed.
+
+  let mut dst = [(0.0, 0.0); 6];
+  let mut xs = Vec::new();
+  let mut ys = Vec::new();
+  for _ in 0 .. 10_000 {
+    rng.stratified_2d(2, 3, &mut dst);
+    xs.push(dst[5].0 * 2.0 - 1.0);
+    ys.push(dst[5].1 * 3.0 - 2.0);
+  }
+  assert!(stats::ks_uniform(&xs) < stats::ks_critical_value(xs.len()));
+  assert!(stats::ks_uniform(&ys) < stats::ks_critical_value(ys.len()));
+
+  rng.stratified_2d(0, 3, &mut []);
+}
+
+#[test]
+#[should_panic]
+fn test_stratified_2d_wrong_length() {
+  Rng::from_u64(0).stratified_2d(2, 3, &mut [(0.0, 0.0); 5]);
+}
+
+#[test]
+fn test_random_walks() {
+  let mut rng = Rng::from_u64(0);
+
+  let mut prev = 0.0;
+  let mut sum = 0.0;
+  let n = 100_000;
+  for x in rng.random_walk_1d(0.5).take(n) {
+    assert!((x - prev).abs() == 0.5);
+    sum += x - prev;
+    prev = x;
+  }
+  assert!((sum / 0.5).abs() < 5.0 * (n as f64).sqrt());
+
+  let mut prev = (0, 0);
+  let mut moves = [0; 4];
+  for (x, y) in rng.random_walk_lattice_2d().take(n) {
+    let i =
+      match (x - prev.0, y - prev.1) {
+        (1, 0) => 0,
+        (-1, 0) => 1,
+        (0, 1) => 2,
+        (0, -1) => 3,
+        _ => panic!(),
+      };
+    moves[i] += 1;
+    prev = (x, y);
+  }
+  for m in moves {
+    assert!((m as f64 - n as f64 / 4.0).abs() < 5.0 * (n as f64 * 3.0 / 16.0).sqrt());
+  }
+
+  // The walk continues to be lazily sampled from the borrowed generator.
+
+  let mut a = Rng::from_u64(1);
+  let mut b = a.clone();
+  let _ = a.random_walk_lattice_2d().nth(9);
+  for _ in 0 .. 10 { let _ = b.u64(); }
+  assert!(a.u64() == b.u64());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_brownian_walk() {
+  let mut rng = Rng::from_u64(0);
+
+  // The positions after 100 steps with standard deviation 0.1 have the
+  // standard normal distribution.
+
+  let n = 10_000;
+  let xs: Vec<f64> = (0 .. n).map(|_| rng.brownian_walk_1d(0.1).nth(99).unwrap()).collect();
+  let mean = xs.iter().sum::<f64>() / n as f64;
+  let var = xs.iter().map(|x| x * x).sum::<f64>() / n as f64;
+  assert!(mean.abs() < 0.05);
+  assert!((var - 1.0).abs() < 0.05);
+
+  let steps: Vec<f64> = rng.brownian_walk_1d(2.0).take(n).collect();
+  let mut prev = 0.0;
+  let mut sum = 0.0;
+  let mut sum_sq = 0.0;
+  for &x in &steps {
+    sum += x - prev;
+    sum_sq += (x - prev) * (x - prev);
+    prev = x;
+  }
+  assert!((sum / n as f64).abs() < 0.1);
+  assert!((sum_sq / n as f64 - 4.0).abs() < 0.2);
+}
+
+#[test]
+fn test_jitter() {
+  use dandelion::jitter::decorrelated_jitter;
+  use dandelion::jitter::equal_jitter;
+  use dandelion::jitter::full_jitter;
+
+  let mut rng = Rng::from_u64(0);
+  let base = Duration::from_millis(100);
+  let cap = Duration::from_secs(10);
+
+  for attempt in [0, 1, 5, 6, 7, 31, 32, 1000] {
+    let t = if attempt <= 6 { base * (1 << attempt) } else { cap };
+    let mut lo = cap;
+    let mut hi = Duration::ZERO;
+    for _ in 0 .. 1000 {
+      let x = full_jitter(&mut rng, base, cap, attempt);
+      let y = equal_jitter(&mut rng, base, cap, attempt);
+      assert!(x <= t);
+      assert!(t / 2 <= y && y <= t);
+      lo = lo.min(x);
+      hi = hi.max(x);
+    }
+    assert!(lo < t / 50 && hi > t * 49 / 50);
+  }
+
+  let mut previous = base;
+  for _ in 0 .. 1000 {
+    let x = decorrelated_jitter(&mut rng, base, cap, previous);
+    assert!(base <= x && x <= cap && x <= previous * 3);
+    previous = x;
+  }
+
+  let huge = Duration::MAX;
+  assert!(full_jitter(&mut rng, huge, huge, 10) > Duration::from_nanos(u64::MAX));
+  assert!(equal_jitter(&mut rng, huge, huge, 10) >= huge / 2);
+  assert!(decorrelated_jitter(&mut rng, huge, huge, huge) == huge);
+  assert!(decorrelated_jitter(&mut rng, base, cap, Duration::ZERO) == base);
+}
+
+#[test]
+fn test_duration_between() {
+  let mut rng = Rng::from_u64(0);
+
+  for (lo, hi) in [
+    (Duration::ZERO, Duration::ZERO),
+    (Duration::new(1, 999_999_999), Duration::new(2, 0)),
+    (Duration::new(5, 0), Duration::new(5, 3)),
+    (Duration::MAX, Duration::MAX),
+    (Duration::MAX - Duration::from_nanos(1), Duration::MAX),
+  ] {
+    let mut seen = std::collections::BTreeSet::new();
+    for _ in 0 .. 100 {
+      let d = rng.duration_between(lo, hi);
+      assert!(lo <= d && d <= hi);
+      let _ = seen.insert(d);
+    }
+    assert!(seen.len() as u128 == (hi - lo).as_nanos() + 1);
+  }
+
+  // The subsecond component is uniform even when the range spans a huge
+  // number of seconds.
+
+  let n = 10_000;
+  let xs: Vec<f64> =
+    (0 .. n)
+      .map(|_| rng.duration_between(Duration::ZERO, Duration::MAX))
+      .map(|d| (d.subsec_nanos() as f64 + 0.5) / 1e9)
+      .collect();
+  assert!(stats::ks_uniform(&xs) < stats::ks_critical_value(n));
+
+  let ys: Vec<f64> =
+    (0 .. n)
+      .map(|_| rng.duration_between(Duration::ZERO, Duration::MAX))
+      .map(|d| d.as_secs() as f64 / u64::MAX as f64)
+      .collect();
+  assert!(stats::ks_uniform(&ys) < stats::ks_critical_value(n));
+}
+
+#[test]
+#[should_panic]
+fn test_duration_between_reversed() {
+  let _ = Rng::from_u64(0).duration_between(Duration::from_secs(2), Duration::from_secs(1));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_system_time_between() {
+  use std::time::SystemTime;
+
+  let mut rng = Rng::from_u64(0);
+  let lo = SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 999_999_998);
+
+  let mut seen = std::collections::BTreeSet::new();
+  for _ in 0 .. 100 {
+    let t = rng.system_time_between(lo, lo + Duration::from_nanos(3));
+    assert!(lo <= t && t <= lo + Duration::from_nanos(3));
+    let _ = seen.insert(t);
+  }
+  assert!(seen.len() == 4);
+  assert!(rng.system_time_between(lo, lo) == lo);
+}
+
+#[cfg(feature = "std")]
+#[test]
+#[should_panic]
+fn test_system_time_between_reversed() {
+  let t = std::time::SystemTime::UNIX_EPOCH;
+  let _ = Rng::from_u64(0).system_time_between(t + Duration::from_secs(1), t);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_datetime_between() {
+  use chrono::DateTime;
+  use chrono::FixedOffset;
+  use chrono::TimeZone;
+  use chrono::Utc;
+
+  let mut rng = Rng::from_u64(0);
+
+  // The range crosses the epoch, where the sign of the timestamp changes.
+
+  let lo = DateTime::from_timestamp(-1, 999_999_999).unwrap();
+  let hi = DateTime::from_timestamp(0, 1).unwrap();
+  let mut seen = std::collections::BTreeSet::new();
+  for _ in 0 .. 100 {
+    let t = rng.datetime_between(lo, hi);
+    assert!(lo <= t && t <= hi);
+    let _ = seen.insert(t);
+  }
+  assert!(seen.len() == 3);
+
+  let tz = FixedOffset::east_opt(9 * 3600).unwrap();
+  let lo = tz.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap();
+  let hi = tz.with_ymd_and_hms(2100, 1, 1, 0, 0, 0).unwrap();
+  for _ in 0 .. 100 {
+    let t = rng.datetime_between(lo, hi);
+    assert!(lo <= t && t <= hi);
+    assert!(t.offset() == &tz);
+  }
+
+  let t = DateTime::<Utc>::MAX_UTC;
+  assert!(rng.datetime_between(t, t) == t);
+  let _ = rng.datetime_between(DateTime::<Utc>::MIN_UTC, DateTime::<Utc>::MAX_UTC);
+}
+
+#[cfg(feature = "half")]
+#[test]
+fn test_f16() {
+  use dandelion::Core;
+  use half::f16;
+
+  // A core whose outputs are shifted right so that small magnitudes, which
+  // round to subnormal `f16`s, are also covered.
+
+  struct Shifted(Rng, u32);
+
+  impl Core for Shifted {
+    fn next_u64(&mut self) -> u64 {
+      self.0.u64() >> self.1
+    }
+  }
+
+  // Each sample is the nearest `f16` to the exact value `|x| / 2⁶³`, which we
+  // check against its neighbors with integer arithmetic.
+
+  let scaled = |y: f16| (y.to_f64() * 2f64.powi(63)) as u128;
+
+  for shift in 0 .. 64 {
+    let mut rng = Rng::from_core(Shifted(Rng::from_u64(shift as u64), shift));
+    let mut a = Rng::from_u64(shift as u64);
+
+    for _ in 0 .. 2_000 {
+      let y = rng.f16();
+      let x = ((a.u64() >> shift) as i64).unsigned_abs() as u128;
+      assert!(y.to_bits() & 0x8000 == 0);
+      let lo = f16::from_bits(y.to_bits().saturating_sub(1));
+      let hi = f16::from_bits(y.to_bits() + 1);
+      assert!(scaled(y).abs_diff(x) <= scaled(lo).abs_diff(x));
+      assert!(scaled(y).abs_diff(x) <= scaled(hi).abs_diff(x));
+    }
+  }
+
+  let mut rng = Rng::from_u64(0);
+  let n = 100_000;
+  let mean = (0 .. n).map(|_| rng.f16().to_f64()).sum::<f64>() / n as f64;
+  assert!((mean - 0.5).abs() < 0.01);
+  let _: f16 = rng.random();
+}
+
+#[cfg(feature = "fixed")]
+#[test]
+fn test_fixed_between() {
+  use fixed::types::I8F8;
+  use fixed::types::I64F64;
+  use fixed::types::U0F8;
+  use fixed::types::U8F8;
+
+  let mut rng = Rng::from_u64(0);
+
+  let lo = I8F8::from_num(-0.5);
+  let hi = I8F8::from_num(0.25);
+  let mut seen = std::collections::BTreeSet::new();
+  for _ in 0 .. 10_000 {
+    let x = rng.fixed_between(lo, hi);
+    assert!(lo <= x && x <= hi);
+    let _ = seen.insert(x);
+  }
+  assert!(seen.len() == 193);
+
+  let lo = U8F8::from_num(3);
+  let hi = U8F8::from_num(3) + U8F8::DELTA;
+  for _ in 0 .. 100 {
+    let x = rng.fixed_between(lo, hi);
+    assert!(x == lo || x == hi);
+  }
+
+  assert!(rng.fixed_between(U0F8::ZERO, U0F8::ZERO) == U0F8::ZERO);
+  let _ = rng.fixed_between(U0F8::MIN, U0F8::MAX);
+  let _ = rng.fixed_between(I64F64::MIN, I64F64::MAX);
+}
+
+#[cfg(feature = "fixed")]
+#[test]
+#[should_panic]
+fn test_fixed_between_reversed() {
+  use fixed::types::I16F16;
+
+  let _ = Rng::from_u64(0).fixed_between(I16F16::ONE, I16F16::ZERO);
+}
+
+#[cfg(feature = "rust_decimal")]
+#[test]
+fn test_decimal_between() {
+  use rust_decimal::Decimal;
+
+  let mut rng = Rng::from_u64(0);
+
+  // The sample has the larger scale, here two decimal places.
+
+  let lo = Decimal::new(-1, 0);
+  let hi = Decimal::new(100, 2);
+  let mut seen = std::collections::BTreeSet::new();
+  for _ in 0 .. 10_000 {
+    let x = rng.decimal_between(lo, hi);
+    assert!(lo <= x && x <= hi);
+    assert!(x.scale() == 2);
+    let _ = seen.insert(x);
+  }
+  assert!(seen.len() == 201);
+
+  let x = Decimal::new(12345, 3);
+  assert!(rng.decimal_between(x, x) == x);
+  let _ = rng.decimal_between(Decimal::MIN, Decimal::MAX);
+}
+
+#[cfg(feature = "rust_decimal")]
+#[test]
+#[should_panic]
+fn test_decimal_between_unrepresentable() {
+  use rust_decimal::Decimal;
+
+  let _ = Rng::from_u64(0).decimal_between(Decimal::new(1, 28), Decimal::MAX);
+}
+
+#[cfg(feature = "num-traits")]
+#[test]
+fn test_uniform_int() {
+  use num_traits::PrimInt;
+
+  fn check<T: PrimInt + std::fmt::Debug>(rng: &mut Rng, lo: T, hi: T, count: usize) {
+    let mut seen = std::collections::BTreeSet::new();
+    for _ in 0 .. 1000 {
+      let x = rng.uniform_int(lo, hi);
+      assert!(lo <= x && x <= hi);
+      let _ = seen.insert(x);
+    }
+    assert!(seen.len() == count);
+  }
+
+  let mut rng = Rng::from_u64(0);
+  check(&mut rng, -3i8, 3, 7);
+  check(&mut rng, 250u8, 255, 6);
+  check(&mut rng, i16::MIN, i16::MIN + 1, 2);
+  check(&mut rng, u64::MAX - 4, u64::MAX, 5);
+  check(&mut rng, i128::MAX, i128::MAX, 1);
+  check(&mut rng, -2isize, 2, 5);
+  let _ = rng.uniform_int(i128::MIN, i128::MAX);
+  let _ = rng.uniform_int(u128::MIN, u128::MAX);
+}
+
+#[cfg(feature = "num-traits")]
+#[test]
+#[should_panic]
+fn test_uniform_int_reversed() {
+  let _ = Rng::from_u64(0).uniform_int(1u32, 0);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_weight_init() {
+  let mut rng = Rng::from_u64(0);
+  let n = 100_001;
+  let mut buf = vec![0f32; n];
+
+  let moments = |buf: &[f32]| {
+    let mean = buf.iter().map(|&x| x as f64).sum::<f64>() / buf.len() as f64;
+    let var = buf.iter().map(|&x| (x as f64 - mean).powi(2)).sum::<f64>() / buf.len() as f64;
+    (mean, var.sqrt())
+  };
+
+  rng.fill_normal(&mut buf, 3.0, 2.0);
+  let (mean, sd) = moments(&buf);
+  assert!((mean - 3.0).abs() < 0.03 && (sd - 2.0).abs() < 0.03);
+  assert!(buf[n - 1] != 0.0);
+
+  rng.fill_he_normal(&mut buf, 50);
+  let (mean, sd) = moments(&buf);
+  assert!(mean.abs() < 0.003 && (sd - 0.2).abs() < 0.003);
+
+  // The uniform distribution on [-a, a] has standard deviation a / sqrt(3).
+
+  rng.fill_xavier_uniform(&mut buf, 100, 200);
+  let a = (6.0f64 / 300.0).sqrt();
+  let (mean, sd) = moments(&buf);
+  assert!(buf.iter().all(|&x| (x as f64).abs() <= a));
+  assert!(mean.abs() < 0.003 && (sd - a / 3f64.sqrt()).abs() < 0.003);
+  assert!(buf[n - 1] != 0.0);
+
+  let xs: Vec<f64> = buf.iter().map(|&x| (x as f64 / a + 1.0) / 2.0).collect();
+  assert!(stats::ks_uniform(&xs) < stats::ks_critical_value(n));
+
+  rng.fill_normal(&mut [], 0.0, 1.0);
+  rng.fill_xavier_uniform(&mut [], 1, 0);
+}
+
+#[test]
+fn test_network_addresses() {
+  let mut rng = Rng::from_u64(0);
+
+  let mut blocks = [0; 3];
+  for _ in 0 .. 10_000 {
+    let a = rng.ipv4_private();
+    assert!(a.is_private());
+    blocks[match a.octets()[0] { 10 => 0, 172 => 1, _ => 2 }] += 1;
+  }
+  assert!(blocks[0] > blocks[1] * 8 && blocks[1] > blocks[2] * 8 && blocks[2] > 0);
+
+  let mut blocks = [0; 3];
+  for _ in 0 .. 10_000 {
+    let [a, b, c, _] = rng.ipv4_documentation().octets();
+    let i =
+      match (a, b, c) {
+        (192, 0, 2) => 0,
+        (198, 51, 100) => 1,
+        (203, 0, 113) => 2,
+        _ => panic!(),
+      };
+    blocks[i] += 1;
+  }
+  assert!(blocks.iter().all(|&k| (3000 .. 3700).contains(&k)));
+
+  for _ in 0 .. 1000 {
+    let a = rng.ipv6_unique_local().segments();
+    let b = rng.ipv6_documentation().segments();
+    assert!(a[0] >> 8 == 0xfd);
+    assert!(b[0] == 0x2001 && b[1] == 0x0db8);
+  }
+
+  // The remaining bits are not constant.
+
+  let xs: Vec<_> = (0 .. 100).map(|_| u128::from(rng.ipv6_unique_local())).collect();
+  assert!(xs.iter().fold(0, |acc, x| acc | x) == u128::MAX >> 8 | 0xfd << 120);
+  let xs: Vec<_> = (0 .. 100).map(|_| u128::from(rng.ipv6_documentation())).collect();
+  assert!(xs.iter().fold(0, |acc, x| acc | x) == u128::MAX >> 32 | 0x2001_0db8 << 96);
+  let xs: Vec<_> = (0 .. 100).map(|_| u128::from(rng.ipv6())).collect();
+  assert!(xs.iter().fold(0, |acc, x| acc | x) == u128::MAX);
+
+  let mut ports = [false; 3];
+  for _ in 0 .. 1000 {
+    let s = rng.socket_addr_v4(8080 ..= 8082);
+    ports[(s.port() - 8080) as usize] = true;
+  }
+  assert!(ports.iter().all(|&p| p));
+  assert!(rng.socket_addr_v4(0 ..= 0).port() == 0);
+  assert!((0 .. 1000).any(|_| rng.socket_addr_v4(0 ..= 65535).port() >= 65000));
+
+  let mut or = [0u8; 6];
+  for _ in 0 .. 100 {
+    let m = rng.mac_address();
+    assert!(m[0] & 0x03 == 0x02);
+    for i in 0 .. 6 { or[i] |= m[i]; }
+  }
+  assert!(or == [0xfe, 0xff, 0xff, 0xff, 0xff, 0xff]);
+}
+
+#[test]
+#[should_panic]
+fn test_socket_addr_v4_empty() {
+  #[allow(clippy::reversed_empty_ranges)]
+  let _ = Rng::from_u64(0).socket_addr_v4(2 ..= 1);
+}
+
+#[test]
+fn test_fill_ascii() {
+  let mut rng = Rng::from_u64(0);
+
+  // Several bytes are produced from each sample, so every position within a
+  // batch of bytes should be uniformly distributed.
+
+  for alphabet in [&b"ab"[..], b"abc", b"0123456789", &[0; 255], &[0; 256]] {
+    let alphabet: Vec<u8> = alphabet.iter().enumerate().map(|(i, _)| i as u8).collect();
+    let n = alphabet.len();
+    let mut buf = vec![0; 64];
+    let mut counts = vec![vec![0; n]; buf.len()];
+    let trials = 200 * n;
+    for _ in 0 .. trials {
+      rng.fill_ascii(&mut buf, &alphabet);
+      for (i, &b) in buf.iter().enumerate() { counts[i][b as usize] += 1; }
+    }
+    let expected = trials as f64 / n as f64;
+    for c in counts.iter().flatten() {
+      assert!((*c as f64 - expected).abs() < 6.0 * expected.sqrt());
+    }
+  }
+
+  let mut buf = [0; 10];
+  rng.fill_ascii(&mut buf, b"x");
+  assert!(&buf == b"xxxxxxxxxx");
+  rng.fill_ascii(&mut [], b"x");
+}
+
+#[test]
+#[should_panic]
+fn test_fill_ascii_empty_alphabet() {
+  Rng::from_u64(0).fill_ascii(&mut [0; 1], b"");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_strings() {
+  let mut rng = Rng::from_u64(0);
+
+  for len in [0, 1, 15, 16, 17, 100] {
+    let a = rng.alphanumeric_string(len);
+    let h = rng.hex_string(len);
+    let s = rng.string_from_alphabet(&['é', '😀', 'z'], len);
+    assert!(a.len() == len && a.bytes().all(|b| b.is_ascii_alphanumeric()));
+    assert!(h.len() == len && h.bytes().all(|b| matches!(b, b'0' ..= b'9' | b'a' ..= b'f')));
+    assert!(s.chars().count() == len && s.chars().all(|c| matches!(c, 'é' | '😀' | 'z')));
+  }
+
+  let a = rng.alphanumeric_string(100_000);
+  let h = rng.hex_string(100_000);
+  let mut seen = std::collections::HashMap::new();
+  for c in a.chars() { *seen.entry(c).or_insert(0) += 1; }
+  assert!(seen.len() == 62 && seen.values().all(|&k| (1400 .. 1830).contains(&k)));
+  let mut seen = std::collections::HashMap::new();
+  for c in h.chars() { *seen.entry(c).or_insert(0) += 1; }
+  assert!(seen.len() == 16 && seen.values().all(|&k| (5800 .. 6700).contains(&k)));
+}
+
+#[test]
+fn test_char() {
+  let mut rng = Rng::from_u64(0);
+
+  // The surrogate gap is skipped without bias against its neighbors.
+
+  let mut counts = [0; 4];
+  for _ in 0 .. 40_000 {
+    let c = rng.between_char('\u{d7fe}', '\u{e001}');
+    counts[match c { '\u{d7fe}' => 0, '\u{d7ff}' => 1, '\u{e000}' => 2, '\u{e001}' => 3, _ => panic!() }] += 1;
+  }
+  assert!(counts.iter().all(|&k| (9600 .. 10400).contains(&k)));
+
+  for (lo, hi) in [('a', 'a'), ('\0', '\0'), (char::MAX, char::MAX), ('\u{d7ff}', '\u{e000}')] {
+    for _ in 0 .. 100 {
+      let c = rng.between_char(lo, hi);
+      assert!(lo <= c && c <= hi);
+    }
+  }
+
+  // The planes are sampled in proportion to their number of scalar values.
+
+  let n = 100_000;
+  let xs: Vec<f64> =
+    (0 .. n)
+      .map(|_| rng.char() as u32)
+      .map(|c| if c < 0xd800 { c } else { c - 0x800 })
+      .map(|c| (c as f64 + 0.5) / 1_112_064.0)
+      .collect();
+  assert!(stats::ks_uniform(&xs) < stats::ks_critical_value(n));
+}
+
+#[test]
+#[should_panic]
+fn test_between_char_reversed() {
+  let _ = Rng::from_u64(0).between_char('b', 'a');
+}
+
+#[test]
+fn test_ulid() {
+  let mut rng = Rng::from_u64(0);
+
+  let decode = |s: &[u8; 26]| -> u128 {
+    s.iter().fold(0, |acc, &c| {
+      let d = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ".iter().position(|&x| x == c).unwrap();
+      acc << 5 | d as u128
+    })
+  };
+
+  let a = rng.ulid(1469918176385);
+  assert!(&a[.. 10] == b"01ARYZ6S41");
+  assert!(decode(&a) >> 80 == 1469918176385);
+  assert!(&rng.ulid(0)[.. 10] == b"0000000000");
+  assert!(&rng.ulid((1 << 48) - 1)[.. 10] == b"7ZZZZZZZZZ");
+
+  let mut ids: Vec<_> = (0 .. 1000).map(|t| rng.ulid(t / 10)).collect();
+  let random_bits = ids.iter().fold(0, |acc, s| acc | decode(s) & (1 << 80) - 1);
+  assert!(random_bits == (1 << 80) - 1);
+  let unsorted = ids.clone();
+  ids.sort();
+  assert!(ids.iter().zip(&unsorted).all(|(a, b)| a[.. 10] == b[.. 10]));
+}
+
+#[test]
+#[should_panic]
+fn test_ulid_out_of_range() {
+  let _ = Rng::from_u64(0).ulid(1 << 48);
+}
+
+#[test]
+fn test_ksuid() {
+  let mut rng = Rng::from_u64(0);
+
+  let alphabet = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+  // Decodes to big-endian 32-bit digits.
+
+  let decode = |s: &[u8; 27]| -> [u32; 5] {
+    let mut n = [0u32; 5];
+    for &c in s {
+      let mut carry = alphabet.iter().position(|&x| x == c).unwrap() as u64;
+      for d in n.iter_mut().rev() {
+        let v = *d as u64 * 62 + carry;
+        *d = v as u32;
+        carry = v >> 32;
+      }
+      assert!(carry == 0);
+    }
+    n
+  };
+
+  assert!(decode(b"aWgEPTl1tmebfsQzFP4bxwgy80V") == [u32::MAX; 5]);
+  assert!(decode(b"0ujtsYcgvSTl8PAuAdqWYSMnLOv") == [0x0669f7ef, 0xb5a1cd34, 0xb5f99d11, 0x54fb6853, 0x345c9735]);
+
+  let a = rng.ksuid(1507608047);
+  assert!(decode(&a)[0] == 1507608047 - 1_400_000_000);
+  assert!(&a[.. 5] == b"0ujts");
+  assert!(decode(&rng.ksuid(1_400_000_000))[0] == 0);
+  assert!(decode(&rng.ksuid(1_400_000_000 + u32::MAX as u64))[0] == u32::MAX);
+
+  let mut ids: Vec<_> = (0 .. 1000).map(|t| rng.ksuid(1_500_000_000 + t * 1000)).collect();
+  let random_bits = ids.iter().fold([0; 4], |acc, s| {
+    let n = decode(s);
+    [acc[0] | n[1], acc[1] | n[2], acc[2] | n[3], acc[3] | n[4]]
+  });
+  assert!(random_bits == [u32::MAX; 4]);
+  let unsorted = ids.clone();
+  ids.sort();
+  assert!(ids == unsorted);
+}
+
+#[test]
+#[should_panic]
+fn test_ksuid_out_of_range() {
+  let _ = Rng::from_u64(0).ksuid(1_399_999_999);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_markov_chain() {
+  use dandelion::markov::MarkovChain;
+
+  let mut rng = Rng::from_u64(0);
+
+  assert!(MarkovChain::new::<[f64; 0]>(&[]).is_none());
+  assert!(MarkovChain::new(&[[1.0, 1.0]]).is_none());
+  assert!(MarkovChain::new(&[vec![1.0, 1.0], vec![1.0]]).is_none());
+  assert!(MarkovChain::new(&[[1.0, 1.0], [0.0, 0.0]]).is_none());
+  assert!(MarkovChain::new(&[[1.0, -1.0], [1.0, 1.0]]).is_none());
+  assert!(MarkovChain::new(&[[1.0, f64::NAN], [1.0, 1.0]]).is_none());
+  assert!(MarkovChain::new(&[[1.0, f64::INFINITY], [1.0, 1.0]]).is_none());
+  assert!(MarkovChain::new(&[[f64::MAX, f64::MAX], [1.0, 1.0]]).is_none());
+
+  // A deterministic cycle.
+
+  let mut chain = MarkovChain::new(&[[0.0, 1.0, 0.0], [0.0, 0.0, 2.0], [3.0, 0.0, 0.0]]).unwrap();
+  assert!(chain.num_states() == 3 && chain.state() == 0);
+  let states: Vec<usize> = chain.iter(&mut rng).take(6).collect();
+  assert!(states == [1, 2, 0, 1, 2, 0]);
+  chain.set_state(2);
+  assert!(chain.step(&mut rng) == 0);
+
+  // The long run frequencies match the stationary distribution, which is
+  // (5/6, 1/6) for this chain.
+
+  let mut chain = MarkovChain::new(&[[0.9, 0.1], [0.5, 0.5]]).unwrap();
+  let n = 100_000;
+  let ones = chain.iter(&mut rng).take(n).filter(|&s| s == 1).count();
+  assert!((ones as f64 / n as f64 - 1.0 / 6.0).abs() < 0.01);
+
+  // Each row is sampled in proportion to its weights.
+
+  let weights = [1.0, 2.0, 3.0, 4.0, 0.0, 10.0];
+  let mut chain = MarkovChain::new(&[weights; 6]).unwrap();
+  let mut counts = [0; 6];
+  for s in chain.iter(&mut rng).take(n) { counts[s] += 1; }
+  for (c, w) in counts.iter().zip(weights) {
+    let p = w / 20.0;
+    assert!((*c as f64 - n as f64 * p).abs() <= 6.0 * (n as f64 * p * (1.0 - p)).sqrt());
+  }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+#[should_panic]
+fn test_markov_chain_set_state_out_of_range() {
+  dandelion::markov::MarkovChain::new(&[[1.0]]).unwrap().set_state(1);
+}
+
+#[cfg(feature = "graph")]
+#[test]
+fn test_erdos_renyi() {
+  use dandelion::graph::erdos_renyi;
+
+  let mut rng = Rng::from_u64(0);
+
+  assert!(erdos_renyi(&mut rng, 100, 0.0).is_empty());
+  assert!(erdos_renyi(&mut rng, 100, f64::NAN).is_empty());
+  assert!(erdos_renyi(&mut rng, 0, 0.5).is_empty());
+  assert!(erdos_renyi(&mut rng, 1, 0.5).is_empty());
+
+  let complete = erdos_renyi(&mut rng, 5, 1.0);
+  assert!(complete == [(0, 1), (0, 2), (1, 2), (0, 3), (1, 3), (2, 3), (0, 4), (1, 4), (2, 4), (3, 4)]);
+
+  // Each edge appears with probability `p`, independently of its position.
+
+  let n = 40;
+  let p = 0.3;
+  let trials = 2000;
+  let mut counts = vec![vec![0; n]; n];
+  let mut total = 0;
+  for _ in 0 .. trials {
+    let edges = erdos_renyi(&mut rng, n as u32, p);
+    assert!(edges.windows(2).all(|w| (w[0].1, w[0].0) < (w[1].1, w[1].0)));
+    for &(u, v) in &edges {
+      assert!(u < v && (v as usize) < n);
+      counts[v as usize][u as usize] += 1;
+    }
+    total += edges.len();
+  }
+  let pairs = n * (n - 1) / 2;
+  let mean = total as f64 / (trials * pairs) as f64;
+  assert!((mean - p).abs() < 0.002);
+  for (v, row) in counts.iter().enumerate() {
+    for &c in &row[.. v] {
+      assert!((c as f64 - trials as f64 * p).abs() < 6.0 * (trials as f64 * p * (1.0 - p)).sqrt());
+    }
+  }
+}
+
+#[cfg(feature = "graph")]
+#[test]
+fn test_barabasi_albert() {
+  use dandelion::graph::barabasi_albert;
+
+  let mut rng = Rng::from_u64(0);
+
+  assert!(barabasi_albert(&mut rng, 2, 1) == [(0, 1)]);
+
+  let n = 10_000;
+  let m = 3;
+  let edges = barabasi_albert(&mut rng, n, m);
+  assert!(edges.len() == (m * (n - m)) as usize);
+
+  let mut sorted = edges.clone();
+  sorted.sort_unstable();
+  sorted.dedup();
+  assert!(sorted.len() == edges.len());
+
+  let mut degree = vec![0u32; n as usize];
+  for &(u, v) in &edges {
+    assert!(u < v && v < n);
+    degree[u as usize] += 1;
+    degree[v as usize] += 1;
+  }
+  assert!(degree[m as usize ..].iter().all(|&d| d >= m));
+
+  // Preferential attachment makes early vertices hubs, with degrees growing
+  // like the square root of the number of vertices.
+
+  let early: u32 = degree[.. 10].iter().sum();
+  let late: u32 = degree[n as usize - 10 ..].iter().sum();
+  assert!(early > 10 * late);
+}
+
+#[cfg(feature = "graph")]
+#[test]
+#[should_panic]
+fn test_barabasi_albert_invalid() {
+  let _ = dandelion::graph::barabasi_albert(&mut Rng::from_u64(0), 3, 3);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_random_tree() {
+  let mut rng = Rng::from_u64(0);
+
+  assert!(rng.random_tree(0).is_empty());
+  assert!(rng.random_tree(1) == [0]);
+  assert!(rng.random_tree(2) == [0, 0]);
+
+  // Following parents from any vertex reaches the root.
+
+  for n in [3, 10, 1000] {
+    let parent = rng.random_tree(n);
+    assert!(parent.len() == n as usize && parent[0] == 0);
+    for v in 0 .. n {
+      let mut u = v;
+      for _ in 0 .. n { u = parent[u as usize]; }
+      assert!(u == 0);
+    }
+  }
+
+  // There are 4⁴⁻² = 16 labeled trees on 4 vertices, and each one should be
+  // equally likely. A tree is identified by its set of edges.
+
+  let n = 160_000;
+  let mut counts = std::collections::HashMap::new();
+  for _ in 0 .. n {
+    let parent = rng.random_tree(4);
+    let mut edges: Vec<(u32, u32)> = (1 .. 4).map(|v| (v.min(parent[v as usize]), v.max(parent[v as usize]))).collect();
+    edges.sort();
+    *counts.entry(edges).or_insert(0) += 1;
+  }
+  assert!(counts.len() == 16);
+  assert!(counts.values().all(|&c| (c as f64 - 10_000.0).abs() < 500.0));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_random_parent_array() {
+  let mut rng = Rng::from_u64(0);
+
+  assert!(rng.random_parent_array(0).is_empty());
+  assert!(rng.random_parent_array(1) == [0]);
+  assert!(rng.random_parent_array(2) == [0, 0]);
+
+  // The labels are in preorder: each vertex's parent is the previous vertex
+  // or one of its ancestors.
+
+  for n in [3, 10, 1000] {
+    let parent = rng.random_parent_array(n);
+    assert!(parent.len() == n as usize && parent[0] == 0);
+    let mut path = vec![0];
+    for v in 1 .. n {
+      while *path.last().unwrap() != parent[v as usize] { let _ = path.pop().unwrap(); }
+      path.push(v);
+    }
+  }
+
+  // There are 5 ordered trees on 4 vertices, and each one should be equally
+  // likely.
+
+  let n = 100_000;
+  let mut counts = std::collections::HashMap::new();
+  for _ in 0 .. n {
+    *counts.entry(rng.random_parent_array(4)).or_insert(0) += 1;
+  }
+  assert!(counts.len() == 5);
+  assert!(counts.values().all(|&c| (c as f64 - 20_000.0).abs() < 700.0));
+}
+
+#[cfg(feature = "value")]
+#[test]
+fn test_value_generator() -> std::fmt::Result {
+  use dandelion::value::JsonWriter;
+  use dandelion::value::ValueGenerator;
+  use dandelion::value::Visitor;
+
+  // Checks the structure of the visited values. Each open container records
+  // whether it is a map and, for maps, whether a key is awaiting its value.
+
+  #[derive(Default)]
+  struct Check { stack: Vec<(bool, bool)>, max_depth: usize, values: u32, kinds: [u32; 7] }
+
+  impl Check {
+    fn value(&mut self, kind: usize) {
+      if let Some((is_map, pending)) = self.stack.last_mut() {
+        assert!(*pending || ! *is_map);
+        *pending = false;
+      }
+      self.values += 1;
+      self.kinds[kind] += 1;
+    }
+    fn begin(&mut self, is_map: bool) {
+      self.stack.push((is_map, false));
+      self.max_depth = self.max_depth.max(self.stack.len());
+    }
+    fn end(&mut self, is_map: bool) {
+      assert!(self.stack.pop() == Some((is_map, false)));
+    }
+  }
+
+  impl Visitor for Check {
+    fn null(&mut self) { self.value(0); }
+    fn bool(&mut self, _: bool) { self.value(1); }
+    fn i64(&mut self, _: i64) { self.value(2); }
+    fn f64(&mut self, x: f64) { assert!(x.is_finite()); self.value(3); }
+    fn string(&mut self, x: &str) { assert!(x.chars().count() <= 5); self.value(4); }
+    fn begin_array(&mut self) { self.value(5); self.begin(false); }
+    fn end_array(&mut self) { self.end(false); }
+    fn begin_map(&mut self) { self.value(6); self.begin(true); }
+    fn key(&mut self, x: &str) {
+      assert!(x.chars().count() <= 5);
+      assert!(self.stack.last() == Some(&(true, false)));
+      self.stack.last_mut().unwrap().1 = true;
+    }
+    fn end_map(&mut self) { self.end(true); }
+  }
+
+  let mut rng = Rng::from_u64(0);
+  let generator = ValueGenerator { max_depth: 3, max_values: 40, max_len: 6, max_string_len: 5 };
+  let mut kinds = [0; 7];
+  let mut deepest = 0;
+
+  for _ in 0 .. 1000 {
+    let mut check = Check::default();
+    generator.generate(&mut rng, &mut check);
+    assert!(check.stack.is_empty());
+    assert!(check.max_depth <= 3 && check.values <= 40 && check.values >= 1);
+    deepest = deepest.max(check.max_depth);
+    for (k, c) in kinds.iter_mut().zip(check.kinds) { *k += c; }
+  }
+  assert!(deepest == 3 && kinds.iter().all(|&k| k > 100));
+
+  let scalars = ValueGenerator { max_depth: 0, ..generator.clone() };
+  for _ in 0 .. 100 {
+    let mut check = Check::default();
+    scalars.generate(&mut rng, &mut check);
+    assert!(check.values == 1 && check.max_depth == 0);
+  }
+
+  // The same seed generates the same value.
+
+  let a = { let mut w = JsonWriter::new(); generator.generate(&mut Rng::from_u64(1), &mut w); w.finish() };
+  let b = { let mut w = JsonWriter::new(); generator.generate(&mut Rng::from_u64(1), &mut w); w.finish() };
+  assert!(a == b);
+
+  let mut w = JsonWriter::new();
+  w.begin_map();
+  w.key("a\"\\\n\u{1}é");
+  w.begin_array();
+  w.null();
+  w.bool(true);
+  w.i64(-3);
+  w.f64(0.5);
+  w.f64(1e300);
+  w.begin_map();
+  w.end_map();
+  w.end_array();
+  w.key("b");
+  w.string("");
+  w.end_map();
+
+  let mut out = String::new();
+  writeln!(&mut out, "{}", w.finish())?;
+  let mut w = JsonWriter::new();
+  ValueGenerator { max_depth: 2, max_values: 8, max_len: 3, max_string_len: 4 }.generate(&mut Rng::from_u64(0), &mut w);
+  writeln!(&mut out, "{}", w.finish())?;
+
+  expect![[r#"
+      {"a\"\\\n\u0001é":[null,true,-3,0.5,1e300,{}],"b":""}
+      -160058.08565427925
+  "#]].assert_eq(out.drain(..).as_str());
+
+  Ok(())
+}
+
+#[cfg(feature = "pattern")]
+#[test]
+fn test_pattern() {
+  use dandelion::pattern::Pattern;
+
+  let mut rng = Rng::from_u64(0);
+
+  // Each pattern is paired with a predicate that recognizes its matches.
+
+  type Matches = Box<dyn Fn(&str) -> bool>;
+
+  let cases: Vec<(&str, Matches)> = vec![
+    ("", Box::new(|s| s.is_empty())),
+    ("abc", Box::new(|s| s == "abc")),
+    ("^abc$", Box::new(|s| s == "abc")),
+    (r"a\$", Box::new(|s| s == "a$")),
+    (r"[a-z]{3,8}@(example|test)\.com", Box::new(|s| {
+      let Some((user, domain)) = s.split_once('@') else { return false };
+      (3 ..= 8).contains(&user.len())
+        && user.bytes().all(|b| b.is_ascii_lowercase())
+        && (domain == "example.com" || domain == "test.com")
+    })),
+    (r"\d{4}-\d{2}", Box::new(|s| {
+      s.len() == 7 && s.bytes().enumerate().all(|(i, b)| if i == 4 { b == b'-' } else { b.is_ascii_digit() })
+    })),
+    (r"[^a-z]+", Box::new(|s| (1 ..= 9).contains(&s.len()) && s.bytes().all(|b| (b' ' ..= b'~').contains(&b) && ! b.is_ascii_lowercase()))),
+    (r"[]a]?", Box::new(|s| s.is_empty() || s == "]" || s == "a")),
+    (r"[\w-]*", Box::new(|s| s.len() <= 8 && s.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-'))),
+    (r"(a|b(c|d)){2}", Box::new(|s| ["aa", "abc", "abd", "bca", "bda", "bcbc", "bcbd", "bdbc", "bdbd"].contains(&s))),
+    (r"x{2,}", Box::new(|s| (2 ..= 10).contains(&s.len()) && s.bytes().all(|b| b == b'x'))),
+    (".", Box::new(|s| s.len() == 1 && s.bytes().all(|b| (b' ' ..= b'~').contains(&b)))),
+    ("[\u{d7ff}-\u{e000}]", Box::new(|s| s == "\u{d7ff}" || s == "\u{e000}")),
+    ("é|😀", Box::new(|s| s == "é" || s == "😀")),
+  ];
+
+  for (pattern, matches) in &cases {
+    let p = Pattern::parse(pattern).unwrap();
+    for _ in 0 .. 200 {
+      let s = p.sample(&mut rng);
+      assert!(matches(&s), "{:?} {:?}", pattern, s);
+    }
+  }
+
+  for pattern in ["(", ")", "a)", "[", "[]", "[z-a]", "*", "a**", "a{", "a{2", "a{3,2}", "a{x}", r"\q", "a|*", "[^ -~]"] {
+    assert!(Pattern::parse(pattern).is_none(), "{:?}", pattern);
+  }
+
+  // Alternatives, repetition counts, and class members are uniform.
+
+  let mut counts = std::collections::HashMap::new();
+  for _ in 0 .. 60_000 {
+    *counts.entry(rng.from_pattern("(a|bb|[cde]|f{0,2})")).or_insert(0) += 1;
+  }
+  let expected = [("a", 15000.0), ("bb", 15000.0), ("c", 5000.0), ("d", 5000.0), ("e", 5000.0), ("", 5000.0), ("f", 5000.0), ("ff", 5000.0)];
+  assert!(counts.len() == expected.len());
+  for (s, e) in expected {
+    assert!((counts[s] as f64 - e).abs() < 6.0 * f64::sqrt(e), "{:?}", s);
+  }
+}
+
+#[cfg(feature = "pattern")]
+#[test]
+#[should_panic]
+fn test_from_pattern_invalid() {
+  let _ = Rng::from_u64(0).from_pattern("(");
+}
+
+#[test]
+fn test_round_stochastic() {
+  let mut rng = Rng::from_u64(0);
+
+  for x in [0.0, 1.0, -1.0, 3.0, -1e15, 1e300, -1e300, f64::INFINITY, f64::NEG_INFINITY] {
+    assert!(rng.round_stochastic(x) == x as i64);
+  }
+  assert!(rng.round_stochastic(f64::NAN) == 0);
+  assert!(rng.round_stochastic(-0.0) == 0);
+
+  for x in [0.25f64, 2.9, -0.25, -7.5, 1e-300, -1e-300, 123456789.125] {
+    let n = 100_000;
+    let lo = x.floor() as i64;
+    let mut ups = 0;
+    for _ in 0 .. n {
+      let y = rng.round_stochastic(x);
+      assert!(y == lo || y == lo + 1);
+      ups += (y == lo + 1) as u32;
+    }
+    let p = x - x.floor();
+    assert!((ups as f64 - n as f64 * p).abs() <= 6.0 * (n as f64 * p * (1.0 - p)).sqrt());
+  }
+
+  for (x, step) in [(0.3f64, 0.25f64), (-0.3, 0.25), (10.0, 3.0), (1.0, 0.5)] {
+    let n = 100_000;
+    let mut sum = 0.0;
+    for _ in 0 .. n {
+      let y = rng.quantize_stochastic(x, step);
+      let k = y / step;
+      assert!(k == k.round() && (y - x).abs() < step);
+      sum += y;
+    }
+    assert!((sum / n as f64 - x).abs() < 0.01 * step);
+  }
+}
+
+#[test]
+fn test_mix() {
+  use dandelion::mix::{mix128, mix64};
+
+  assert!(mix64(0) == 0);
+  assert!(mix128(0) == 0);
+
+  // The seeding constructors hash their seeds with `mix128`.
+
+  for seed in [0, 1, 12345, u64::MAX] {
+    let s = mix128(seed as u128 | 1 << 64);
+    assert!(Rng::from_u64(seed).state().get() == s);
+  }
+
+  // Flipping any one input bit flips about half of the output bits.
+
+  let mut rng = Rng::from_u64(0);
+  let mut flips64 = 0;
+  let mut flips128 = 0;
+  for _ in 0 .. 1000 {
+    let x = rng.u64();
+    let y = (rng.u64() as u128) << 64 | rng.u64() as u128;
+    let k = rng.bounded_u32(63);
+    let j = rng.bounded_u32(127);
+    flips64 += (mix64(x) ^ mix64(x ^ 1u64 << k)).count_ones();
+    flips128 += (mix128(y) ^ mix128(y ^ 1u128 << j)).count_ones();
+  }
+  assert!((30_000 ..= 34_000).contains(&flips64));
+  assert!((60_000 ..= 68_000).contains(&flips128));
+}
+
+#[test]
+fn test_hash() {
+  use dandelion::hash::{BuildMix128Hasher, Mix128Hasher, hash_one};
+  use std::collections::HashSet;
+  use std::hash::{BuildHasher, Hasher};
+
+  // Hashes are deterministic and depend on the seed.
+
+  assert!(hash_one("abc") == hash_one("abc"));
+  assert!(hash_one("abc") != hash_one("abd"));
+  assert!(hash_one(&1u64) == BuildMix128Hasher.hash_one(1u64));
+
+  let mut a = Mix128Hasher::with_seed(1);
+  let mut b = Mix128Hasher::with_seed(2);
+  a.write(b"hello");
+  b.write(b"hello");
+  assert!(a.finish() != b.finish());
+
+  // Trailing zeros and block boundaries are distinguished.
+
+  let mut seen = HashSet::new();
+  for n in 0 .. 40 {
+    let mut h = Mix128Hasher::new();
+    h.write(&vec![0u8; n]);
+    assert!(seen.insert(h.finish()));
+  }
+
+  // The hasher works as a hash map hasher, and spreads small keys evenly.
+
+  let set: HashSet<u64, BuildMix128Hasher> = (0 .. 1000).collect();
+  assert!(set.len() == 1000 && set.contains(&999));
+
+  let mut counts = [0u32; 16];
+  for i in 0 .. 16_000u64 {
+    counts[(hash_one(&i) % 16) as usize] += 1;
+  }
+  assert!(counts.iter().all(|&c| (850 ..= 1150).contains(&c)));
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_derive() {
+  use dandelion::FromRng;
+  use dandelion::Random;
+
+  #[derive(Random, Debug, PartialEq)]
+  struct Unit;
+
+  #[derive(Random, Debug, PartialEq)]
+  struct Pair(u32, bool);
+
+  #[derive(Random, Debug, PartialEq)]
+  struct Wrapper<T> { inner: T, id: u64 }
+
+  #[derive(Random, Debug, PartialEq)]
+  enum Shape {
+    #[random(weight = 3)]
+    Circle { r: f64 },
+    Square(f64),
+    #[random(weight = 0)]
+    #[allow(dead_code)]
+    Never,
+    Empty,
+  }
+
+  // Fields are sampled in declaration order.
+
+  let mut a = Rng::from_u64(0);
+  let mut b = Rng::from_u64(0);
+  let w = a.random::<Wrapper<Pair>>();
+  assert!(w == Wrapper { inner: Pair(b.u32(), b.bool()), id: b.u64() });
+  assert!(Unit::from_rng(&mut a) == Unit);
+
+  let mut x = Rng::from_core(dandelion::xoshiro::Xoshiro256StarStar::from_u64(0));
+  let mut y = x.clone();
+  let p = x.random::<Pair>();
+  assert!(p == Pair(y.u32(), y.bool()));
+
+  let mut counts = [0; 4];
+  for _ in 0 .. 50_000 {
+    match a.random::<Shape>() {
+      Shape::Circle { r } => { assert!((0.0 .. 1.0).contains(&r)); counts[0] += 1; }
+      Shape::Square(_) => { counts[1] += 1; }
+      Shape::Never => { counts[2] += 1; }
+      Shape::Empty => { counts[3] += 1; }
+    }
+  }
+  assert!(counts[2] == 0);
+  assert!((29_000 ..= 31_000).contains(&counts[0]));
+  assert!((9_500 ..= 10_500).contains(&counts[1]));
+  assert!((9_500 ..= 10_500).contains(&counts[3]));
+}
+
+#[test]
+fn test_from_rng() {
+  let mut rng = Rng::from_u64(0);
+
+  // Each sample consumes the same outputs as the corresponding method.
+
+  let mut a = rng.clone();
+  assert!(rng.random::<u8>() == a.u32() as u8);
+  assert!(rng.random::<i16>() == a.u32() as i16);
+  assert!(rng.random::<usize>() == a.u64() as usize);
+  assert!(rng.random::<char>() == a.char());
+  assert!(rng.random::<f32>() == a.f32());
+  let x = a.u64() as u128;
+  let y = a.u64() as u128;
+  assert!(rng.random::<u128>() == x | y << 64);
+  assert!(rng.random::<[u32; 4]>() == [a.u32(), a.u32(), a.u32(), a.u32()]);
+  assert!(rng.random::<(u64, bool, f64)>() == (a.u64(), a.bool(), a.f64()));
+  assert!(rng.random::<[(u8, i8); 2]>() == [(a.u32() as u8, a.u32() as i8), (a.u32() as u8, a.u32() as i8)]);
+
+  // Small integer types are uniform over all of their values.
+
+  let mut counts = [0u32; 256];
+  for _ in 0 .. 256_000 {
+    counts[rng.random::<u8>() as usize] += 1;
+  }
+  assert!(counts.iter().all(|&c| (850 ..= 1150).contains(&c)));
+  assert!((0 .. 1000).any(|_| rng.random::<i128>() < 0));
+  let _ = rng.random::<(u8, u16, u32, u64, i8, i16, i32, i64, isize, i128, bool, f64)>();
+}
+
+#[test]
+fn test_option_result() {
+  let mut rng = Rng::from_u64(0);
+
+  assert!(rng.option(0.0, |_| -> () { unreachable!() }).is_none());
+  assert!(rng.option(1.0, |rng| rng.random::<[u8; 3]>()).is_some());
+  assert!(rng.result(1.0, |_| 1, |_| -> () { unreachable!() }) == Ok(1));
+  assert!(rng.result(0.0, |_| -> () { unreachable!() }, |_| 2) == Err(2));
+
+  let n = 100_000;
+  let somes = (0 .. n).filter(|_| rng.option(0.3, |rng| rng.u32()).is_some()).count();
+  let oks = (0 .. n).filter(|_| rng.result(0.7, |_| (), |_| ()).is_ok()).count();
+  assert!((29_000 ..= 31_000).contains(&somes));
+  assert!((69_000 ..= 71_000).contains(&oks));
+}
+
+#[test]
+fn test_distribution() {
+  use dandelion::distribution::{Distribution, from_fn, random};
+
+  let mut rng = Rng::from_u64(0);
+
+  // Combinators draw from the generator exactly as the equivalent code would.
+
+  let d = random::<u32>().map(|x| x % 10).zip(from_fn(|rng| rng.bool()));
+  let mut a = rng.clone();
+  for _ in 0 .. 100 {
+    assert!(rng.sample(&d) == (a.u32() % 10, a.bool()));
+  }
+
+  let even = random::<u8>().filter_reject(|x| x % 2 == 0, 64);
+  for _ in 0 .. 1000 {
+    assert!(even.sample(&mut rng).unwrap() % 2 == 0);
+  }
+
+  let never = random::<u8>().filter_reject(|_| false, 5);
+  let mut a = rng.clone();
+  assert!(rng.sample(&never).is_none());
+  for _ in 0 .. 5 { let _ = a.u32(); }
+  assert!(rng.u64() == a.u64());
+
+  // An exponential distribution, clamped and rounded.
+
+  let d = from_fn(|rng| -(1.0 - rng.f64()).ln() * 10.0).map(|x| x.min(50.0).round() as u32);
+  let mean = (0 .. 100_000).map(|_| d.sample(&mut rng) as f64).sum::<f64>() / 100_000.0;
+  assert!((9.6 ..= 10.2).contains(&mean));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_distribution_boxed() {
+  use dandelion::distribution::{BoxedDistribution, Distribution, from_fn, random};
+
+  let ds: Vec<BoxedDistribution<'_, u64>> = vec![
+    random::<u64>().map(|x| x % 6 + 1).boxed(),
+    from_fn(|_| 7).boxed(),
+  ];
+
+  let mut rng = Rng::from_u64(0);
+  for _ in 0 .. 1000 {
+    assert!((1 ..= 6).contains(&rng.sample(&ds[0])));
+    assert!(ds[1].sample(&mut rng) == 7);
+  }
+}
+
+#[test]
+fn test_extend_random() {
+  use std::collections::{BTreeMap, HashSet};
+
+  let mut rng = Rng::from_u64(0);
+  let mut a = rng.clone();
+
+  let mut v = vec![7u32];
+  rng.extend_random(&mut v, 3, |rng| rng.u32());
+  assert!(v == [7, a.u32(), a.u32(), a.u32()]);
+
+  let s: HashSet<u32> = rng.collect_random(1000, |rng| rng.bounded_u32(9));
+  assert!(s.len() == 10);
+
+  let m: BTreeMap<u8, u64> = rng.collect_random(5, |rng| rng.random::<(u8, u64)>());
+  assert!((1 ..= 5).contains(&m.len()));
+
+  let e: Vec<f64> = rng.collect_random(0, |_| -> f64 { unreachable!() });
+  assert!(e.is_empty());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_random_vec() {
+  let mut rng = Rng::from_u64(0);
+  let mut a = rng.clone();
+  let v = rng.random_vec::<(u64, bool)>(4);
+  assert!(v.len() == 4);
+  assert!(v.iter().all(|&x| x == (a.u64(), a.bool())));
+}
+
+#[test]
+fn test_entropy_source() {
+  use dandelion::entropy::EntropySource;
+
+  struct Counter(u8);
+
+  impl EntropySource for Counter {
+    type Error = ();
+
+    fn fill(&mut self, buf: &mut [u8]) -> Result<(), ()> {
+      for b in buf.iter_mut() { *b = self.0; self.0 = self.0.wrapping_add(1); }
+      Ok(())
+    }
+  }
+
+  // The seed is the little-endian interpretation of 16 bytes from the source,
+  // with the low bit set.
+
+  let rng = Rng::from_entropy_source(&mut Counter(0)).unwrap();
+  let s = u128::from_le_bytes(array::from_fn(|i| i as u8)) | 1;
+  assert!(rng.state().get() == s);
+
+  let mut failing = |_: &mut [u8]| Err("no entropy");
+  assert!(Rng::from_entropy_source(&mut failing).err() == Some("no entropy"));
+
+  let mut zeros = |buf: &mut [u8]| -> Result<(), ()> { buf.fill(0); Ok(()) };
+  assert!(Rng::from_entropy_source(&mut zeros).unwrap().state().get() == 1);
+}
+
+#[cfg(feature = "thread_local")]
+#[test]
+fn test_thread_local_seed_from() {
+  std::thread::spawn(|| {
+    let mut ones = |buf: &mut [u8]| -> Result<(), ()> { buf.fill(1); Ok(()) };
+    dandelion::thread_local::seed_from(&mut ones).unwrap();
+    let mut rng = Rng::from_entropy_source(&mut ones).unwrap();
+    assert!(dandelion::thread_local::u64() == rng.u64());
+    assert!(dandelion::thread_local::seed_from(&mut |_: &mut [u8]| Err(())).is_err());
+    assert!(dandelion::thread_local::u64() == rng.u64());
+  }).join().unwrap();
+}
+
+// Checks every interleaving of concurrent draws and reseeding of the atomic
+// global generator. Run with
+//
+//   RUSTFLAGS="--cfg loom" cargo test --release --features atomic test_atomic_loom
+
+#[cfg(all(feature = "atomic", loom))]
+#[test]
+fn test_atomic_loom() {
+  loom::model(|| {
+    dandelion::atomic::seed(1);
+    let t = loom::thread::spawn(dandelion::atomic::u64);
+    let u = loom::thread::spawn(|| dandelion::atomic::seed(2));
+    let x = dandelion::atomic::u64();
+    let y = t.join().unwrap();
+    u.join().unwrap();
+    let z = dandelion::atomic::u64();
+
+    // The draws before the reseed take a prefix of the first stream, and the
+    // draws after it take a prefix of the second stream, without repeats.
+
+    let before = [x, y].iter().filter(|&&w| w == splitmix64(1, 1) || w == splitmix64(1, 2)).count();
+    let after = [x, y].iter().filter(|&&w| w == splitmix64(2, 1) || w == splitmix64(2, 2)).count();
+    assert!(x != y && before + after == 2);
+    assert!(z == splitmix64(2, after as u64 + 1));
+  });
+
+  loom::model(|| {
+    dandelion::atomic::seed(1);
+    let t = loom::thread::spawn(|| dandelion::atomic::bits(40));
+    let x = dandelion::atomic::bits(40);
+    let y = t.join().unwrap();
+
+    // Each bit is handed out once. The caller whose refill lands first takes
+    // 40 bits of its draw and banks the rest, and the other caller takes the
+    // banked bits and 16 bits of its own draw.
+
+    let w = [splitmix64(1, 1), splitmix64(1, 2)];
+    let split = |p: u64, q: u64| (p & (1 << 40) - 1, p >> 40 | (q & (1 << 16) - 1) << 24);
+    let is = |(l, h)| (x, y) == (l, h) || (x, y) == (h, l);
+    assert!(is(split(w[0], w[1])) || is(split(w[1], w[0])));
+  });
+}
+
+#[cfg(feature = "thread_local")]
+#[test]
+fn test_thread_local_bits() {
+  std::thread::spawn(|| {
+    dandelion::thread_local::seed(7);
+    let _ = dandelion::thread_local::bits(5);
+    dandelion::thread_local::seed(7);
+    let mut rng = Rng::from_u64(7);
+    let x = (0 .. 64).fold(0, |x, i| x | dandelion::thread_local::bits(1) << i);
+    assert!(x == rng.u64());
+    let y = dandelion::thread_local::bits(3) | dandelion::thread_local::bits(64) << 3;
+    assert!(y == rng.u64());
+    assert!(dandelion::thread_local::bits(0) == 0);
+  }).join().unwrap();
+}
+
+#[cfg(all(feature = "log", feature = "getrandom"))]
+#[test]
+fn test_log_seed() {
+  use std::sync::Mutex;
+
+  static RECORDS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+  struct Logger;
+
+  impl log::Log for Logger {
+    fn enabled(&self, _: &log::Metadata<'_>) -> bool {
+      true
+    }
+
+    fn log(&self, record: &log::Record<'_>) {
+      if record.target() == "dandelion" {
+        RECORDS.lock().unwrap().push(record.args().to_string());
+      }
+    }
+
+    fn flush(&self) {
+    }
+  }
+
+  log::set_logger(&Logger).unwrap();
+  log::set_max_level(log::LevelFilter::Debug);
+
+  let rng = Rng::from_operating_system();
+  let expected = format!("seeded from operating system: state = {:#034x}", rng.state());
+  assert!(RECORDS.lock().unwrap().contains(&expected));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_tape() {
+  // A recorded run draws the same values as the inner generator.
+
+  let mut plain = Rng::from_u64(0);
+  let mut rng = Rng::from_u64(0).recording();
+  let mut xs = Vec::new();
+  for _ in 0 .. 100 {
+    xs.push((rng.u64(), rng.bounded_u64(9), rng.between_u64(5, 10), rng.f64(), rng.bool(), rng.i32(), rng.bounded_u32(3)));
+    assert!(xs.last().unwrap() == &(plain.u64(), plain.bounded_u64(9), plain.between_u64(5, 10), plain.f64(), plain.bool(), plain.i32(), plain.bounded_u32(3)));
+  }
+  assert!(rng.tape().len() == 100 * 10);
+
+  // A replayed run sees the same values.
+
+  let mut rng = Rng::from_tape(rng.into_tape());
+  for x in xs {
+    assert!(x == (rng.u64(), rng.bounded_u64(9), rng.between_u64(5, 10), rng.f64(), rng.bool(), rng.i32(), rng.bounded_u32(3)));
+  }
+  assert!(rng.position() == 1000 && rng.remaining() == 0);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+#[should_panic]
+fn test_tape_exhausted() {
+  let mut rng = Rng::from_tape(vec![1, 2]);
+  let _ = rng.bounded_u64(10);
+  let _ = rng.u64();
+}
+
+#[test]
+fn test_counting() {
+  let mut plain = Rng::from_u64(0);
+  let mut rng = Rng::from_u64(0).counting();
+  assert!(rng.outputs_drawn() == 0);
+
+  assert!(rng.u64() == plain.u64());
+  assert!(rng.bounded_u32(6) == plain.bounded_u32(6));
+  assert!(rng.f64() == plain.f64());
+  assert!(rng.bool() == plain.bool());
+  assert!(rng.between_u64(3, 4) == plain.between_u64(3, 4));
+  assert!(rng.outputs_drawn() == 7);
+
+  rng.reset_count();
+  assert!(rng.outputs_drawn() == 0);
+  assert!(rng.into_inner().u64() == plain.u64());
+}
+
+#[test]
+fn test_bernoulli_u32() {
+  let mut rng = Rng::from_u64(0);
+
+  assert!((0 .. 1000).all(|_| ! rng.bernoulli_u32(0)));
+  assert!((0 .. 1000).all(|_| rng.bernoulli_u32(u32::MAX)));
+
+  // Each sample consumes one output.
+
+  let mut a = rng.clone();
+  let _ = rng.bernoulli_u32(12345);
+  let _ = a.u64();
+  assert!(rng.u64() == a.u64());
+
+  let n = 100_000;
+  let k = (0 .. n).filter(|_| rng.bernoulli_u32(0x4000_0000)).count();
+  assert!((24_000 ..= 26_000).contains(&k));
+}
+
+#[test]
+fn test_sample_until() {
+  let mut rng = Rng::from_u64(0);
+
+  for _ in 0 .. 1000 {
+    let x = rng.sample_until(|rng| rng.bounded_u32(9), |&x| x >= 7);
+    assert!((7 ..= 9).contains(&x));
+  }
+
+  // A rejected sample consumes its draws.
+
+  let mut a = rng.clone();
+  let mut tries = 0;
+  let x = rng.sample_until(|rng| { tries += 1; rng.u64() }, |&x| x < 1 << 62);
+  let y = (0 .. tries).map(|_| a.u64()).last().unwrap();
+  assert!(x == y);
+
+  let mut calls = 0;
+  assert!(rng.try_sample_until(|rng| { calls += 1; rng.u64() }, |_| false, 5).is_none());
+  assert!(calls == 5);
+  assert!(rng.try_sample_until(|rng| rng.u64(), |_| true, 0).is_none());
+  assert!(rng.try_sample_until(|rng| rng.u64() % 2, |&x| x == 1, 100) == Some(1));
+}
+
+#[test]
+fn test_bounded_with_budget() {
+  let mut rng = Rng::from_u64(0);
+
+  assert!(rng.bounded_u32_with_budget(5, 0).is_none());
+  assert!(rng.bounded_u64_with_budget(u64::MAX, 0).is_none());
+  assert!(rng.bounded_u32_with_budget(0, 1) == Some(0));
+  assert!(rng.bounded_u64_with_budget(0, 1) == Some(0));
+
+  let mut a = rng.clone();
+  assert!(rng.bounded_u64_with_budget(u64::MAX, 1) == Some(a.u64()));
+  assert!(rng.bounded_u32_with_budget(u32::MAX, 1) == Some(a.u32()));
+
+  // With n = 2³¹, just under half of the draws are rejected, so small budgets
+  // frequently miss.
+
+  let n = 100_000;
+  let misses = (0 .. n).filter(|_| rng.bounded_u32_with_budget(1 << 31, 1).is_none()).count();
+  assert!((49_000 ..= 51_000).contains(&misses));
+  let misses = (0 .. n).filter(|_| rng.bounded_u64_with_budget(1 << 63, 2).is_none()).count();
+  assert!((24_000 ..= 26_000).contains(&misses));
+
+  // The accepted samples are uniform.
+
+  let mut counts = [0u32; 7];
+  for _ in 0 .. 70_000 {
+    let x = rng.bounded_u32_with_budget(6, 8).unwrap();
+    counts[x as usize] += 1;
+    let y = rng.bounded_u64_with_budget(6, 8).unwrap();
+    counts[y as usize] += 1;
+  }
+  assert!(counts.iter().all(|&c| (19_000 ..= 21_000).contains(&c)));
+
+  // Rejection sampling is exact. With m = 3 · 2⁶², the first 2⁶² values of the
+  // low half of the product are rejected.
+
+  let m = 3u128 << 62;
+  let mut a = rng.clone();
+  for _ in 0 .. 1000 {
+    let x = a.u64();
+    let lo = (x as u128 * m) as u64;
+    let expected = if lo < 1 << 62 { None } else { Some((x as u128 * m >> 64) as u64) };
+    assert!(rng.bounded_u64_with_budget((m - 1) as u64, 1) == expected);
+  }
+}
+
+#[cfg(feature = "thread_local")]
+#[test]
+fn test_compat_fastrand() {
+  use dandelion::compat::fastrand;
+
+  // Seeding makes the thread-local generator deterministic.
+
+  fastrand::seed(7);
+  let x = fastrand::u64(..);
+  fastrand::seed(7);
+  assert!(fastrand::u64(..) == x);
+
+  for _ in 0 .. 1000 {
+    assert!((1 ..= 6).contains(&fastrand::u32(1 ..= 6)));
+    assert!(fastrand::usize(.. 10) < 10);
+    assert!((-3 .. 3).contains(&fastrand::i32(-3 .. 3)));
+    assert!((-128 ..= -127).contains(&fastrand::i8(..= -127)));
+    assert!(fastrand::u8(255 ..) == 255);
+    assert!((i64::MAX - 1 ..= i64::MAX).contains(&fastrand::i64(i64::MAX - 1 ..)));
+    assert!((0.0 .. 1.0).contains(&fastrand::f64()));
+    assert!((0.0 .. 1.0).contains(&fastrand::f32()));
+    assert!((u128::MAX - 1 ..= u128::MAX).contains(&fastrand::u128(u128::MAX - 1 ..)));
+    assert!((-3 .. 3).contains(&fastrand::i128(-3 .. 3)));
+    assert!(['\u{d7ff}', '\u{e000}'].contains(&fastrand::char((Bound::Excluded('\u{d7fe}'), Bound::Excluded('\u{e001}')))));
+    assert!(fastrand::alphanumeric().is_ascii_alphanumeric());
+    assert!(fastrand::lowercase().is_ascii_lowercase());
+    assert!(fastrand::uppercase().is_ascii_uppercase());
+    assert!(fastrand::digit(16).is_ascii_hexdigit() && ! fastrand::digit(16).is_ascii_uppercase());
+  }
+
+  let mut seen = [false; 256];
+  for _ in 0 .. 10_000 {
+    seen[(fastrand::i16(-128 .. 128) + 128) as usize] = true;
+  }
+  assert!(seen.iter().all(|&b| b));
+
+  let mut v: Vec<u32> = (0 .. 100).collect();
+  fastrand::shuffle(&mut v);
+  let mut w = v.clone();
+  w.sort();
+  assert!(w == (0 .. 100).collect::<Vec<_>>() && v != w);
+
+  assert!(fastrand::choice(Vec::<u8>::new()).is_none());
+  assert!(fastrand::choice([5]) == Some(5));
+  assert!((0 .. 10).contains(&fastrand::choice(0 .. 10).unwrap()));
+
+  let mut buf = [0u8; 64];
+  fastrand::fill(&mut buf);
+  assert!(buf.iter().any(|&b| b != 0));
+  let _ = fastrand::bool();
+  let _ = fastrand::u16(..);
+  let _ = fastrand::isize(..);
+  let _ = fastrand::char(..);
+}
+
+#[cfg(feature = "thread_local")]
+#[test]
+#[should_panic]
+fn test_compat_fastrand_empty_range() {
+  let _ = dandelion::compat::fastrand::u32(5 .. 5);
+}
+
+#[cfg(feature = "thread_local")]
+#[test]
+#[should_panic]
+fn test_compat_fastrand_digit_base_too_large() {
+  let _ = dandelion::compat::fastrand::digit(37);
+}
+
+#[test]
+fn test_core() {
+  use dandelion::Core;
+
+  // A custom core gets the full method surface.
+
+  #[derive(Clone)]
+  struct Weyl(u64);
+
+  impl Core for Weyl {
+    fn next_u64(&mut self) -> u64 {
+      self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+      self.0
+    }
+  }
+
+  let mut rng = Rng::from_core(Weyl(0));
+  assert!(rng.u64() == 0x9e37_79b9_7f4a_7c15);
+  assert!(rng.core().0 == 0x9e37_79b9_7f4a_7c15);
+  assert!((1 ..= 6).contains(&rng.between_u32(1, 6)));
+  let (x, y) = rng.in_unit_disk();
+  assert!(x * x + y * y <= 1.0);
+  let _ = rng.random_walk_1d(1.0).take(10).count();
+  let _ = rng.option(0.5, |rng| rng.ipv4());
+  let mut buf = [0u8; 8];
+  rng.bytes(&mut buf);
+
+  // Distributions and `FromRng` work with any core.
+
+  use dandelion::distribution::Distribution;
+  use dandelion::distribution::Uniform;
+  use dandelion::weighted::WeightedIndex;
+
+  assert!((1 ..= 6).contains(&rng.sample(&Uniform::new(1, 6))));
+  let _ = rng.random::<(u32, [bool; 2])>();
+  let mut weights = [1.0, 0.0, 2.0];
+  let w = WeightedIndex::from_buffer(&mut weights[..]).unwrap();
+  assert!(rng.sample(&w.map(|i| i != 1)));
+  let mut xoshiro = Rng::from_core(dandelion::xoshiro::Xoshiro256StarStar::from_u64(0));
+  assert!((1 ..= 6).contains(&xoshiro.sample(&Uniform::new(1, 6))));
+  let _ = xoshiro.random::<u32>();
+
+  // The default core produces the same stream through either interface.
+
+  let mut a = Rng::from_u64(0);
+  let mut core: Dandelion = a.clone().into_core();
+  for _ in 0 .. 100 {
+    assert!(a.u64() == core.next_u64());
+  }
+}
+
+#[test]
+fn test_xoshiro() {
+  use dandelion::Core;
+  use dandelion::xoshiro::Xoshiro256StarStar;
+
+  // Reference outputs for the state [1, 2, 3, 4].
+
+  let mut core = Xoshiro256StarStar::from_state([1, 2, 3, 4]).unwrap();
+  assert!(core.next_u64() == 11520);
+  assert!(core.next_u64() == 0);
+  assert!(core.next_u64() == 1509978240);
+  assert!(core.next_u64() == 1215971899390074240);
+
+  assert!(Xoshiro256StarStar::from_state([0; 4]).is_none());
+
+  let mut rng = Rng::from_core(Xoshiro256StarStar::from_u64(0));
+  let mut jumped = rng.clone();
+  jumped.core_mut().jump();
+  assert!(rng.core().state() != jumped.core().state());
+  assert!(rng.u64() != jumped.u64());
+  assert!(rng.bounded_u64(10) <= 10);
+  let _ = rng.f64();
+}
+
+// Without the `alloc` feature, the crate must not allocate. A counting
+// allocator checks this for the thread running the test, because the test
+// harness allocates on other threads.
+
+#[cfg(not(feature = "alloc"))]
+mod no_alloc {
+  use std::alloc::GlobalAlloc;
+  use std::alloc::Layout;
+  use std::alloc::System;
+  use std::cell::Cell;
+  use dandelion::Rng;
+
+  struct CountingAllocator;
+
+  std::thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+  }
+
+  unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+      let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+      unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+      unsafe { System.dealloc(ptr, layout) }
+    }
+  }
+
+  #[global_allocator]
+  static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+  #[test]
+  fn test_no_alloc() {
+    let before = ALLOCATIONS.with(Cell::get);
+    let mut rng = Rng::from_u64(0);
+    let _ = rng.u64();
+    let _ = rng.bounded_u64(5);
+    let _ = rng.between_i64(-5, 5);
+    let _ = rng.f64();
+    let _ = rng.char();
+    let _ = rng.random::<[u32; 4]>();
+    let _ = rng.split();
+    let mut buf = [0u8; 100];
+    rng.bytes(&mut buf);
+    rng.fill_ascii(&mut buf, b"abc");
+    rng.shuffle(&mut buf);
+    let mut counting = rng.clone().counting();
+    let _ = counting.u64();
+    let _ = rng.random_walk_1d(1.0).take(10).count();
+    let after = ALLOCATIONS.with(Cell::get);
+    assert!(before == after);
+  }
+}
+
+#[test]
+fn test_const() {
+
+  const SAMPLES: (u64, u32, i64, [u8; 13]) = {
+    let core = Dandelion::from_u64(7);
+    let (core, a) = core.bounded_u64(1000);
+    let (core, b) = core.between_u32(10, 20);
+    let (core, c) = core.between_i64(-5, 5);
+    let (_, d) = core.byte_array::<13>();
+    (a, b, c, d)
+  };
+
+  let mut rng = Rng::from_u64(7);
+  assert!(SAMPLES.0 == rng.bounded_u64(1000));
+  assert!(SAMPLES.1 == rng.between_u32(10, 20));
+  assert!(SAMPLES.2 == rng.between_i64(-5, 5));
+  assert!(SAMPLES.3 == rng.byte_array::<13>());
+
+  let mut rng = Rng::from_u64(8);
+  let mut core = Dandelion::from_u64(8);
+  for _ in 0 .. 10 {
+    let (next, x) = core.byte_array::<40>();
+    assert!(x == rng.byte_array::<40>());
+    core = next;
+  }
+  let (core, x) = core.between_i32(-3, 3);
+  assert!(x == rng.between_i32(-3, 3));
+  let (core, x) = core.bounded_u32(9);
+  assert!(x == rng.bounded_u32(9));
+  let (core, x) = core.u64();
+  assert!(x == rng.u64());
+  assert!(core.state() == rng.state());
+  assert!(Dandelion::from_state(core.state()).state() == rng.state());
+}
+
+#[cfg(all(feature = "debug_diagnostics", debug_assertions))]
+#[test]
+fn test_debug_diagnostics() {
+  let _ = Rng::from_state(Rng::from_u64(0).state());
+  let _ = Rng::from_state(Rng::new([0; 15]).state());
+
+  for s in [1, 0xffff, u128::MAX, u64::MAX as u128, 1 << 64 | 1, !0 << 8] {
+    let s = NonZeroU128::new(s).unwrap();
+    assert!(std::panic::catch_unwind(|| Rng::from_state(s)).is_err());
+    let _ = Rng::from_core(Dandelion::from_state(s));
+  }
+}
+
+#[cfg(feature = "getrandom")]
+#[test]
+fn test_os_rng() {
+  use dandelion::Core;
+  use dandelion::OsRng;
+  use dandelion::entropy::OsEntropy;
+
+  fn roll<C: Core>(rng: &mut Rng<C>) -> u32 {
+    rng.between_u32(1, 6)
+  }
+
+  let mut rng = OsRng::from_core(OsEntropy);
+  assert!((1 ..= 6).contains(&roll(&mut rng)));
+  assert!((1 ..= 6).contains(&roll(&mut Rng::from_u64(0))));
+  assert!(rng.u64() != rng.u64() || rng.u64() != rng.u64());
+  let _ = rng.bounded_u64(5);
+  let _ = rng.f64();
+  let _ = rng.byte_array::<33>();
+}
+
+#[test]
+fn test_fault_plan() {
+  use dandelion::fault::FaultPlan;
+
+  let plan = FaultPlan::new(42, 0.25);
+  assert!(plan.seed() == 42 && plan.rate() == 0.25);
+
+  let failures: Vec<u64> = plan.failures(0 .. 10_000).collect();
+  assert!((2300 ..= 2700).contains(&failures.len()));
+  for site in 0 .. 10_000 {
+    assert!(plan.should_fail(site) == failures.contains(&site));
+  }
+
+  // The schedule is reproducible from the seed, and differs between seeds.
+
+  assert!(FaultPlan::new(42, 0.25).failures(0 .. 10_000).eq(failures.iter().copied()));
+  assert!(! FaultPlan::new(43, 0.25).failures(0 .. 10_000).eq(failures.iter().copied()));
+
+  // Plans with different seeds are not relabelings of each other.
+
+  let d = dandelion::mix::mix64(42) ^ dandelion::mix::mix64(43);
+  let other = FaultPlan::new(43, 0.25);
+  assert!((0 .. 1000).any(|site| plan.should_fail(site) != other.should_fail(site ^ d)));
+
+  assert!(FaultPlan::new(42, 0.0).failures(0 .. 1000).count() == 0);
+  assert!(FaultPlan::new(42, 1.0).failures(0 .. 1000).count() == 1000);
+}
+
+#[test]
+fn test_bit_permutation() {
+  use dandelion::bit_permutation::BitPermutation;
+
+  let mut rng = Rng::from_u64(0);
+
+  // Each bit is equally likely to end up in each position.
+
+  let mut counts = [[0u32; 64]; 4];
+  for _ in 0 .. 6400 {
+    let p = rng.random_bit_permutation();
+    assert!(BitPermutation::from_array(*p.as_array()) == Some(p));
+    for (i, count) in counts.iter_mut().enumerate() {
+      count[p.apply(1 << i).trailing_zeros() as usize] += 1;
+    }
+    let x = rng.u64();
+    assert!(p.apply(x).count_ones() == x.count_ones());
+    assert!(p.inverse().apply(p.apply(x)) == x);
+  }
+  assert!(counts.iter().flatten().all(|&n| (50 ..= 150).contains(&n)));
+
+  assert!(BitPermutation::default().apply(12345) == 12345);
+  assert!(BitPermutation::from_array([0; 64]).is_none());
+  let mut map = *BitPermutation::IDENTITY.as_array();
+  map[63] = 64;
+  assert!(BitPermutation::from_array(map).is_none());
+
+  for x in [0, 1, 0xff, 0xffff_ffff, !0xff, u64::MAX] {
+    let mut seen = 0u64;
+    for _ in 0 .. 1000 {
+      let y = rng.shuffle_bits_u64(x);
+      assert!(y.count_ones() == x.count_ones());
+      seen |= y;
+    }
+    assert!(seen == if x == 0 { 0 } else { u64::MAX });
+  }
+
+  let mut counts = [0u32; 64];
+  for _ in 0 .. 6400 {
+    counts[rng.shuffle_bits_u64(1 << 5).trailing_zeros() as usize] += 1;
+    let y = rng.shuffle_bits_u64(!(1 << 5));
+    counts[(!y).trailing_zeros() as usize] += 1;
+  }
+  assert!(counts.iter().all(|&n| (130 ..= 270).contains(&n)));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_antithetic() {
+  use dandelion::antithetic::AntitheticRng;
+
+  let mut rng = AntitheticRng::new(Rng::from_u64(0));
+
+  // The hands stay in sync however the calls are interleaved.
+
+  let xs: Vec<f64> = (0 .. 100).map(|_| rng.primary().f64()).collect();
+  assert!(rng.lag() == 100);
+  let ys: Vec<f64> = (0 .. 150).map(|_| rng.mirror().f64()).collect();
+  assert!(rng.lag() == 50);
+  let zs: Vec<f64> = (0 .. 50).map(|_| rng.primary().f64()).collect();
+  assert!(rng.lag() == 0);
+  for (x, y) in xs.iter().chain(&zs).zip(&ys) {
+    assert!((x + y - 1.0).abs() < 1e-15);
+  }
+
+  // Antithetic pairs reduce the variance of the mean of a monotone function.
+
+  let n = 10_000;
+  let mut sum = 0.0;
+  let mut sum_sq = 0.0;
+  for _ in 0 .. n {
+    let a = rng.primary().f64().powi(2);
+    let b = rng.mirror().f64().powi(2);
+    let m = (a + b) / 2.0;
+    sum += m;
+    sum_sq += m * m;
+  }
+  let mean = sum / n as f64;
+  let var = sum_sq / n as f64 - mean * mean;
+  assert!((mean - 1.0 / 3.0).abs() < 0.005);
+  assert!(var < 0.01);
+
+  // In common random numbers mode, both hands see the same samples.
+
+  let mut rng = AntitheticRng::common_random_numbers(Rng::from_u64(0));
+  let mut reference = Rng::from_u64(0);
+  let mut primary = rng.primary();
+  let a = primary.bounded_u64(100);
+  let b = primary.f64();
+  assert!(a == reference.bounded_u64(100) && b == reference.f64());
+  let mut mirror = rng.mirror();
+  assert!(mirror.bounded_u64(100) == a && mirror.f64() == b);
+}
+
+#[test]
+fn test_u128() {
+  let mut a = Rng::from_u64(0);
+  let mut b = a.clone();
+
+  for _ in 0 .. 100 {
+    let x = b.u64() as u128;
+    let y = b.u64() as u128;
+    assert!(a.u128() == x | y << 64);
+    let x = b.u64() as u128;
+    let y = b.u64() as u128;
+    assert!(a.i128() == (x | y << 64) as i128);
+  }
+
+  let mut ones = 0;
+  for _ in 0 .. 1000 { ones += a.u128().count_ones(); }
+  assert!((63_000 ..= 65_000).contains(&ones));
+}
+
+#[test]
+fn test_bounded_u128() {
+  let mut rng = Rng::from_u64(0);
+
+  let mut counts = [0u32; 7];
+  for _ in 0 .. 70_000 { counts[rng.bounded_u128(6) as usize] += 1; }
+  assert!(counts.iter().all(|&n| (9_500 ..= 10_500).contains(&n)));
+
+  assert!(rng.bounded_u128(0) == 0);
+
+  // With a bound of 2¹²⁸ - 1, both halves of the result are uniform.
+
+  let xs: Vec<f64> = (0 .. 10_000).map(|_| rng.bounded_u128(u128::MAX) as u64 as f64 / u64::MAX as f64).collect();
+  let ys: Vec<f64> = (0 .. 10_000).map(|_| (rng.bounded_u128(u128::MAX) >> 64) as f64 / u64::MAX as f64).collect();
+  assert!(stats::ks_uniform(&xs) < stats::ks_critical_value(10_000));
+  assert!(stats::ks_uniform(&ys) < stats::ks_critical_value(10_000));
+
+  // A bound just above 2⁶⁴ exercises the carry between the limbs.
+
+  let n = 1u128 << 64 | 1;
+  let xs: Vec<f64> = (0 .. 10_000).map(|_| rng.bounded_u128(n) as f64 / n as f64).collect();
+  assert!(stats::ks_uniform(&xs) < stats::ks_critical_value(10_000));
+
+  for _ in 0 .. 1000 {
+    let x = rng.between_u128(u128::MAX - 2, 1);
+    assert!(x >= u128::MAX - 2 || x <= 1);
+    let x = rng.between_i128(-3, 3);
+    assert!((-3 ..= 3).contains(&x));
+    let x = rng.between_i128(i128::MAX, i128::MIN);
+    assert!(x == i128::MAX || x == i128::MIN);
+  }
+}
+
+#[test]
+fn test_usize() {
+  let mut a = Rng::from_u64(0);
+  let mut b = a.clone();
+
+  // On 64-bit targets these are the same as the `u64` methods.
+
+  #[cfg(target_pointer_width = "64")]
+  for _ in 0 .. 100 {
+    assert!(a.usize() as u64 == b.u64() as usize as u64);
+    assert!(a.isize() as i64 == b.u64() as isize as i64);
+    assert!(a.bounded_usize(1000) as u64 == b.bounded_u64(1000));
+    assert!(a.between_usize(10, 20) as u64 == b.between_u64(10, 20));
+  }
+
+  let mut counts = [0u32; 5];
+  for _ in 0 .. 50_000 {
+    counts[a.bounded_usize(4)] += 1;
+    let x = a.between_isize(-2, 2);
+    assert!((-2 ..= 2).contains(&x));
+    let x = a.between_usize(usize::MAX, 0);
+    assert!(x == usize::MAX || x == 0);
+  }
+  assert!(counts.iter().all(|&n| (9_500 ..= 10_500).contains(&n)));
+}
+
+#[test]
+fn test_index() {
+  let mut rng = Rng::from_u64(0);
+
+  let mut counts = [0u32; 3];
+  for _ in 0 .. 30_000 { counts[rng.index(3)] += 1; }
+  assert!(counts.iter().all(|&n| (9_500 ..= 10_500).contains(&n)));
+
+  for _ in 0 .. 100 {
+    assert!(rng.index(1) == 0);
+    assert!(rng.index(usize::MAX) < usize::MAX);
+  }
+}
+
+#[test]
+#[should_panic]
+fn test_index_empty() {
+  let _ = Rng::from_u64(0).index(0);
+}
+
+#[test]
+fn test_small_ints() {
+  let mut a = Rng::from_u64(0);
+  let mut b = a.clone();
+
+  for _ in 0 .. 100 {
+    assert!(a.u8() == b.u64() as u8);
+    assert!(a.u16() == b.u64() as u16);
+    assert!(a.i8() == b.u64() as i8);
+    assert!(a.i16() == b.u64() as i16);
+  }
+
+  let mut counts = [0u32; 7];
+  for _ in 0 .. 70_000 {
+    counts[a.bounded_u8(6) as usize] += 1;
+    let x = a.between_i8(-3, 3);
+    assert!((-3 ..= 3).contains(&x));
+    let x = a.between_i16(i16::MAX, i16::MIN);
+    assert!(x == i16::MAX || x == i16::MIN);
+    let x = a.between_u16(1000, 1006);
+    assert!((1000 ..= 1006).contains(&x));
+    let x = a.between_u8(250, 5);
+    assert!(x >= 250 || x <= 5);
+  }
+  assert!(counts.iter().all(|&n| (9_500 ..= 10_500).contains(&n)));
+
+  let mut seen = [false; 256];
+  for _ in 0 .. 10_000 { seen[a.bounded_u8(255) as usize] = true; }
+  assert!(seen.iter().all(|&x| x));
+  assert!(a.bounded_u16(0) == 0);
+}
+
+#[test]
+fn test_below() {
+  let mut rng = Rng::from_u64(0);
+  let mut counts = [0u32; 4];
+
+  for _ in 0 .. 40_000 {
+    counts[rng.below_u32(4) as usize] += 1;
+    assert!(rng.below_u64(3) < 3);
+    assert!(rng.below_u32(1) == 0);
+    assert!(rng.below_u64(u64::MAX) < u64::MAX);
+  }
+
+  assert!(counts.iter().all(|&n| (9_500 ..= 10_500).contains(&n)));
+}
+
+#[test]
+#[should_panic]
+fn test_below_zero() {
+  let _ = Rng::from_u64(0).below_u64(0);
+}
+
+#[test]
+fn test_range() {
+  let mut rng = Rng::from_u64(0);
+  let mut counts = [0u32; 6];
+
+  for _ in 0 .. 60_000 {
+    counts[rng.range(1 ..= 6) as usize - 1] += 1;
+    assert!(rng.range(0 .. 6usize) < 6);
+    assert!((-3 .. 3).contains(&rng.range(-3i8 .. 3)));
+    assert!(rng.range(250u8 ..) >= 250);
+    assert!(rng.range(..= i64::MIN + 1) <= i64::MIN + 1);
+    assert!(rng.range(.. 1u128) == 0);
+    assert!(rng.range((Bound::Excluded(5u16), Bound::Included(6))) == 6);
+  }
+
+  assert!(counts.iter().all(|&n| (9_500 ..= 10_500).contains(&n)));
+
+  let _: u64 = rng.range(..);
+  let _: i32 = rng.range(i32::MIN ..= i32::MAX);
+}
+
+#[test]
+#[should_panic]
+fn test_range_empty() {
+  let _ = Rng::from_u64(0).range(3 .. 3);
+}
+
+#[test]
+#[should_panic]
+fn test_range_empty_exclusive_min() {
+  let _ = Rng::from_u64(0).range(.. u8::MIN);
+}
+
+#[test]
+fn test_bounded_exact() {
+  let mut rng = Rng::from_u64(0);
+
+  assert!(rng.bounded_u32_exact(0) == 0);
+  assert!(rng.bounded_u64_exact(0) == 0);
+
+  let mut a = rng.clone();
+  assert!(rng.bounded_u64_exact(u64::MAX) == a.u64());
+  assert!(rng.bounded_u32_exact(u32::MAX) == a.u32());
+
+  // The exact samplers consume the same draws as the budgeted ones when the
+  // budget is never exhausted.
+
+  for n in [1, 6, 1 << 31, (1 << 31) + 1, u32::MAX - 1] {
+    for _ in 0 .. 1_000 {
+      let mut a = rng.clone();
+      assert!(Some(rng.bounded_u32_exact(n)) == a.bounded_u32_with_budget(n, u32::MAX));
+      assert!(rng.u64() == a.u64());
+      let n = n as u64 * 3 << 30;
+      assert!(Some(rng.bounded_u64_exact(n)) == a.bounded_u64_with_budget(n, u32::MAX));
+      assert!(rng.u64() == a.u64());
+    }
+  }
+
+  let mut counts = [0u32; 7];
+  for _ in 0 .. 70_000 {
+    counts[rng.bounded_u32_exact(6) as usize] += 1;
+    counts[rng.bounded_u64_exact(6) as usize] += 1;
+  }
+  assert!(counts.iter().all(|&c| (19_000 ..= 21_000).contains(&c)));
+}
+
+#[test]
+fn test_uniform() {
+  use dandelion::distribution::Uniform;
+
+  let mut rng = Rng::from_u64(0);
+
+  // The samples match the exact bounded sampler draw for draw.
+
+  for (lo, hi) in [(0, 0), (1, 6), (10, 1 << 63), (u64::MAX - 2, 3), (0, u64::MAX)] {
+    let d = Uniform::new(lo, hi);
+    let mut a = rng.clone();
+    for _ in 0 .. 1_000 {
+      assert!(rng.sample(&d) == lo.wrapping_add(a.bounded_u64_exact(hi.wrapping_sub(lo))));
+    }
+  }
+
+  let d = Uniform::below(7);
+  let mut counts = [0u32; 7];
+  for _ in 0 .. 70_000 { counts[rng.sample(&d) as usize] += 1; }
+  assert!(counts.iter().all(|&c| (9_500 ..= 10_500).contains(&c)));
+}
+
+#[test]
+#[should_panic]
+fn test_uniform_below_zero() {
+  let _ = dandelion::distribution::Uniform::below(0);
+}
+
+#[test]
+fn test_between_f64() {
+  let mut rng = Rng::from_u64(0);
+
+  let n = 10_000;
+  let xs: Vec<f64> = (0 .. n).map(|_| (rng.between_f64(-3.0, 5.0) + 3.0) / 8.0).collect();
+  assert!(stats::ks_uniform(&xs) < stats::ks_critical_value(n));
+
+  for _ in 0 .. 10_000 {
+    let x = rng.between_f64(1.0, 1.0 + f64::EPSILON);
+    assert!(x == 1.0 || x == 1.0 + f64::EPSILON);
+    let x = rng.between_f64(-f64::MAX, f64::MAX);
+    assert!(x.is_finite());
+    let x = rng.between_f32(-f32::MAX, f32::MAX);
+    assert!(x.is_finite());
+    let x = rng.between_f32(0.1, 0.3);
+    assert!((0.1 ..= 0.3).contains(&x));
+  }
+
+  assert!(rng.between_f64(2.5, 2.5) == 2.5);
+}
+
+#[test]
+#[should_panic]
+fn test_between_f64_nan() {
+  let _ = Rng::from_u64(0).between_f64(f64::NAN, 1.0);
+}
+
+#[test]
+#[should_panic]
+fn test_between_f32_reversed() {
+  let _ = Rng::from_u64(0).between_f32(1.0, 0.0);
+}
+
+#[test]
+fn test_f64_signed() {
+  let mut rng = Rng::from_u64(0);
+
+  let n = 10_000;
+  let xs: Vec<f64> = (0 .. n).map(|_| (rng.f64_signed() + 1.0) / 2.0).collect();
+  assert!(stats::ks_uniform(&xs) < stats::ks_critical_value(n));
+  let xs: Vec<f64> = (0 .. n).map(|_| (rng.f32_signed() as f64 + 1.0) / 2.0).collect();
+  assert!(stats::ks_uniform(&xs) < stats::ks_critical_value(n));
+
+  // The signed samples are the unsigned ones without the absolute value.
+
+  let mut a = rng.clone();
+  for _ in 0 .. 1_000 {
+    assert!(rng.f64_signed().abs() == a.f64());
+    assert!(rng.f32_signed().abs() == a.f32());
+  }
+
+
+  for _ in 0 .. 1_000 {
+    let mut a = rng.clone();
+    assert!((rng.f64_signed() < 0.0) == (a.i64() < 0));
+  }
+}
+
+#[test]
+fn test_fill_f64() {
+  let mut rng = Rng::from_u64(0);
+  let mut a = rng.clone();
+
+  let mut xs = [0.0; 100];
+  let mut ys = [0.0; 100];
+  rng.fill_f64(&mut xs);
+  rng.fill_f32(&mut ys);
+  assert!(xs.iter().all(|&x| x == a.f64()));
+  assert!(ys.iter().all(|&y| y == a.f32()));
+  assert!(rng.u64() == a.u64());
+
+  rng.fill_f64(&mut []);
+  assert!(rng.u64() == a.u64());
+}
+
+#[test]
+fn test_bernoulli_ratio() {
+  let mut rng = Rng::from_u64(0);
+
+  for _ in 0 .. 1_000 {
+    assert!(! rng.bernoulli_ratio(0, 1));
+    assert!(! rng.bernoulli_ratio(0, u64::MAX));
+    assert!(rng.bernoulli_ratio(1, 1));
+    assert!(rng.bernoulli_ratio(u64::MAX, u64::MAX));
+    assert!(rng.bernoulli_ratio(8, 7));
+  }
+
+  let n = 700_000;
+  let k = (0 .. n).filter(|_| rng.bernoulli_ratio(3, 7)).count();
+  assert!((299_000 ..= 301_000).contains(&k));
+
+  // The draws are the same as for `bounded_u64_exact`.
+
+  let mut a = rng.clone();
+  for _ in 0 .. 1_000 {
+    assert!(rng.bernoulli_ratio(5, 1 << 63 | 1) == (a.bounded_u64_exact(1 << 63) < 5));
+  }
+}
+
+#[test]
+#[should_panic]
+fn test_bernoulli_ratio_zero_denominator() {
+  let _ = Rng::from_u64(0).bernoulli_ratio(0, 0);
+}
+
+#[test]
+fn test_one_in() {
+  let mut rng = Rng::from_u64(0);
+
+  assert!((0 .. 1000).all(|_| rng.one_in(1)));
+
+  let n = 1_000_000;
+  let k = (0 .. n).filter(|_| rng.one_in(100)).count();
+  assert!((9_500 ..= 10_500).contains(&k));
+
+  let mut a = rng.clone();
+  for _ in 0 .. 1_000 {
+    assert!(rng.one_in(7) == a.bernoulli_ratio(1, 7));
+  }
+}
+
+#[test]
+#[should_panic]
+fn test_one_in_zero() {
+  let _ = Rng::from_u64(0).one_in(0);
+}
+
+#[test]
+fn test_bernoulli_mask64() {
+  let mut rng = Rng::from_u64(0);
+
+  for _ in 0 .. 1_000 {
+    assert!(rng.bernoulli_mask64(0.0) == 0);
+    assert!(rng.bernoulli_mask64(-1.0) == 0);
+    assert!(rng.bernoulli_mask64(f64::NAN) == 0);
+    assert!(rng.bernoulli_mask64(1.0) == u64::MAX);
+    assert!(rng.bernoulli_mask64(2.0) == u64::MAX);
+  }
+
+  // With `p = 1/2` and `p = 1/4` the mask is built from one and two draws.
+
+  let mut a = rng.clone();
+  for _ in 0 .. 1_000 {
+    assert!(rng.bernoulli_mask64(0.5) == ! a.u64());
+    let x = a.u64();
+    let y = a.u64();
+    assert!(rng.bernoulli_mask64(0.25) == ! x & ! y);
+  }
+
+  for p in [0.001, 0.3, 0.9, 0.999] {
+    let n = 20_000;
+    let mut counts = [0u32; 64];
+    for _ in 0 .. n {
+      let x = rng.bernoulli_mask64(p);
+      for (i, c) in counts.iter_mut().enumerate() { *c += (x >> i & 1) as u32; }
+    }
+    let sigma = (n as f64 * p * (1.0 - p)).sqrt();
+    assert!(counts.iter().all(|&c| (c as f64 - n as f64 * p).abs() < 5.0 * sigma));
+  }
+}
+
+#[test]
+fn test_mask_u64() {
+  let mut rng = Rng::from_u64(0);
+
+  assert!(rng.mask_u32(0) == u32::MAX);
+  assert!(rng.mask_u64(0) == u64::MAX);
+
+  let mut a = rng.clone();
+  for _ in 0 .. 1_000 {
+    assert!(rng.mask_u64(1) == a.u64());
+    assert!(rng.mask_u32(1) == a.u32());
+    assert!(rng.mask_u64(3) == a.u64() & a.u64() & a.u64());
+  }
+
+  assert!(rng.mask_u64(1_000_000) == 0);
+
+  for k in 1 .. 5 {
+    let n = 20_000;
+    let p = 0.5f64.powi(k as i32);
+    let ones = (0 .. n).map(|_| rng.mask_u64(k).count_ones() + rng.mask_u32(k).count_ones()).sum::<u32>();
+    let m = 96.0 * n as f64;
+    assert!((ones as f64 / m - p).abs() < 5.0 * (p * (1.0 - p) / m).sqrt());
+  }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_log_uniform() {
+  let mut rng = Rng::from_u64(0);
+
+  // Each decade is equally likely.
+
+  let mut counts = [0u32; 3];
+  for _ in 0 .. 30_000 {
+    let x = rng.log_uniform_u64(1, 999);
+    assert!((1 ..= 999).contains(&x));
+    counts[x.ilog10() as usize] += 1;
+  }
+  assert!(counts.iter().all(|&c| (9_500 ..= 10_500).contains(&c)));
+
+  // The probability of `k` is proportional to `ln((k + 1) / k)`.
+
+  let n = 100_000;
+  let ones = (0 .. n).filter(|_| rng.log_uniform_u64(1, 3) == 1).count();
+  let p = 2f64.ln() / 4f64.ln();
+  assert!((ones as f64 / n as f64 - p).abs() < 0.01);
+
+  let n = 10_000;
+  let xs: Vec<f64> = (0 .. n).map(|_| (rng.log_uniform_f64(1e-3, 1e3) / 1e-3).ln() / 1e6f64.ln()).collect();
+  assert!(stats::ks_uniform(&xs) < stats::ks_critical_value(n));
+
+  for _ in 0 .. 1_000 {
+    assert!(rng.log_uniform_u64(5, 5) == 5);
+    assert!(rng.log_uniform_u64(u64::MAX - 1, u64::MAX) >= u64::MAX - 1);
+    assert!(rng.log_uniform_u64(1, u64::MAX) >= 1);
+    assert!((1e-300 ..= f64::MAX).contains(&rng.log_uniform_f64(1e-300, f64::MAX)));
+  }
+}
+
+#[cfg(feature = "std")]
+#[test]
+#[should_panic]
+fn test_log_uniform_zero() {
+  let _ = Rng::from_u64(0).log_uniform_u64(0, 10);
+}
+
+#[test]
+fn test_bits() {
+  let mut rng = Rng::from_u64(0);
+  let mut a = rng.clone();
+
+  // The bits of each draw are handed out from the least significant, and the
+  // generator only draws again when the bank runs out.
+
+  assert!(rng.bits(0) == 0);
+  let x = a.u64();
+  let mut z = 0;
+  for i in 0 .. 64 { z |= rng.bits(1) << i; }
+  assert!(z == x);
+  let y = a.u64();
+  assert!(rng.bits(3) == y & 7);
+  assert!(rng.bits(60) == y >> 3 & (1 << 60) - 1);
+  let w = a.u64();
+  assert!(rng.bits(5) == (y >> 63 | (w & 15) << 1));
+  let v = a.u64();
+  assert!(rng.bits(64) == (w >> 4 | v << 60));
+
+  // The other methods draw from the core directly, and the state does not
+  // include the bank.
+
+  assert!(rng.u64() == a.u64());
+  let mut b = Rng::from_state(rng.state());
+  assert!(b.bits(64) == a.clone().u64());
+  assert!(rng.bits(60) == v >> 4);
+
+  let mut rng = Rng::from_u64(1);
+  let mut counts = [0u32; 8];
+  for _ in 0 .. 80_000 { counts[rng.bits(3) as usize] += 1; }
+  assert!(counts.iter().all(|&c| (9_500 ..= 10_500).contains(&c)));
+}
+
+#[test]
+#[should_panic]
+fn test_bits_too_many() {
+  let _ = Rng::from_u64(0).bits(65);
+}
+
+#[test]
+fn test_u64_with_popcount() {
+  let mut rng = Rng::from_u64(0);
+
+  assert!(rng.u64_with_popcount(0) == 0);
+  assert!(rng.u64_with_popcount(64) == u64::MAX);
+
+  for k in 0 ..= 64 {
+    for _ in 0 .. 100 {
+      assert!(rng.u64_with_popcount(k).count_ones() == k);
+    }
+  }
+
+  // Every one of the C(4, 2) = 6 words with two ones among the low four bits
+  // is equally likely, as is every position of the two ones overall.
+
+  let mut counts = [0u32; 16];
+  let mut positions = [0u32; 64];
+  let n = 100_000;
+  for _ in 0 .. n {
+    let x = rng.u64_with_popcount(2);
+    for (i, c) in positions.iter_mut().enumerate() { *c += (x >> i & 1) as u32; }
+    if x < 16 { counts[x as usize] += 1; }
+  }
+  let expected = n as f64 / 2016.0;
+  for x in [3, 5, 6, 9, 10, 12] {
+    assert!((counts[x] as f64 - expected).abs() < 5.0 * expected.sqrt());
+  }
+  assert!(positions.iter().all(|&c| (2_850 ..= 3_400).contains(&c)));
+}
+
+#[test]
+#[should_panic]
+fn test_u64_with_popcount_too_many() {
+  let _ = Rng::from_u64(0).u64_with_popcount(65);
+}
+
+#[test]
+fn test_char_from() {
+  let mut rng = Rng::from_u64(0);
+
+  assert!(rng.char_from("x") == 'x');
+  assert!(rng.char_from("é") == 'é');
+
+  let mut counts = [0u32; 4];
+  for _ in 0 .. 40_000 {
+    let c = rng.char_from("♠♥♦♣");
+    counts["♠♥♦♣".chars().position(|d| d == c).unwrap()] += 1;
+  }
+  assert!(counts.iter().all(|&c| (9_500 ..= 10_500).contains(&c)));
+
+  let mut counts = [0u32; 3];
+  for _ in 0 .. 30_000 {
+    counts[(rng.char_from("abc") as u8 - b'a') as usize] += 1;
+  }
+  assert!(counts.iter().all(|&c| (9_500 ..= 10_500).contains(&c)));
+
+  let n = 30_000;
+  let k = (0 .. n).filter(|_| rng.char_from("aab") == 'a').count();
+  assert!((19_500 ..= 20_500).contains(&k));
+}
+
+#[test]
+#[should_panic]
+fn test_char_from_empty() {
+  let _ = Rng::from_u64(0).char_from("");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_charset() {
+  use dandelion::Charset;
+
+  type Check = (Charset, fn(&u8) -> bool, usize);
+
+  let checks: [Check; 8] = [
+    (Charset::Alphanumeric, u8::is_ascii_alphanumeric, 62),
+    (Charset::Alphabetic, u8::is_ascii_alphabetic, 52),
+    (Charset::Lowercase, u8::is_ascii_lowercase, 26),
+    (Charset::Uppercase, u8::is_ascii_uppercase, 26),
+    (Charset::Digit, u8::is_ascii_digit, 10),
+    (Charset::LowerHex, |b| b.is_ascii_digit() || (b'a' ..= b'f').contains(b), 16),
+    (Charset::UpperHex, |b| b.is_ascii_digit() || (b'A' ..= b'F').contains(b), 16),
+    (Charset::Graphic, u8::is_ascii_graphic, 94),
+  ];
+
+  for (charset, pred, n) in checks {
+    let bytes = charset.as_bytes();
+    assert!(bytes.len() == n);
+    assert!(bytes.iter().all(pred));
+    assert!((0 ..= 127).filter(pred).count() == n);
+  }
+
+  let mut rng = Rng::from_u64(0);
+  let mut counts = [0u32; 128];
+  for _ in 0 .. 62_000 { counts[rng.ascii(Charset::Alphanumeric) as usize] += 1; }
+  assert!(counts.iter().all(|&c| c == 0 || (800 ..= 1_200).contains(&c)));
+  assert!(counts.iter().filter(|&&c| c != 0).count() == 62);
+
+  let mut a = rng.clone();
+  assert!(rng.alphanumeric() == a.ascii(Charset::Alphanumeric));
+
+  let s = rng.string(10_000, Charset::Graphic);
+  assert!(s.len() == 10_000 && s.bytes().all(|b| b.is_ascii_graphic()));
+  assert!(rng.string(0, Charset::Digit).is_empty());
+}
+
+#[test]
+fn test_fill_hex() {
+  let mut rng = Rng::from_u64(0);
+  let mut a = rng.clone();
+
+  let mut buf = [0u8; 20];
+  rng.fill_hex(&mut buf);
+  let x = a.u64();
+  let y = a.u64();
+  for (i, &c) in buf.iter().enumerate() {
+    let w = if i < 16 { x >> 4 * i } else { y >> 4 * (i - 16) };
+    assert!(c == b"0123456789abcdef"[(w & 15) as usize]);
+  }
+
+  let mut counts = [0u32; 16];
+  let mut buf = vec![0u8; 160_000];
+  rng.fill_hex(&mut buf);
+  for &c in &buf { counts[(c as char).to_digit(16).unwrap() as usize] += 1; }
+  assert!(counts.iter().all(|&c| (9_500 ..= 10_500).contains(&c)));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_token_base62() {
+  let mut rng = Rng::from_u64(0);
+
+  let s = rng.token_base62(62_000);
+  assert!(s.len() == 62_000 && s.bytes().all(|b| b.is_ascii_alphanumeric()));
+
+  let mut counts = [0u32; 128];
+  for b in s.bytes() { counts[b as usize] += 1; }
+  assert!(counts.iter().filter(|&&c| c != 0).all(|&c| (800 ..= 1_200).contains(&c)));
+  assert!(counts.iter().filter(|&&c| c != 0).count() == 62);
+  assert!(rng.token_base62(0).is_empty());
+}
+
+#[test]
+fn test_ip_in_subnet() {
+  use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+  let mut rng = Rng::from_u64(0);
+
+  let net = Ipv4Addr::new(192, 168, 7, 77);
+  let mut seen = [false; 16];
+  for _ in 0 .. 1_000 {
+    let ip = rng.ipv4_in_subnet(net, 28);
+    let [a, b, c, d] = ip.octets();
+    assert!([a, b, c] == [192, 168, 7] && d & 0xf0 == 0x40);
+    seen[(d & 15) as usize] = true;
+  }
+  assert!(seen.iter().all(|&x| x));
+
+  assert!(rng.ipv4_in_subnet(net, 32) == net);
+  let _ = rng.ipv4_in_subnet(net, 0);
+
+  let net: Ipv6Addr = "2001:db8:1234::".parse().unwrap();
+  for _ in 0 .. 1_000 {
+    let ip = rng.ipv6_in_subnet(net, 48);
+    assert!(ip.segments()[.. 3] == [0x2001, 0xdb8, 0x1234]);
+    assert!(rng.ipv6_in_subnet(net, 127).segments()[7] <= 1);
+  }
+  assert!(rng.ipv6_in_subnet(net, 128) == net);
+
+  assert!(rng.ip_in_subnet(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 0)), 8).is_ipv4());
+  assert!(rng.ip_in_subnet(IpAddr::V6(net), 64).is_ipv6());
+
+  let n = 10_000;
+  let v4 = (0 .. n).filter(|_| matches!(rng.socket_addr(80 ..= 80), SocketAddr::V4(s) if s.port() == 80)).count();
+  assert!((4_750 ..= 5_250).contains(&v4));
+  assert!((0 .. 1_000).all(|_| (8080 ..= 8082).contains(&rng.socket_addr_v6(8080 ..= 8082).port())));
+}
+
+#[test]
+#[should_panic]
+fn test_ipv4_in_subnet_long_prefix() {
+  let _ = Rng::from_u64(0).ipv4_in_subnet(std::net::Ipv4Addr::LOCALHOST, 33);
+}
+
+#[test]
+fn test_mac_address_with_flags() {
+  let mut rng = Rng::from_u64(0);
+
+  let mut a = rng.clone();
+  for _ in 0 .. 1_000 {
+    assert!(rng.mac_address() == a.mac_address_with_flags(true, false));
+  }
+
+  for (local, multicast) in [(false, false), (false, true), (true, false), (true, true)] {
+    let flags = (local as u8) << 1 | multicast as u8;
+    let mut or = [0u8; 8];
+    let mut and = [0xffu8; 8];
+    for _ in 0 .. 1_000 {
+      let m = rng.mac_address_with_flags(local, multicast);
+      let e = rng.eui64_with_flags(local, multicast);
+      assert!(m[0] & 3 == flags && e[0] & 3 == flags);
+      for (i, &x) in e.iter().enumerate() { or[i] |= x; and[i] &= x; }
+    }
+    assert!(or[0] | 3 == 0xff && and[0] & 0xfc == 0);
+    assert!(or[1 ..].iter().all(|&x| x == 0xff) && and[1 ..].iter().all(|&x| x == 0));
+  }
+}
+
+#[test]
+fn test_shuffle() {
+  let mut rng = Rng::from_u64(0);
+
+  rng.shuffle::<u8>(&mut []);
+  let mut one = [7];
+  rng.shuffle(&mut one);
+  assert!(one == [7]);
+
+  // All 24 permutations of four elements are equally likely.
+
+  let mut counts = std::collections::HashMap::new();
+  for _ in 0 .. 240_000 {
+    let mut a = [0, 1, 2, 3];
+    rng.shuffle(&mut a);
+    *counts.entry(a).or_insert(0u32) += 1;
+  }
+  assert!(counts.len() == 24);
+  assert!(counts.values().all(|&c| (9_500 ..= 10_500).contains(&c)));
+
+  let mut a: Vec<u32> = (0 .. 1000).collect();
+  rng.shuffle(&mut a);
+  a.sort();
+  assert!(a.iter().copied().eq(0 .. 1000));
+}
+
+#[test]
+fn test_partial_shuffle() {
+  let mut rng = Rng::from_u64(0);
+
+  let mut a = [0, 1, 2, 3, 4];
+  let (x, y) = rng.partial_shuffle(&mut a, 0);
+  assert!(x.is_empty() && y.len() == 5);
+  let (x, y) = rng.partial_shuffle(&mut a, 5);
+  assert!(x.len() == 5 && y.is_empty());
+
+  // All 5 × 4 = 20 ordered pairs are equally likely.
+
+  let mut counts = [[0u32; 5]; 5];
+  for _ in 0 .. 200_000 {
+    let mut a = [0, 1, 2, 3, 4];
+    let (x, y) = rng.partial_shuffle(&mut a, 2);
+    counts[x[0]][x[1]] += 1;
+    let mut rest = [x[0], x[1], y[0], y[1], y[2]];
+    rest.sort();
+    assert!(rest == [0, 1, 2, 3, 4]);
+  }
+  for (i, row) in counts.iter().enumerate() {
+    for (j, &c) in row.iter().enumerate() {
+      assert!(if i == j { c == 0 } else { (9_500 ..= 10_500).contains(&c) });
+    }
+  }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_choose_multiple() {
+  let mut rng = Rng::from_u64(0);
+
+  let xs = [1, 1, 2, 3, 5, 8, 13];
+  assert!(rng.choose_multiple(&xs, 0).is_empty());
+  let mut all: Vec<_> = rng.choose_multiple(&xs, 7).into_iter().copied().collect();
+  all.sort();
+  assert!(all == xs);
+
+  let mut counts = [0u32; 10];
+  for _ in 0 .. 50_000 {
+    let chosen = rng.choose_multiple(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9], 3);
+    assert!(chosen.len() == 3);
+    assert!(chosen[0] != chosen[1] && chosen[1] != chosen[2] && chosen[0] != chosen[2]);
+    for &x in chosen { counts[x] += 1; }
+  }
+  assert!(counts.iter().all(|&c| (14_500 ..= 15_500).contains(&c)));
+}
+
+#[test]
+#[should_panic]
+fn test_partial_shuffle_too_many() {
+  let _ = Rng::from_u64(0).partial_shuffle(&mut [1, 2, 3], 4);
+}
+
+#[test]
+fn test_weighted_index() {
+  use dandelion::weighted::WeightedIndex;
+
+  let mut rng = Rng::from_u64(0);
+
+  assert!(WeightedIndex::from_buffer(&mut [][..]).is_none());
+  assert!(WeightedIndex::from_buffer(&mut [0.0, 0.0][..]).is_none());
+  assert!(WeightedIndex::from_buffer(&mut [1.0, -1.0][..]).is_none());
+  assert!(WeightedIndex::from_buffer(&mut [1.0, f64::NAN][..]).is_none());
+  assert!(WeightedIndex::from_buffer(&mut [f64::MAX, f64::MAX][..]).is_none());
+
+  let mut buf = [0.0, 1.0, 0.0, 3.0, 6.0, 0.0];
+  let w = WeightedIndex::from_buffer(&mut buf[..]).unwrap();
+  assert!(w.len() == 6 && ! w.is_empty() && w.total() == 10.0);
+
+  let mut counts = [0u32; 6];
+  for _ in 0 .. 100_000 { counts[rng.sample(&w)] += 1; }
+  assert!(counts[0] == 0 && counts[2] == 0 && counts[5] == 0);
+  assert!((9_500 ..= 10_500).contains(&counts[1]));
+  assert!((29_000 ..= 31_000).contains(&counts[3]));
+  assert!((59_000 ..= 61_000).contains(&counts[4]));
+  assert!(buf == [0.0, 1.0, 1.0, 4.0, 10.0, 10.0]);
+
+  // A sample that rounds up to the total still lands on a nonzero weight.
+
+  let w = WeightedIndex::from_buffer([1.0, 0.0]).unwrap();
+  assert!((0 .. 1_000).all(|_| rng.sample(&w) == 0));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_weighted_index_new() {
+  use dandelion::weighted::WeightedIndex;
+
+  let mut rng = Rng::from_u64(0);
+  let weights = [1.0, 2.0, 1.0];
+  let w = WeightedIndex::new(&weights).unwrap();
+  let mut counts = [0u32; 3];
+  for _ in 0 .. 40_000 { counts[rng.sample(&w)] += 1; }
+  assert!((9_500 ..= 10_500).contains(&counts[0]));
+  assert!((19_000 ..= 21_000).contains(&counts[1]));
+  assert!(WeightedIndex::new(&[]).is_none());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_weighted_alias() {
+  use dandelion::weighted::WeightedAlias;
+
+  let mut rng = Rng::from_u64(0);
+
+  assert!(WeightedAlias::new(&[]).is_none());
+  assert!(WeightedAlias::new(&[0.0]).is_none());
+  assert!(WeightedAlias::new(&[1.0, -0.5]).is_none());
+  assert!(WeightedAlias::new(&[1.0, f64::INFINITY]).is_none());
+
+  let w = WeightedAlias::new(&[0.0, 1.0, 0.0, 3.0, 6.0, 0.0]).unwrap();
+  assert!(w.len() == 6 && ! w.is_empty());
+
+  let mut counts = [0u32; 6];
+  for _ in 0 .. 100_000 { counts[rng.sample(&w)] += 1; }
+  assert!(counts[0] == 0 && counts[2] == 0 && counts[5] == 0);
+  assert!((9_500 ..= 10_500).contains(&counts[1]));
+  assert!((29_000 ..= 31_000).contains(&counts[3]));
+  assert!((59_000 ..= 61_000).contains(&counts[4]));
+
+  // Many categories with very different weights.
+
+  let weights: Vec<f64> = (1 ..= 1000).map(|k| 1.0 / k as f64).collect();
+  let total: f64 = weights.iter().sum();
+  let w = WeightedAlias::new(&weights).unwrap();
+  let n = 200_000;
+  let ones = (0 .. n).filter(|_| rng.sample(&w) == 0).count();
+  let p = 1.0 / total;
+  assert!((ones as f64 / n as f64 - p).abs() < 5.0 * (p * (1.0 - p) / n as f64).sqrt());
+}
+
+// The modules that take a generator as an argument accept any core. A
+// counting core draws the same stream as the default core, so the results
+// must match.
+
+#[cfg(feature = "graph")]
+#[test]
+fn test_graph_any_core() {
+  use dandelion::graph;
+
+  let mut a = Rng::from_u64(0).counting();
+  let mut b = Rng::from_u64(0);
+  assert!(graph::erdos_renyi(&mut a, 50, 0.1) == graph::erdos_renyi(&mut b, 50, 0.1));
+  assert!(graph::barabasi_albert(&mut a, 50, 2) == graph::barabasi_albert(&mut b, 50, 2));
+  assert!(a.outputs_drawn() > 0);
+}
+
+#[test]
+fn test_jitter_any_core() {
+  use dandelion::jitter::decorrelated_jitter;
+  use dandelion::jitter::equal_jitter;
+  use dandelion::jitter::full_jitter;
+
+  let mut a = Rng::from_u64(0).counting();
+  let mut b = Rng::from_u64(0);
+  let base = Duration::from_millis(10);
+  let cap = Duration::from_secs(10);
+  assert!(full_jitter(&mut a, base, cap, 3) == full_jitter(&mut b, base, cap, 3));
+  assert!(equal_jitter(&mut a, base, cap, 3) == equal_jitter(&mut b, base, cap, 3));
+  assert!(decorrelated_jitter(&mut a, base, cap, base) == decorrelated_jitter(&mut b, base, cap, base));
+  assert!(a.outputs_drawn() > 0);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_markov_chain_any_core() {
+  use dandelion::markov::MarkovChain;
+
+  let mut x = MarkovChain::new(&[[1.0, 1.0], [1.0, 2.0]]).unwrap();
+  let mut y = x.clone();
+  let mut a = Rng::from_u64(0).counting();
+  let mut b = Rng::from_u64(0);
+  assert!(x.step(&mut a) == y.step(&mut b));
+  assert!(x.iter(&mut a).take(100).eq(y.iter(&mut b).take(100)));
+  assert!(a.outputs_drawn() > 0);
+}
+
+#[cfg(feature = "noise")]
+#[test]
+fn test_noise_any_core() {
+  use dandelion::noise::Perlin;
+
+  let mut a = Rng::from_u64(0).counting();
+  let mut b = Rng::from_u64(0);
+  let p = Perlin::new(&mut a);
+  let q = Perlin::new(&mut b);
+  assert!(p.noise2(1.5, 2.25) == q.noise2(1.5, 2.25));
+  assert!(p.noise3(1.5, 2.25, 3.75) == q.noise3(1.5, 2.25, 3.75));
+  assert!(a.outputs_drawn() > 0);
+}
+
+#[test]
+fn test_quasi_any_core() {
+  use dandelion::quasi::Halton;
+  use dandelion::quasi::Sobol;
+
+  let mut a = Rng::from_u64(0).counting();
+  let mut b = Rng::from_u64(0);
+  assert!(Sobol::<3>::scrambled(&mut a).point(5) == Sobol::<3>::scrambled(&mut b).point(5));
+  assert!(Halton::<3>::scrambled(&mut a).point(5) == Halton::<3>::scrambled(&mut b).point(5));
+  assert!(a.outputs_drawn() > 0);
+}
+
+#[cfg(feature = "value")]
+#[test]
+fn test_value_generator_any_core() {
+  use dandelion::value::JsonWriter;
+  use dandelion::value::ValueGenerator;
+
+  let mut a = Rng::from_u64(0).counting();
+  let mut b = Rng::from_u64(0);
+  let mut x = JsonWriter::new();
+  let mut y = JsonWriter::new();
+  ValueGenerator::default().generate(&mut a, &mut x);
+  ValueGenerator::default().generate(&mut b, &mut y);
+  assert!(x.finish() == y.finish());
+  assert!(a.outputs_drawn() > 0);
+}
+
+proptest! {
+  #[test]
+  fn prop_between_in_range(seed: u64, lo: u64, hi: u64) {
+    let mut rng = Rng::from_u64(seed);
+
+    let x = rng.between_u64(lo, hi);
+    prop_assert!(x.wrapping_sub(lo) <= hi.wrapping_sub(lo));
+
+    let x = rng.between_i64(lo as i64, hi as i64);
+    prop_assert!((x as u64).wrapping_sub(lo) <= hi.wrapping_sub(lo));
+
+    let (lo, hi) = (lo as u32, hi as u32);
+
+    let x = rng.between_u32(lo, hi);
+    prop_assert!(x.wrapping_sub(lo) <= hi.wrapping_sub(lo));
+
+    let x = rng.between_i32(lo as i32, hi as i32);
+    prop_assert!((x as u32).wrapping_sub(lo) <= hi.wrapping_sub(lo));
+  }
+
+  #[test]
+  fn prop_between_wraps_around(seed: u64, lo: u64, hi: u64) {
+    prop_assume!(hi < lo);
+
+    let mut rng = Rng::from_u64(seed);
+
+    let x = rng.between_u64(lo, hi);
+    prop_assert!(lo <= x || x <= hi);
+
+    let x = rng.between_i64(lo as i64, hi as i64);
+    prop_assert!(lo as i64 <= x || x <= hi as i64);
+  }
+
+  #[test]
+  fn prop_between_wraps_around_32(seed: u64, lo: u32, hi: u32) {
+    prop_assume!(hi < lo);
+
+    let mut rng = Rng::from_u64(seed);
+
+    let x = rng.between_u32(lo, hi);
+    prop_assert!(lo <= x || x <= hi);
+
+    let x = rng.between_i32(lo as i32, hi as i32);
+    prop_assert!(lo as i32 <= x || x <= hi as i32);
+  }
+
+  #[test]
+  fn prop_between_hits_endpoints(seed: u64, lo: u64, span in 0_u64 .. 4) {
+    let mut rng = Rng::from_u64(seed);
+    let hi = lo.wrapping_add(span);
+
+    let xs: Vec<u64> = (0 .. 256).map(|_| rng.between_u64(lo, hi)).collect();
+    prop_assert!(xs.contains(&lo) && xs.contains(&hi));
+
+    let xs: Vec<i64> = (0 .. 256).map(|_| rng.between_i64(lo as i64, hi as i64)).collect();
+    prop_assert!(xs.contains(&(lo as i64)) && xs.contains(&(hi as i64)));
+
+    let (lo, hi) = (lo as u32, hi as u32);
+
+    let xs: Vec<u32> = (0 .. 256).map(|_| rng.between_u32(lo, hi)).collect();
+    prop_assert!(xs.contains(&lo) && xs.contains(&hi));
+
+    let xs: Vec<i32> = (0 .. 256).map(|_| rng.between_i32(lo as i32, hi as i32)).collect();
+    prop_assert!(xs.contains(&(lo as i32)) && xs.contains(&(hi as i32)));
+  }
+}
@@ -0,0 +1,66 @@
+//! Statistical helpers for the tests.
+
+/// Computes the Kolmogorov-Smirnov statistic of the samples against the given
+/// cumulative distribution function.
+
+pub(crate) fn ks<F>(samples: &[f64], cdf: F) -> f64
+where
+  F: Fn(f64) -> f64
+{
+  let mut samples = samples.to_vec();
+  samples.sort_by(f64::total_cmp);
+  let n = samples.len() as f64;
+  let mut d = 0f64;
+  for (i, &x) in samples.iter().enumerate() {
+    let p = cdf(x);
+    d = d.max(p - i as f64 / n);
+    d = d.max((i + 1) as f64 / n - p);
+  }
+  d
+}
+
+/// Computes the Kolmogorov-Smirnov statistic of the samples against the
+/// uniform distribution over [0, 1].
+
+pub(crate) fn ks_uniform(samples: &[f64]) -> f64 {
+  ks(samples, |x| x.clamp(0.0, 1.0))
+}
+
+/// The critical value of the Kolmogorov-Smirnov statistic for `n` samples at
+/// significance level 10⁻⁶.
+///
+/// This uses the asymptotic distribution, which is accurate for the sample
+/// sizes used in the tests.
+
+pub(crate) fn ks_critical_value(n: usize) -> f64 {
+  (2e6f64.ln() / 2.0).sqrt() / (n as f64).sqrt()
+}
+
+/// Computes the Pearson correlation coefficient of the paired samples.
+
+pub(crate) fn correlation(xs: &[f64], ys: &[f64]) -> f64 {
+  assert!(xs.len() == ys.len());
+  let n = xs.len() as f64;
+  let mx = xs.iter().sum::<f64>() / n;
+  let my = ys.iter().sum::<f64>() / n;
+  let mut sxy = 0.0;
+  let mut sxx = 0.0;
+  let mut syy = 0.0;
+  for (&x, &y) in xs.iter().zip(ys) {
+    sxy += (x - mx) * (y - my);
+    sxx += (x - mx) * (x - mx);
+    syy += (y - my) * (y - my);
+  }
+  sxy / (sxx * syy).sqrt()
+}
+
+/// Computes the z-score of the number of bit positions at which the paired
+/// words agree, which for independent uniform words is binomially distributed
+/// with success probability 1/2.
+
+pub(crate) fn bit_agreement_z(xs: &[u64], ys: &[u64]) -> f64 {
+  assert!(xs.len() == ys.len());
+  let n = 64.0 * xs.len() as f64;
+  let k = xs.iter().zip(ys).map(|(x, y)| (!(x ^ y)).count_ones() as f64).sum::<f64>();
+  (k - n / 2.0) / (n / 4.0).sqrt()
+}